@@ -1,5 +1,5 @@
 use std::env;
-use std::fs::File;
+use std::fs::{self, File};
 use std::io::{BufWriter, Write};
 use std::path::Path;
 
@@ -21,6 +21,74 @@ fn main() {
     )
     .unwrap();
     writeln!(&mut file, ";").unwrap();
+
+    let yml = fs::read_to_string(LANGUAGES).unwrap();
+    let languages = languages(&yml);
+    let mut color_map = phf_codegen::Map::new();
+    // keyed by the lowercase name, so a language is found regardless of case
+    for (name, color) in &languages {
+        color_map.entry(name.as_str(), &format!("{color:?}"));
+    }
+
+    write!(
+        &mut file,
+        "pub static LANGUAGE_COLORS: phf::Map<&'static str, &'static str> = {}",
+        color_map.build()
+    )
+    .unwrap();
+    writeln!(&mut file, ";").unwrap();
+
+    let snapshot = snapshot();
+    match &snapshot {
+        Some(path) => writeln!(
+            &mut file,
+            "pub static SNAPSHOT: Option<&str> = Some(include_str!({path:?}));"
+        ),
+        None => writeln!(&mut file, "pub static SNAPSHOT: Option<&str> = None;"),
+    }
+    .unwrap();
+
+    println!("cargo::rerun-if-changed=build.rs");
+    println!("cargo::rerun-if-changed={LANGUAGES}");
+    println!("cargo::rerun-if-changed={CONFIG}");
+    if let Some(path) = snapshot {
+        println!("cargo::rerun-if-changed={path}");
+    }
+}
+
+const CONFIG: &str = "config.toml";
+
+/// The full path of the snapshot of projects in the config, if there is one, to embed
+fn snapshot() -> Option<String> {
+    let config = fs::read_to_string(CONFIG).unwrap();
+    let config = toml::from_str::<toml::Table>(&config).unwrap();
+    let path = config.get("snapshot")?.get("path")?.as_str()?;
+
+    let path = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join(path);
+    assert!(path.is_file(), "no snapshot at {}", path.display());
+    Some(path.display().to_string())
+}
+
+/// Colors of languages, in the format of linguist's `languages.yml`
+const LANGUAGES: &str = "languages.yml";
+
+/// Reads the lowercase name and color of each language,
+/// from a `Name:` line and the `  color:` under it
+fn languages(yml: &str) -> Vec<(String, String)> {
+    let mut languages = Vec::new();
+    let mut name = None;
+    for line in yml.lines() {
+        if let Some(color) = line.strip_prefix("  color:") {
+            if let Some(name) = name.take() {
+                languages.push((name, color.trim().trim_matches('"').to_owned()));
+            }
+        } else if !line.starts_with([' ', '#', '-'])
+            && let Some(key) = line.strip_suffix(':')
+        {
+            name = Some(key.trim_matches('"').to_lowercase());
+        }
+    }
+    languages
 }
 
 static ICONS: [&str; 5351] = [
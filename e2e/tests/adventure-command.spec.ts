@@ -0,0 +1,60 @@
+import { expect } from '@playwright/test';
+
+import { test } from './fixtures/input';
+
+test.describe('adventure command', () => {
+	test('starts on the porch and moves between rooms', async ({
+		page,
+		inputElements,
+	}) => {
+		const { input } = inputElements;
+
+		await input.focus();
+		await page.keyboard.type('adventure');
+		await page.keyboard.press('Enter');
+
+		const screen = page.getByTestId('screen');
+		await expect(screen).toContainText('Front Porch');
+		await expect(input).not.toBeAttached();
+
+		await page.keyboard.type('go north');
+		await page.keyboard.press('Enter');
+		await expect(screen).toContainText('Hallway');
+		await expect(screen).toContainText('moves: 1');
+	});
+
+	test('saves to a slot in localStorage', async ({ page, inputElements }) => {
+		const { input } = inputElements;
+
+		await input.focus();
+		await page.keyboard.type('adventure');
+		await page.keyboard.press('Enter');
+
+		await page.keyboard.type('save 2');
+		await page.keyboard.press('Enter');
+		await expect(page.getByTestId('screen')).toContainText('Saved to slot 2.');
+
+		const stored = await page.evaluate(() =>
+			localStorage.getItem('wcli:adventure:2'),
+		);
+		expect(JSON.parse(stored ?? '{}')).toMatchObject({ room: 'porch' });
+	});
+
+	test('leaves a summary and restores the prompt on quit', async ({
+		page,
+		inputElements,
+	}) => {
+		const { input } = inputElements;
+
+		await input.focus();
+		await page.keyboard.type('adventure');
+		await page.keyboard.press('Enter');
+
+		await page.keyboard.type('quit');
+		await page.keyboard.press('Enter');
+
+		await expect(page.getByTestId('screen')).not.toBeAttached();
+		await expect(page.getByText('adventure: left after 0 moves')).toBeVisible();
+		await expect(page.getByRole('textbox')).toBeFocused();
+	});
+});
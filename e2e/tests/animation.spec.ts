@@ -0,0 +1,64 @@
+import { expect } from '@playwright/test';
+
+import { test } from './fixtures/input';
+
+test.describe('animations', () => {
+	test('matrix plays full-screen until Ctrl+C', async ({
+		page,
+		inputElements,
+	}) => {
+		const { input } = inputElements;
+
+		await input.focus();
+		await page.keyboard.type('matrix');
+		await page.keyboard.press('Enter');
+
+		const screen = page.getByTestId('screen');
+		await expect(screen).toBeVisible();
+		await expect(input).not.toBeAttached();
+
+		await page.keyboard.press('Control+c');
+		await expect(screen).not.toBeAttached();
+		await expect(page.getByRole('textbox')).toBeFocused();
+	});
+
+	test('sl exits on its own once the train has passed', async ({
+		page,
+		inputElements,
+	}) => {
+		const { input } = inputElements;
+
+		await input.focus();
+		await page.keyboard.type('sl');
+		await page.keyboard.press('Enter');
+
+		const screen = page.getByTestId('screen');
+		await expect(screen).toBeVisible();
+		await expect(screen).not.toBeAttached({ timeout: 15000 });
+		await expect(page.getByRole('textbox')).toBeFocused();
+	});
+
+	test.describe('with reduced motion', () => {
+		test.use({ contextOptions: { reducedMotion: 'reduce' } });
+
+		test('sl shows a still frame inline', async ({ page, inputElements }) => {
+			const { input } = inputElements;
+
+			await input.focus();
+			await page.keyboard.type('sl');
+			await page.keyboard.press('Enter');
+
+			await expect(page.getByTestId('still')).toContainText('_D _|');
+			await expect(page.getByTestId('screen')).not.toBeAttached();
+			await expect(page.getByRole('textbox')).toBeFocused();
+		});
+
+		test('banner is shown without typing', async ({ page }) => {
+			await page.goto('/');
+
+			await expect(page.getByTestId('banner').locator('.invisible')).toHaveText(
+				Array(11).fill(''),
+			);
+		});
+	});
+});
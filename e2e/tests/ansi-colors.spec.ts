@@ -0,0 +1,23 @@
+import { expect } from '@playwright/test';
+
+import { test } from './fixtures/input';
+
+test.describe('ansi colors', () => {
+	test('renders SGR sequences as theme colors', async ({ page, inputElements }) => {
+		await inputElements.input.focus();
+		await page.keyboard.type('echo -e "\\e[31mred\\e[0m plain"');
+		await page.keyboard.press('Enter');
+
+		const red = page.locator('span', { hasText: /^red$/ });
+		await expect(red).toHaveAttribute('style', /var\(--color-red-theme\)/);
+		await expect(page.getByText(/\\e\[/)).not.toBeAttached();
+	});
+
+	test('renders bold text', async ({ page, inputElements }) => {
+		await inputElements.input.focus();
+		await page.keyboard.type('echo -e "\\e[1mbold\\e[0m"');
+		await page.keyboard.press('Enter');
+
+		await expect(page.locator('span.font-bold', { hasText: 'bold' })).toBeVisible();
+	});
+});
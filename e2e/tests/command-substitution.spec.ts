@@ -0,0 +1,56 @@
+import { expect } from '@playwright/test';
+
+import { test } from './fixtures/input';
+
+const URL = 'https://api.github.com/users/*/repos**';
+
+test.describe('command substitution', () => {
+	test('splices output into the arguments', async ({
+		page,
+		inputElements,
+	}) => {
+		await inputElements.input.focus();
+		await page.keyboard.type('echo "I have $(echo 3) repos"');
+		await page.keyboard.press('Enter');
+
+		await expect(
+			page.getByText('I have 3 repos', { exact: true }),
+		).toBeVisible();
+	});
+
+	test('waits for commands that load', async ({ page, inputElements }) => {
+		await page.route(URL, async route => {
+			await route.fulfill({
+				status: 200,
+				json: [
+					{
+						name: 'seaq',
+						html_url: 'https://github.com/nt54hamnghi/seaq',
+						description: 'About seaq',
+						stargazers_count: 5,
+					},
+				],
+			});
+		});
+
+		await inputElements.input.focus();
+		await page.keyboard.type('x=$(projects); echo "got: $x"');
+		await page.keyboard.press('Enter');
+
+		await expect(page.getByText(/^got: [\s\S]*seaq/)).toBeVisible();
+		await expect(page.getByRole('table')).not.toBeAttached();
+	});
+
+	test('is interrupted with the command', async ({ page, inputElements }) => {
+		await page.route(URL, async () => {
+			// never respond
+		});
+
+		await inputElements.input.focus();
+		await page.keyboard.type('echo $(projects)');
+		await page.keyboard.press('Enter');
+		await page.keyboard.press('Control+c');
+
+		await expect(page.getByText('^C', { exact: true })).toBeVisible();
+	});
+});
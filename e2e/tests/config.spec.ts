@@ -48,12 +48,12 @@ test.describe('config integration', () => {
 
     test('projects command uses config for API URL and displays in-progress repos', async ({ page, config, inputElements }) => {
         const { input } = inputElements;
-        const expectedUrl = `https://api.github.com/users/${config.github.username}/repos`;
+        const expectedUrl = `https://api.github.com/users/${config.github.username}/repos?per_page=100`;
 
         // Intercept API calls to verify URL and return empty array for clean testing
         let actualUrl = '';
-        // Pattern matches: https://api.github.com/users/ANY_USERNAME/repos
-        await page.route('https://api.github.com/users/*/repos', async (route) => {
+        // Pattern matches: https://api.github.com/users/ANY_USERNAME/repos, and its query
+        await page.route('https://api.github.com/users/*/repos**', async (route) => {
             actualUrl = route.request().url();
             // Return empty array so we only see in-progress repos
             await route.fulfill({ status: 200, json: [] });
@@ -0,0 +1,85 @@
+import { expect } from '@playwright/test';
+
+import { test } from './fixtures/input';
+
+test.describe('edit command', () => {
+	test('opens the editor and hides the prompt', async ({
+		page,
+		inputElements,
+	}) => {
+		const { input } = inputElements;
+
+		await input.focus();
+		await page.keyboard.type('edit notes.txt');
+		await page.keyboard.press('Enter');
+
+		await expect(page.getByTestId('editor')).toBeVisible();
+		await expect(page.getByTestId('editor-path')).toHaveText('notes.txt');
+		await expect(page.getByTestId('editor-status')).toHaveText(
+			'[ New File ]',
+		);
+		await expect(input).not.toBeAttached();
+	});
+
+	test('saves to localStorage and restores the prompt on exit', async ({
+		page,
+		inputElements,
+	}) => {
+		const { input } = inputElements;
+
+		await input.focus();
+		await page.keyboard.type('edit notes.txt');
+		await page.keyboard.press('Enter');
+
+		await page.keyboard.type('hello');
+		await page.keyboard.press('Enter');
+		await page.keyboard.type('world');
+		await page.keyboard.press('Control+s');
+
+		await expect(page.getByTestId('editor-status')).toHaveText(
+			'[ Wrote 2 lines ]',
+		);
+
+		const stored = await page.evaluate(() => localStorage.getItem('wcli:fs'));
+		expect(JSON.parse(stored ?? '{}')).toEqual({
+			files: { 'notes.txt': 'hello\nworld' },
+		});
+
+		await page.keyboard.press('Control+x');
+		await expect(page.getByTestId('editor')).not.toBeAttached();
+		await expect(page.getByRole('textbox')).toBeFocused();
+	});
+
+	test('asks before exiting with unsaved changes', async ({
+		page,
+		inputElements,
+	}) => {
+		const { input } = inputElements;
+
+		await input.focus();
+		await page.keyboard.type('edit notes.txt');
+		await page.keyboard.press('Enter');
+
+		await page.keyboard.type('draft');
+		await page.keyboard.press('Control+x');
+		await expect(page.getByTestId('editor-status')).toContainText(
+			'Save modified buffer?',
+		);
+
+		await page.keyboard.press('n');
+		await expect(page.getByTestId('editor')).not.toBeAttached();
+
+		const stored = await page.evaluate(() => localStorage.getItem('wcli:fs'));
+		expect(JSON.parse(stored ?? '{}')).toEqual({ files: {} });
+	});
+
+	test('reports a missing file operand', async ({ page, inputElements }) => {
+		const { input } = inputElements;
+
+		await input.focus();
+		await page.keyboard.type('edit');
+		await page.keyboard.press('Enter');
+
+		await expect(page.getByText('edit: missing file operand')).toBeVisible();
+	});
+});
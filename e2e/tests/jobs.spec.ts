@@ -0,0 +1,147 @@
+import { expect } from '@playwright/test';
+
+import { test } from './fixtures/input';
+
+const URL = 'https://api.github.com/users/*/repos**';
+
+const REPO_DATA = [
+	{
+		name: 'seaq',
+		html_url: 'https://github.com/nt54hamnghi/seaq',
+		description: 'About seaq',
+		stargazers_count: 5,
+	},
+];
+
+test.describe('background jobs', () => {
+	test('runs a job without blocking the prompt', async ({
+		page,
+		inputElements,
+	}) => {
+		const { input } = inputElements;
+		let respond = () => {};
+		await page.route(URL, route => {
+			respond = () => route.fulfill({ status: 200, json: REPO_DATA });
+		});
+
+		await input.focus();
+		await page.keyboard.type('projects &');
+		await page.keyboard.press('Enter');
+
+		await expect(page.getByText('[1] projects')).toBeVisible();
+		await expect(page.getByRole('textbox')).toBeFocused();
+		// the output stays out of sight until brought to the foreground
+		await expect(page.getByText('One moment...')).not.toBeVisible();
+
+		await page.keyboard.type('jobs');
+		await page.keyboard.press('Enter');
+		await expect(page.getByTestId('jobs')).toHaveText('[1]+ Running projects');
+
+		respond();
+		await expect(page.getByTestId('notice')).toHaveText('[1]+ Done projects');
+
+		await page.keyboard.type('fg %1');
+		await page.keyboard.press('Enter');
+		await expect(page.getByText('seaq')).toBeVisible();
+		await expect(page.getByRole('textbox')).toBeFocused();
+	});
+
+	test('jobs reports a finished job once, then forgets it', async ({
+		page,
+		inputElements,
+	}) => {
+		const { input } = inputElements;
+		await page.route(URL, route =>
+			route.fulfill({ status: 200, json: REPO_DATA }),
+		);
+
+		await input.focus();
+		await page.keyboard.type('projects &');
+		await page.keyboard.press('Enter');
+		await expect(page.getByTestId('notice')).toHaveText('[1]+ Done projects');
+
+		await page.keyboard.type('jobs');
+		await page.keyboard.press('Enter');
+		await expect(page.getByTestId('jobs')).toHaveText('[1]+ Done projects');
+
+		await page.keyboard.type('jobs');
+		await page.keyboard.press('Enter');
+		await expect(page.getByTestId('jobs').last()).toHaveText('');
+
+		await page.keyboard.type('fg %1');
+		await page.keyboard.press('Enter');
+		await expect(page.getByText('fg: %1: no such job')).toBeVisible();
+	});
+
+	test('reports the status of a job that failed', async ({
+		page,
+		inputElements,
+	}) => {
+		await page.route(URL, route =>
+			route.fulfill({ status: 404, json: { message: 'Not Found' } }),
+		);
+
+		await inputElements.input.focus();
+		await page.keyboard.type('projects &');
+		await page.keyboard.press('Enter');
+
+		await expect(page.getByTestId('notice')).toHaveText(
+			'[1]+ Exit 1 projects',
+		);
+	});
+
+	test('kill cancels a running job', async ({ page, inputElements }) => {
+		const { input } = inputElements;
+		await page.route(URL, () => {});
+
+		await input.focus();
+		await page.keyboard.type('projects &');
+		await page.keyboard.press('Enter');
+		await page.keyboard.type('kill %1');
+		await page.keyboard.press('Enter');
+
+		await expect(page.getByText('[1]+ Terminated projects')).toBeVisible();
+		await expect(page.getByTestId('notice')).not.toBeAttached();
+
+		await page.keyboard.type('fg %1');
+		await page.keyboard.press('Enter');
+		await expect(page.getByText('fg: %1: no such job')).toBeVisible();
+	});
+
+	test('fg waits for a running job and Ctrl+C interrupts it', async ({
+		page,
+		inputElements,
+	}) => {
+		const { input } = inputElements;
+		await page.route(URL, () => {});
+
+		await input.focus();
+		await page.keyboard.type('projects &');
+		await page.keyboard.press('Enter');
+		await page.keyboard.type('fg');
+		await page.keyboard.press('Enter');
+
+		await expect(page.getByText('One moment...')).toBeVisible();
+		await expect(page.getByRole('textbox')).not.toBeAttached();
+
+		await page.keyboard.press('Control+c');
+		await expect(page.getByTestId('interrupted')).toHaveText('^C');
+		await expect(page.getByRole('textbox')).toBeFocused();
+	});
+
+	test('refuses to run full-screen commands in the background', async ({
+		page,
+		inputElements,
+	}) => {
+		const { input } = inputElements;
+
+		await input.focus();
+		await page.keyboard.type('snake &');
+		await page.keyboard.press('Enter');
+
+		await expect(
+			page.getByText('snake: cannot run in the background'),
+		).toBeVisible();
+		await expect(page.getByTestId('screen')).not.toBeAttached();
+	});
+});
@@ -0,0 +1,58 @@
+import { expect } from '@playwright/test';
+
+import { test } from './fixtures/input';
+
+const URL = 'https://api.github.com/users/*/repos**';
+
+test.describe('jq command', () => {
+	test('queries the projects as JSON', async ({ page, inputElements }) => {
+		await page.route(URL, async route => {
+			await route.fulfill({
+				status: 200,
+				json: [
+					{
+						name: 'seaq',
+						html_url: 'https://github.com/nt54hamnghi/seaq',
+						description: 'About seaq',
+						stargazers_count: 5,
+					},
+					{
+						name: 'wcli',
+						html_url: 'https://github.com/nt54hamnghi/wcli',
+						description: 'About wcli',
+						stargazers_count: 12,
+					},
+				],
+			});
+		});
+
+		await inputElements.input.focus();
+		await page.keyboard.type(
+			"projects --json | jq -r '.[] | select(.stargazers_count > 10) | .name'",
+		);
+		await page.keyboard.press('Enter');
+
+		await expect(page.getByText('wcli', { exact: true })).toBeVisible();
+		await expect(page.getByText('seaq', { exact: true })).not.toBeAttached();
+	});
+
+	test('pretty-prints in color', async ({ page, inputElements }) => {
+		await inputElements.input.focus();
+		await page.keyboard.type(`echo '{"a": [1, "b"]}' | jq .a`);
+		await page.keyboard.press('Enter');
+
+		const string = page.getByText('"b"', { exact: true });
+		await expect(string).toBeVisible();
+		await expect(string).toHaveCSS('color', /.+/);
+	});
+
+	test('reports what it cannot index', async ({ page, inputElements }) => {
+		await inputElements.input.focus();
+		await page.keyboard.type(`echo '[1]' | jq .a`);
+		await page.keyboard.press('Enter');
+
+		await expect(
+			page.getByText('jq: error: Cannot index array with "a"'),
+		).toBeVisible();
+	});
+});
@@ -0,0 +1,34 @@
+import { expect } from '@playwright/test';
+
+import { test } from './fixtures/input';
+
+test.describe('pipelines', () => {
+	test('sorts and counts piped lines', async ({ page, inputElements }) => {
+		await inputElements.input.focus();
+		await page.keyboard.type('printf "b\\na\\nb\\n" | sort | uniq -c');
+		await page.keyboard.press('Enter');
+
+		await expect(page.getByText('2 b', { exact: false })).toBeVisible();
+		await expect(page.getByText('1 a', { exact: false })).toBeVisible();
+	});
+
+	test('shows only the output of the last command', async ({
+		page,
+		inputElements,
+	}) => {
+		await inputElements.input.focus();
+		await page.keyboard.type('help | head -n 1 | wc -l');
+		await page.keyboard.press('Enter');
+
+		await expect(page.getByText('1', { exact: true })).toBeVisible();
+		await expect(page.getByTestId('help-commands')).not.toBeAttached();
+	});
+
+	test('cuts fields and translates them', async ({ page, inputElements }) => {
+		await inputElements.input.focus();
+		await page.keyboard.type('echo a:b:c | cut -d : -f 2- | tr a-z A-Z');
+		await page.keyboard.press('Enter');
+
+		await expect(page.getByText('B:C', { exact: true })).toBeVisible();
+	});
+});
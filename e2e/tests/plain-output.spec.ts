@@ -0,0 +1,68 @@
+import { expect } from '@playwright/test';
+
+import { test } from './fixtures/input';
+
+const URL = 'https://api.github.com/users/*/repos**';
+
+test.describe('plain output', () => {
+	test('draws projects as a box table', async ({ page, inputElements }) => {
+		await page.route(URL, async route => {
+			await route.fulfill({
+				status: 200,
+				json: [
+					{
+						name: 'seaq',
+						html_url: 'https://github.com/nt54hamnghi/seaq',
+						description: 'About seaq',
+						stargazers_count: 5,
+					},
+				],
+			});
+		});
+
+		await inputElements.input.focus();
+		await page.keyboard.type('projects --plain');
+		await page.keyboard.press('Enter');
+
+		const table = page.locator('pre').filter({ hasText: '┌' });
+		await expect(table).toContainText('│ seaq');
+		await expect(page.getByRole('table')).not.toBeAttached();
+	});
+
+	test('works with any command with more than text', async ({ page, inputElements }) => {
+		await inputElements.input.focus();
+		await page.keyboard.type('help --plain');
+		await page.keyboard.press('Enter');
+
+		await expect(page.locator('pre').filter({ hasText: 'Commands:' })).toBeVisible();
+	});
+
+	test('is taken after operands too', async ({ page, inputElements }) => {
+		await inputElements.input.focus();
+		await page.keyboard.type('help echo --plain');
+		await page.keyboard.press('Enter');
+
+		await expect(
+			page.locator('pre').filter({ hasText: 'echo - display a line of text' }),
+		).toBeVisible();
+	});
+
+	test('lists the stack with bullets', async ({ page, inputElements }) => {
+		await inputElements.input.focus();
+		await page.keyboard.type('stack --plain');
+		await page.keyboard.press('Enter');
+
+		await expect(page.locator('pre').filter({ hasText: '• ' })).toBeVisible();
+	});
+
+	test('is an argument like any other to text commands', async ({
+		page,
+		inputElements,
+	}) => {
+		await inputElements.input.focus();
+		await page.keyboard.type('echo --plain');
+		await page.keyboard.press('Enter');
+
+		await expect(page.getByText('--plain', { exact: true })).toBeVisible();
+	});
+});
@@ -0,0 +1,29 @@
+import { expect } from '@playwright/test';
+
+import { test } from './fixtures/input';
+
+test.describe('printf command', () => {
+	test('reuses the format for every argument', async ({
+		page,
+		inputElements,
+	}) => {
+		const { input } = inputElements;
+
+		await input.focus();
+		await page.keyboard.type('printf "%-5s|%03d\\n" ab 7 cd 42');
+		await page.keyboard.press('Enter');
+
+		await expect(page.getByText('ab   |007', { exact: true })).toBeVisible();
+		await expect(page.getByText('cd   |042', { exact: true })).toBeVisible();
+	});
+
+	test('reports an invalid number', async ({ page, inputElements }) => {
+		const { input } = inputElements;
+
+		await input.focus();
+		await page.keyboard.type('printf %d abc');
+		await page.keyboard.press('Enter');
+
+		await expect(page.getByText('printf: abc: invalid number')).toBeVisible();
+	});
+});
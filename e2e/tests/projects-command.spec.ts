@@ -2,7 +2,7 @@ import { expect } from '@playwright/test';
 
 import { test } from './fixtures/input';
 
-const URL = 'https://api.github.com/users/*/repos';
+const URL = 'https://api.github.com/users/*/repos**';
 
 test.describe('projects command', () => {
 	const REPO_DATA = [
@@ -11,12 +11,18 @@ test.describe('projects command', () => {
 			html_url: 'https://github.com/nt54hamnghi/seaq',
 			description: 'About seaq',
 			stargazers_count: 5,
+			forks: 2,
+			language: 'Rust',
+			updated_at: '2025-05-01T00:00:00Z',
 		},
 		{
 			name: 'sublist3r-rs',
 			html_url: 'https://github.com/nt54hamnghi/sublist3r-rs',
 			description: 'About sublist3r-rs',
 			stargazers_count: 10,
+			forks: 1,
+			language: 'Go',
+			updated_at: '2025-04-01T00:00:00Z',
 		},
 	];
 
@@ -40,7 +46,9 @@ test.describe('projects command', () => {
 			await expect(
 				page.getByText('error: failed to load project data')
 			).toBeVisible();
-			await expect(page.getByText('try again later')).toBeVisible();
+			await expect(
+				page.getByText('check your connection, then try again')
+			).toBeVisible();
 
 			// Verify no table appears
 			await expect(page.getByRole('table')).not.toBeVisible();
@@ -68,13 +76,78 @@ test.describe('projects command', () => {
 
 			// Verify error message appears (from timeout)
 			await expect(
-				page.getByText('error: failed to load project data')
+				page.getByText('error: failed to load project data: the request timed out')
 			).toBeVisible({ timeout: 10000 });
-			await expect(page.getByText('try again later')).toBeVisible();
+			await expect(page.getByText('try again in a moment')).toBeVisible();
 
 			// Verify no table appears
 			await expect(page.getByRole('table')).not.toBeVisible();
 		});
+
+		test('tells when the rate limit resets', async ({ page, inputElements }) => {
+			const reset = Math.floor(Date.now() / 1000) + 10 * 60;
+			await page.route(URL, async route => {
+				await route.fulfill({
+					status: 403,
+					headers: {
+						'x-ratelimit-remaining': '0',
+						'x-ratelimit-reset': String(reset),
+						'access-control-expose-headers':
+							'x-ratelimit-remaining, x-ratelimit-reset',
+					},
+					json: { message: 'API rate limit exceeded' },
+				});
+			});
+
+			await inputElements.input.focus();
+			await page.keyboard.type('projects --verbose');
+			await page.keyboard.press('Enter');
+
+			await expect(
+				page.getByText('the rate limit was hit, it resets in 10 minutes')
+			).toBeVisible();
+			await expect(page.getByText(/GET https:\/\/api\.github\.com.*: HTTP 403/)).toBeVisible();
+			await expect(page.getByText('try again once it resets')).toBeVisible();
+		});
+
+		test('tells an unknown user apart', async ({ page, inputElements }) => {
+			await page.route(URL, async route => {
+				await route.fulfill({ status: 404, json: { message: 'Not Found' } });
+			});
+
+			await inputElements.input.focus();
+			await page.keyboard.type('projects');
+			await page.keyboard.press('Enter');
+
+			await expect(
+				page.getByText('error: failed to load project data: not found')
+			).toBeVisible();
+			await expect(page.getByText('check the username in the config')).toBeVisible();
+			await expect(page.getByText('HTTP 404')).not.toBeAttached();
+		});
+	});
+
+	test('Ctrl+C interrupts loading and restores the prompt', async ({
+		page,
+		inputElements,
+	}) => {
+		const { input } = inputElements;
+
+		// never respond, so the command stays pending until interrupted
+		await page.route(URL, () => {});
+
+		await input.focus();
+		await page.keyboard.type('projects');
+		await page.keyboard.press('Enter');
+
+		await expect(page.getByText('One moment...')).toBeVisible();
+		await expect(page.getByRole('textbox')).not.toBeAttached();
+
+		await page.keyboard.press('Control+c');
+
+		await expect(page.getByTestId('interrupted')).toHaveText('^C');
+		await expect(page.getByText('One moment...')).not.toBeAttached();
+		await expect(page.getByRole('textbox')).toBeFocused();
 	});
 
 	test.describe('makes API calls and', () => {
@@ -134,6 +207,116 @@ test.describe('projects command', () => {
 				await expect(page.getByRole('table')).not.toBeVisible();
 			});
 		});
+
+		[
+			{ args: '--sort stars', names: ['sublist3r-rs', 'seaq'] },
+			{ args: '--sort forks', names: ['seaq', 'sublist3r-rs'] },
+			{ args: '--sort name --reverse', names: ['sublist3r-rs', 'seaq'] },
+			{ args: '--lang rust', names: ['seaq'] },
+			{ args: '--sort stars --limit 1', names: ['sublist3r-rs'] },
+		].forEach(({ args, names }) => {
+			test(`lists projects with ${args}`, async ({ page, inputElements }) => {
+				const { input } = inputElements;
+
+				await input.focus();
+				await page.keyboard.type(`projects ${args} -o csv`);
+				await page.keyboard.press('Enter');
+
+				const output = page.getByTestId('projects-csv');
+				const rows = names.map(name => `${name},public`).join('\n');
+				await expect(output).toContainText(rows);
+				for (const { name } of REPO_DATA.filter(r => !names.includes(r.name))) {
+					await expect(output).not.toContainText(`${name},`);
+				}
+			});
+		});
+
+		[
+			['yaml', 'name: seaq'],
+			['csv', 'seaq,public,About seaq'],
+			['md', '| seaq | public | About seaq |'],
+		].forEach(([format, text]) => {
+			test(`displays ${format} format with -o ${format}`, async ({
+				page,
+				inputElements,
+			}) => {
+				const { input } = inputElements;
+
+				await input.focus();
+				await page.keyboard.type(`projects -o ${format}`);
+				await page.keyboard.press('Enter');
+
+				const output = page.getByTestId(
+					format === 'md' ? 'projects-markdown' : `projects-${format}`
+				);
+				await expect(output).toContainText(text);
+				await expect(page.getByRole('table')).not.toBeVisible();
+			});
+		});
+	});
+
+	test('follows the pages of repositories', async ({ page, inputElements }) => {
+		await page.route(URL, async route => {
+			const url = route.request().url();
+			if (url.includes('page=2')) {
+				await route.fulfill({ status: 200, json: [REPO_DATA[1]] });
+				return;
+			}
+
+			const next = `${url}&page=2`;
+			await route.fulfill({
+				status: 200,
+				headers: {
+					link: `<${next}>; rel="next", <${next}>; rel="last"`,
+					'access-control-expose-headers': 'link',
+				},
+				json: [REPO_DATA[0]],
+			});
+		});
+
+		await inputElements.input.focus();
+		await page.keyboard.type('projects -o csv');
+		await page.keyboard.press('Enter');
+
+		const output = page.getByTestId('projects-csv');
+		await expect(output).toContainText('seaq,public');
+		await expect(output).toContainText('sublist3r-rs,public');
+	});
+
+	test.describe('caches projects and', () => {
+		test.beforeEach(async ({ page, inputElements }) => {
+			await page.route(URL, async route => {
+				await route.fulfill({ status: 200, json: REPO_DATA });
+			});
+
+			await inputElements.input.focus();
+			await page.keyboard.type('projects -o csv');
+			await page.keyboard.press('Enter');
+			await expect(page.getByTestId('projects-csv')).toContainText('seaq');
+
+			await page.unroute(URL);
+			await page.route(URL, async route => {
+				await route.abort('failed');
+			});
+		});
+
+		test('shows them without the network', async ({ page }) => {
+			await page.keyboard.type('clear');
+			await page.keyboard.press('Enter');
+			await page.keyboard.type('projects -o csv');
+			await page.keyboard.press('Enter');
+
+			await expect(page.getByTestId('projects-csv')).toContainText('seaq');
+		});
+
+		test('fetches them again with --refresh', async ({ page }) => {
+			await page.keyboard.type('projects --refresh');
+			await page.keyboard.press('Enter');
+
+			await expect(
+				page.getByText('error: failed to load project data')
+			).toBeVisible();
+		});
 	});
 
 	['-u', '--unknown'].forEach(invalidFlag => {
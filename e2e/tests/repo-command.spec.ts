@@ -0,0 +1,104 @@
+import { expect } from '@playwright/test';
+
+import { test } from './fixtures/input';
+
+const REPO_URL = 'https://api.github.com/repos/*/wcli';
+
+const REPO_DATA = {
+	full_name: 'nt54hamnghi/wcli',
+	description: 'About wcli',
+	html_url: 'https://github.com/nt54hamnghi/wcli',
+	homepage: 'https://hamnghi.computer',
+	topics: ['cli', 'wasm'],
+	license: { name: 'MIT License' },
+	pushed_at: '2025-06-01T12:00:00Z',
+	open_issues_count: 3,
+	languages_url: 'https://api.github.com/repos/nt54hamnghi/wcli/languages',
+	default_branch: 'main',
+};
+
+test.describe('repo command', () => {
+	test.beforeEach(async ({ page }) => {
+		await page.route(REPO_URL, async route => {
+			await route.fulfill({ status: 200, json: REPO_DATA });
+		});
+		await page.route(`${REPO_URL}/languages`, async route => {
+			await route.fulfill({ status: 200, json: { Rust: 7500, HTML: 2500 } });
+		});
+	});
+
+	test('shows the details, languages and README', async ({
+		page,
+		inputElements,
+	}) => {
+		await page.route(`${REPO_URL}/readme`, async route => {
+			await route.fulfill({
+				status: 200,
+				json: { content: btoa('# wcli\n\nA terminal in the browser') },
+			});
+		});
+
+		await inputElements.input.focus();
+		await page.keyboard.type('repo wcli');
+		await page.keyboard.press('Enter');
+
+		const name = page.getByRole('link', { name: 'nt54hamnghi/wcli' });
+		await expect(name).toHaveAttribute('href', REPO_DATA.html_url);
+		await expect(page.getByText('cli, wasm')).toBeVisible();
+		await expect(page.getByText('MIT License')).toBeVisible();
+		await expect(page.getByText('2025-06-01')).toBeVisible();
+		await expect(page.getByRole('link', { name: '3 open' })).toBeVisible();
+
+		const languages = page.getByTestId('repo-languages');
+		await expect(languages.getByText('Rust 75.0%')).toBeVisible();
+		await expect(languages.getByText('HTML 25.0%')).toBeVisible();
+
+		const readme = page.getByTestId('repo-readme');
+		await expect(readme.getByText('A terminal in the browser')).toBeVisible();
+	});
+
+	test('says when there is no README', async ({ page, inputElements }) => {
+		await page.route(`${REPO_URL}/readme`, async route => {
+			await route.fulfill({ status: 404, json: { message: 'Not Found' } });
+		});
+
+		await inputElements.input.focus();
+		await page.keyboard.type('repo wcli');
+		await page.keyboard.press('Enter');
+
+		await expect(page.getByText('No README yet')).toBeVisible();
+	});
+
+	test('reports a project that does not exist', async ({
+		page,
+		inputElements,
+	}) => {
+		await page.route('https://api.github.com/repos/*/nope', async route => {
+			await route.fulfill({ status: 404, json: { message: 'Not Found' } });
+		});
+
+		await inputElements.input.focus();
+		await page.keyboard.type('repo nope');
+		await page.keyboard.press('Enter');
+
+		await expect(page.getByText('repo: nope: no such project')).toBeVisible();
+	});
+
+	test('refuses a name that is not one of a project', async ({
+		page,
+		inputElements,
+	}) => {
+		let requested = false;
+		await page.route('https://api.github.com/**', async route => {
+			requested = true;
+			await route.abort();
+		});
+
+		await inputElements.input.focus();
+		await page.keyboard.type('repo ../x');
+		await page.keyboard.press('Enter');
+
+		await expect(page.getByText('repo: ../x: not a project name')).toBeVisible();
+		expect(requested).toBe(false);
+	});
+});
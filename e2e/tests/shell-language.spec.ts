@@ -0,0 +1,79 @@
+import { expect } from '@playwright/test';
+
+import { test } from './fixtures/input';
+
+test.describe('shell language', () => {
+	test('loops over words', async ({ page, inputElements }) => {
+		await inputElements.input.focus();
+		await page.keyboard.type('for x in one two; do echo $x; done');
+		await page.keyboard.press('Enter');
+
+		await expect(page.getByText('one', { exact: true })).toBeVisible();
+		await expect(page.getByText('two', { exact: true })).toBeVisible();
+	});
+
+	test('branches on test', async ({ page, inputElements }) => {
+		await inputElements.input.focus();
+		await page.keyboard.type('if [ 1 -lt 2 ]; then echo yes; else echo no; fi');
+		await page.keyboard.press('Enter');
+
+		await expect(page.getByText('yes', { exact: true })).toBeVisible();
+		await expect(page.getByText('no', { exact: true })).not.toBeAttached();
+	});
+
+	test('keeps functions across lines', async ({ page, inputElements }) => {
+		await inputElements.input.focus();
+		await page.keyboard.type('greet() { echo "hello, $1"; }');
+		await page.keyboard.press('Enter');
+		await page.keyboard.type('greet visitor');
+		await page.keyboard.press('Enter');
+
+		await expect(
+			page.getByText('hello, visitor', { exact: true }),
+		).toBeVisible();
+	});
+
+	test('waits for a command to load before branching on its status', async ({
+		page,
+		inputElements,
+	}) => {
+		await page.route('https://api.github.com/repos/*/nope', async route => {
+			await route.fulfill({ status: 404, json: { message: 'Not Found' } });
+		});
+
+		await inputElements.input.focus();
+		await page.keyboard.type('repo nope && echo found || echo missing');
+		await page.keyboard.press('Enter');
+
+		await expect(page.getByText('repo: nope: no such project')).toBeVisible();
+		await expect(page.getByText('missing', { exact: true })).toBeVisible();
+		await expect(page.getByText('found', { exact: true })).not.toBeAttached();
+
+		await page.keyboard.type('repo nope; echo "status $?"');
+		await page.keyboard.press('Enter');
+		await expect(page.getByText('status 1', { exact: true })).toBeVisible();
+	});
+
+	test('points at syntax errors', async ({ page, inputElements }) => {
+		await inputElements.input.focus();
+		await page.keyboard.type('echo a; done');
+		await page.keyboard.press('Enter');
+
+		const error = page.getByTestId('syntax-error');
+		await expect(error).toContainText(
+			"wcli: syntax error: unexpected token `done'",
+		);
+		await expect(error.locator('pre')).toHaveText('echo a; done\n        ^');
+	});
+
+	test('stops runaway loops', async ({ page, inputElements }) => {
+		await inputElements.input.focus();
+		await page.keyboard.type('while true; do true; done');
+		await page.keyboard.press('Enter');
+
+		await expect(
+			page.getByText('wcli: loop stopped after 10000 iterations'),
+		).toBeVisible();
+		await expect(page.getByRole('textbox')).toBeFocused();
+	});
+});
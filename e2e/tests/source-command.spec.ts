@@ -0,0 +1,54 @@
+import { expect, type Page } from '@playwright/test';
+
+import { test } from './fixtures/input';
+
+async function seed(page: Page, files: Record<string, string>) {
+	await page.evaluate(
+		files => localStorage.setItem('wcli:fs', JSON.stringify({ files })),
+		files,
+	);
+}
+
+test.describe('source command', () => {
+	test('runs each line of a file as if typed', async ({
+		page,
+		inputElements,
+	}) => {
+		await seed(page, { 'greet.sh': '# say hi\necho hello\n\necho world' });
+		await page.reload();
+
+		await inputElements.input.focus();
+		await page.keyboard.type('source greet.sh');
+		await page.keyboard.press('Enter');
+
+		await expect(page.getByText('hello', { exact: true })).toBeVisible();
+		await expect(page.getByText('world', { exact: true })).toBeVisible();
+		await expect(page.getByRole('textbox')).toBeFocused();
+	});
+
+	test('reports a missing file', async ({ page, inputElements }) => {
+		await inputElements.input.focus();
+		await page.keyboard.type('source missing.sh');
+		await page.keyboard.press('Enter');
+
+		await expect(page.getByText('source: missing.sh: no such file')).toBeVisible();
+	});
+
+	test('runs ~/.wclirc on boot', async ({ page, inputElements }) => {
+		await seed(page, { '.wclirc': 'echo from rc' });
+		await page.reload();
+
+		await expect(page.getByText('from rc', { exact: true })).toBeVisible();
+		await expect(page.getByTestId('banner')).toBeVisible();
+		await expect(inputElements.input).toBeAttached();
+	});
+
+	test('skips ~/.wclirc with ?nostartup', async ({ page }) => {
+		await page.goto('/');
+		await seed(page, { '.wclirc': 'echo from rc' });
+		await page.goto('/?nostartup');
+
+		await expect(page.getByRole('textbox')).toBeAttached();
+		await expect(page.getByText('from rc', { exact: true })).not.toBeAttached();
+	});
+});
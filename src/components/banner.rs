@@ -1,7 +1,11 @@
 use std::ops::Deref;
+use std::time::Duration;
 
+use leptos::leptos_dom::helpers::IntervalHandle;
 use leptos::prelude::*;
 
+use crate::shell::raw::prefers_reduced_motion;
+
 const ASCII_ART: &str = r#"
             __      __                 
            |  \    |  \                
@@ -19,28 +23,137 @@ pub fn Banner(#[prop(into)] visible: Signal<IsVisible>) -> impl IntoView {
     // use a closure to make accessing the visible signal reactive
     move || {
         visible.read().then(|| {
+            let typewriter = Typewriter::new();
+            let art = typewriter.lines(ASCII_ART);
+            let version = typewriter.chars("version 0.1.2");
+            let help = [
+                typewriter.chars("type "),
+                typewriter.chars("help"),
+                typewriter.chars(" for a list of available commands"),
+            ];
+            let help_command = [
+                typewriter.chars("type "),
+                typewriter.chars("help"),
+                typewriter.chars(" [command] for help about a specific command"),
+            ];
+            let fetch = [
+                typewriter.chars("type "),
+                typewriter.chars("fetch"),
+                typewriter.chars(" to display summary"),
+            ];
+            typewriter.start();
+
+            let line = |[before, code, after]: [_; 3]| {
+                view! {
+                    <p>{before}<code class="text-green-theme">{code}</code>{after}</p>
+                }
+            };
             view! {
                 <div data-testid="banner">
                     // hide the ascii art from screen readers as it's just a decorative element
                     <p class="mb-2 whitespace-pre text-primary" aria-hidden="true">
-                        {ASCII_ART}
-                    </p>
-                    <p>"version 0.1.2"</p>
-                    <p>
-                        "type "<code class="text-green-theme">help</code>
-                        " for a list of available commands"
+                        {art}
                     </p>
-                    <p>
-                        "type "<code class="text-green-theme">help</code>
-                        " [command] for help about a specific command"
-                    </p>
-                    <p>"type "<code class="text-green-theme">fetch</code> " to display summary"</p>
+                    <p>{version}</p>
+                    {line(help)}
+                    {line(help_command)}
+                    {line(fetch)}
                 </div>
             }
         })
     }
 }
 
+/// Interval between the keystrokes of the typewriter
+const KEYSTROKE: Duration = Duration::from_millis(10);
+
+/// Reveals text step by step, as if it were being typed.
+/// Each piece of text reserves its steps in the order it's created,
+/// and text not yet typed is kept in place but invisible,
+/// so the layout doesn't shift while typing.
+#[derive(Clone, Copy)]
+struct Typewriter {
+    typed: RwSignal<usize>,
+    /// Steps reserved so far
+    total: StoredValue<usize>,
+}
+
+impl Typewriter {
+    fn new() -> Self {
+        // with reduced motion, everything is typed from the start
+        let typed = match prefers_reduced_motion() {
+            true => usize::MAX,
+            false => 0,
+        };
+        Self {
+            typed: RwSignal::new(typed),
+            total: StoredValue::new(0),
+        }
+    }
+
+    /// Types `s` one character per step
+    fn chars(self, s: &'static str) -> impl IntoView + use<> {
+        let ends = s.char_indices().map(|(i, c)| i + c.len_utf8()).collect();
+        self.reserve(s, ends)
+    }
+
+    /// Types `s` one line per step
+    fn lines(self, s: &'static str) -> impl IntoView + use<> {
+        let ends = s.split_inclusive('\n').scan(0, |end, line| {
+            *end += line.len();
+            Some(*end)
+        });
+        self.reserve(s, ends.collect())
+    }
+
+    /// Reserves one step for each prefix of `s` ending at `ends`
+    fn reserve(self, s: &'static str, ends: Vec<usize>) -> impl IntoView + use<> {
+        let offset = self.total.get_value();
+        self.total.set_value(offset + ends.len());
+
+        let typed = self.typed;
+        let split = Memo::new(
+            move |_| match typed.get().saturating_sub(offset).min(ends.len()) {
+                0 => 0,
+                n => ends[n - 1],
+            },
+        );
+        view! {
+            <span>{move || &s[..split.get()]}</span>
+            <span class="invisible">{move || &s[split.get()..]}</span>
+        }
+    }
+
+    /// Starts typing, stopping once every reserved step has been typed
+    /// or the text is removed from the page
+    fn start(self) {
+        let total = self.total.get_value();
+        if self.typed.get_untracked() >= total {
+            return;
+        }
+
+        let timer = StoredValue::new_local(None::<IntervalHandle>);
+        let stop = move || {
+            if let Some(handle) = timer.try_update_value(Option::take).flatten() {
+                handle.clear();
+            }
+        };
+
+        let typed = self.typed;
+        let tick = move || match typed.try_update(|n| {
+            *n += 1;
+            *n
+        }) {
+            Some(n) if n < total => {},
+            _ => stop(),
+        };
+        if let Ok(handle) = set_interval_with_handle(tick, KEYSTROKE) {
+            timer.set_value(Some(handle));
+        }
+        on_cleanup(stop);
+    }
+}
+
 /// A boolean indicator of the visibility of the banner.
 /// It's a newtype wrapper around a boolean to make it
 /// unambiguous when providing and using the value as context.
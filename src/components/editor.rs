@@ -0,0 +1,590 @@
+use leptos::either::Either;
+use leptos::ev::{self, KeyboardEvent};
+use leptos::html;
+use leptos::prelude::*;
+use leptos::wasm_bindgen::JsCast;
+use unicode_segmentation::UnicodeSegmentation;
+use web_sys::{ClipboardEvent, ScrollIntoViewOptions, ScrollLogicalPosition};
+
+use super::input::{Cursor, next_grapheme, prev_grapheme};
+use crate::shell::raw::use_keyboard;
+use crate::stores::fs::use_fs;
+
+/// A multi-line text buffer with a grapheme-aware cursor
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Buffer {
+    /// Lines of the buffer, without their trailing newline.
+    /// There is always at least one line.
+    lines: Vec<String>,
+    /// Index of the line the cursor is on
+    row: usize,
+    /// Byte offset of the cursor in the current line, always on a grapheme boundary
+    col: usize,
+}
+
+impl Buffer {
+    pub fn new(text: &str) -> Self {
+        Self {
+            lines: text.split('\n').map(str::to_owned).collect(),
+            row: 0,
+            col: 0,
+        }
+    }
+
+    pub fn text(&self) -> String {
+        self.lines.join("\n")
+    }
+
+    pub fn lines(&self) -> &[String] {
+        &self.lines
+    }
+
+    /// Returns the cursor position as (line index, byte offset)
+    pub fn cursor(&self) -> (usize, usize) {
+        (self.row, self.col)
+    }
+
+    fn line(&self) -> &str {
+        &self.lines[self.row]
+    }
+
+    /// Inserts text at the cursor and moves the cursor past it.
+    /// Newlines in `s` split the current line, so pasted text keeps its shape.
+    pub fn insert(&mut self, s: &str) {
+        for (i, part) in s.split('\n').enumerate() {
+            if i > 0 {
+                self.newline();
+            }
+            self.lines[self.row].insert_str(self.col, part);
+            self.col += part.len();
+        }
+    }
+
+    /// Splits the current line at the cursor
+    pub fn newline(&mut self) {
+        let rest = self.lines[self.row].split_off(self.col);
+        self.lines.insert(self.row + 1, rest);
+        self.row += 1;
+        self.col = 0;
+    }
+
+    /// Deletes the grapheme before the cursor,
+    /// joining with the previous line when at the start of a line
+    pub fn backspace(&mut self) {
+        if self.col > 0 {
+            let prev = prev_grapheme(self.line(), self.col);
+            self.lines[self.row].replace_range(prev..self.col, "");
+            self.col = prev;
+        } else if self.row > 0 {
+            let line = self.lines.remove(self.row);
+            self.row -= 1;
+            self.col = self.line().len();
+            self.lines[self.row].push_str(&line);
+        }
+    }
+
+    /// Deletes the grapheme under the cursor,
+    /// joining with the next line when at the end of a line
+    pub fn delete(&mut self) {
+        if self.col < self.line().len() {
+            let next = next_grapheme(self.line(), self.col);
+            self.lines[self.row].replace_range(self.col..next, "");
+        } else if self.row + 1 < self.lines.len() {
+            let next = self.lines.remove(self.row + 1);
+            self.lines[self.row].push_str(&next);
+        }
+    }
+
+    pub fn left(&mut self) {
+        if self.col > 0 {
+            self.col = prev_grapheme(self.line(), self.col);
+        } else if self.row > 0 {
+            self.row -= 1;
+            self.col = self.line().len();
+        }
+    }
+
+    pub fn right(&mut self) {
+        if self.col < self.line().len() {
+            self.col = next_grapheme(self.line(), self.col);
+        } else if self.row + 1 < self.lines.len() {
+            self.row += 1;
+            self.col = 0;
+        }
+    }
+
+    pub fn up(&mut self) {
+        if self.row > 0 {
+            let column = self.column();
+            self.row -= 1;
+            self.col = self.offset_of(column);
+        }
+    }
+
+    pub fn down(&mut self) {
+        if self.row + 1 < self.lines.len() {
+            let column = self.column();
+            self.row += 1;
+            self.col = self.offset_of(column);
+        }
+    }
+
+    pub fn home(&mut self) {
+        self.col = 0;
+    }
+
+    pub fn end(&mut self) {
+        self.col = self.line().len();
+    }
+
+    /// Moves the cursor to the next occurrence of `query` after the cursor,
+    /// wrapping around the end of the buffer. Returns whether a match was found.
+    pub fn find(&mut self, query: &str) -> bool {
+        if query.is_empty() {
+            return false;
+        }
+
+        // skip the match under the cursor so repeated searches move forward
+        let from = next_grapheme(self.line(), self.col);
+        if let Some(i) = self.line()[from..].find(query) {
+            self.col = from + i;
+            return true;
+        }
+
+        // the last iteration wraps back to the current line, from its start
+        let len = self.lines.len();
+        for k in 1..=len {
+            let row = (self.row + k) % len;
+            if let Some(i) = self.lines[row].find(query) {
+                self.row = row;
+                self.col = i;
+                return true;
+            }
+        }
+
+        false
+    }
+
+    /// Returns the cursor position in the current line, counted in graphemes
+    fn column(&self) -> usize {
+        self.line()[..self.col].graphemes(true).count()
+    }
+
+    /// Returns the byte offset of the `column`-th grapheme in the current line,
+    /// clamped to the end of the line
+    fn offset_of(&self, column: usize) -> usize {
+        self.line()
+            .grapheme_indices(true)
+            .nth(column)
+            .map(|(i, _)| i)
+            .unwrap_or(self.line().len())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Mode {
+    Edit,
+    /// Typing a search query in the status bar
+    Search(String),
+    /// Asking whether to save a modified buffer before exiting
+    ConfirmExit,
+}
+
+/// Full-screen, nano-like editor for a file in the writable layer.
+/// It owns the keyboard until the user exits.
+#[component]
+pub fn Editor(
+    /// Normalized path of the file to edit
+    path: String,
+    /// Called when the user exits the editor
+    on_exit: impl Fn() + 'static,
+) -> impl IntoView {
+    let (fs, set_fs) = use_fs().expect("not yet created");
+    let content = fs.read_untracked().read(&path).map(str::to_owned);
+
+    let buffer = RwSignal::new(Buffer::new(content.as_deref().unwrap_or_default()));
+    let (modified, set_modified) = signal(false);
+    let (mode, set_mode) = signal(Mode::Edit);
+    let (message, set_message) = signal(match &content {
+        Some(_) => format!("[ Read {} lines ]", buffer.read_untracked().lines().len()),
+        None => "[ New File ]".to_owned(),
+    });
+    let editor_ref: NodeRef<html::Div> = NodeRef::new();
+    let path = StoredValue::new(path);
+    let keyboard = use_keyboard().expect("not yet created");
+
+    // pasting isn't a keystroke, so it's caught on the window while the editor is open
+    let paste = window_event_listener(ev::paste, move |e| {
+        e.prevent_default();
+        let text = e
+            .unchecked_ref::<ClipboardEvent>()
+            .clipboard_data()
+            .and_then(|d| d.get_data("text").ok());
+        if let Some(text) = text {
+            buffer.write().insert(&text);
+            set_modified.set(true);
+        }
+    });
+    on_cleanup(move || paste.remove());
+
+    // keep the line with the cursor in view
+    Effect::new(move || {
+        buffer.track();
+        let Some(e) = editor_ref.get() else {
+            return;
+        };
+        if let Ok(Some(line)) = e.query_selector("[data-current]") {
+            let options = ScrollIntoViewOptions::new();
+            options.set_block(ScrollLogicalPosition::Nearest);
+            line.scroll_into_view_with_scroll_into_view_options(&options);
+        }
+    });
+
+    let save = move || {
+        let (text, lines) = buffer.with(|b| (b.text(), b.lines().len()));
+        set_fs.write().write(path.get_value(), text);
+        set_modified.set(false);
+        set_message.set(format!("[ Wrote {lines} lines ]"));
+    };
+
+    let exit = move || {
+        keyboard.release();
+        on_exit();
+    };
+
+    let edit = move |f: fn(&mut Buffer)| {
+        f(&mut buffer.write());
+        set_modified.set(true);
+    };
+
+    let on_keydown = move |e: KeyboardEvent| {
+        let key = e.key();
+        let ctrl = e.ctrl_key() || e.meta_key();
+        // a single grapheme is typed text, anything longer is a named key like "Shift"
+        let printable = !ctrl && key.graphemes(true).count() == 1;
+
+        match mode.get() {
+            Mode::Search(mut query) => {
+                e.prevent_default();
+                match key.as_str() {
+                    "Enter" => {
+                        if buffer.write().find(&query) {
+                            set_message.set(String::new());
+                        } else {
+                            set_message.set(format!("[ \"{query}\" not found ]"));
+                        }
+                        set_mode.set(Mode::Edit);
+                    },
+                    "Escape" => {
+                        set_message.set("[ Cancelled ]".to_owned());
+                        set_mode.set(Mode::Edit);
+                    },
+                    "c" if ctrl => {
+                        set_message.set("[ Cancelled ]".to_owned());
+                        set_mode.set(Mode::Edit);
+                    },
+                    "Backspace" => {
+                        query.pop();
+                        set_mode.set(Mode::Search(query));
+                    },
+                    _ if printable => {
+                        query.push_str(&key);
+                        set_mode.set(Mode::Search(query));
+                    },
+                    _ => {},
+                }
+            },
+            Mode::ConfirmExit => {
+                e.prevent_default();
+                match key.as_str() {
+                    "y" | "Y" => {
+                        save();
+                        exit();
+                    },
+                    "n" | "N" => exit(),
+                    "Escape" => {
+                        set_message.set("[ Cancelled ]".to_owned());
+                        set_mode.set(Mode::Edit);
+                    },
+                    "c" if ctrl => {
+                        set_message.set("[ Cancelled ]".to_owned());
+                        set_mode.set(Mode::Edit);
+                    },
+                    _ => {},
+                }
+            },
+            Mode::Edit => {
+                match key.as_str() {
+                    // ^O is nano's "Write Out"
+                    "s" | "o" if ctrl => save(),
+                    "x" if ctrl => {
+                        if modified.get() {
+                            set_mode.set(Mode::ConfirmExit);
+                        } else {
+                            exit();
+                        }
+                    },
+                    // browsers don't let pages intercept ^W, so ^F is offered as well
+                    "f" | "w" if ctrl => set_mode.set(Mode::Search(String::new())),
+                    "ArrowLeft" => buffer.write().left(),
+                    "ArrowRight" => buffer.write().right(),
+                    "ArrowUp" => buffer.write().up(),
+                    "ArrowDown" => buffer.write().down(),
+                    "Home" => buffer.write().home(),
+                    "End" => buffer.write().end(),
+                    "Enter" => edit(Buffer::newline),
+                    "Backspace" => edit(Buffer::backspace),
+                    "Delete" => edit(Buffer::delete),
+                    "Tab" => edit(|b| b.insert("\t")),
+                    _ if printable => {
+                        buffer.write().insert(&key);
+                        set_modified.set(true);
+                    },
+                    // let the browser handle everything else, e.g. ^C to copy
+                    _ => return,
+                }
+                e.prevent_default();
+            },
+        }
+    };
+
+    keyboard.claim(on_keydown);
+
+    // the history buffer can be wiped while the editor is open,
+    // so make sure the keyboard isn't left claimed by it
+    on_cleanup(move || {
+        if keyboard.is_claimed() {
+            keyboard.release();
+        }
+    });
+
+    let status = move || match mode.get() {
+        Mode::Edit => message.get(),
+        Mode::Search(query) => format!("Search: {query}"),
+        Mode::ConfirmExit => "Save modified buffer? (y)es, (n)o, ^C cancel".to_owned(),
+    };
+
+    view! {
+        <div
+            class="flex fixed inset-0 z-10 flex-col p-4 bg-surface"
+            node_ref=editor_ref
+            data-testid="editor"
+        >
+            <header class="flex justify-between py-0.5 px-2 bg-foreground text-surface">
+                <span>"wcli nano"</span>
+                <span data-testid="editor-path">{path.get_value()}</span>
+                <span class="min-w-[8ch] text-right">
+                    {move || modified.get().then_some("Modified")}
+                </span>
+            </header>
+            <div class="overflow-auto flex-1 py-2" data-testid="editor-content">
+                {move || {
+                    buffer
+                        .with(|b| {
+                            let (row, col) = b.cursor();
+                            let width = b.lines().len().to_string().len();
+                            b.lines()
+                                .iter()
+                                .enumerate()
+                                .map(|(i, line)| {
+                                    let number = format!("{:>width$}", i + 1);
+                                    let content = if i == row {
+                                        let (before, after) = line.split_at(col);
+                                        Either::Left(
+                                            view! {
+                                                <span>{before.to_owned()}</span>
+                                                <Cursor blinking=true />
+                                                <span>{after.to_owned()}</span>
+                                            },
+                                        )
+                                    } else {
+                                        Either::Right(line.clone())
+                                    };
+
+                                    view! {
+                                        <div class="flex gap-4" data-current=(i == row).then_some("")>
+                                            <span class="opacity-60 select-none" aria-hidden="true">
+                                                {number}
+                                            </span>
+                                            <span class="relative whitespace-pre">{content}</span>
+                                        </div>
+                                    }
+                                })
+                                .collect_view()
+                        })
+                }}
+            </div>
+            <p class="text-center whitespace-pre min-h-[1.5em]" data-testid="editor-status">
+                {status}
+            </p>
+            <footer class="grid grid-cols-2 gap-x-6 sm:grid-cols-4">
+                <Shortcut key="^S" desc="Save" />
+                <Shortcut key="^O" desc="Write Out" />
+                <Shortcut key="^F" desc="Where Is" />
+                <Shortcut key="^X" desc="Exit" />
+            </footer>
+        </div>
+    }
+}
+
+#[component]
+fn Shortcut(key: &'static str, desc: &'static str) -> impl IntoView {
+    view! {
+        <span>
+            <span class="px-1 bg-foreground text-surface">{key}</span>
+            " "
+            {desc}
+        </span>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::{fixture, rstest};
+
+    use super::*;
+
+    #[fixture]
+    fn buffer() -> Buffer {
+        Buffer::new("hello\nwörld\n\nfoo bar foo")
+    }
+
+    #[test]
+    fn test_buffer_new_empty() {
+        let b = Buffer::new("");
+        assert_eq!(b.lines(), &[""]);
+        assert_eq!(b.cursor(), (0, 0));
+    }
+
+    #[rstest]
+    #[case::empty("")]
+    #[case::single("hello")]
+    #[case::trailing_newline("hello\n")]
+    #[case::multi("hello\nworld\n\nfoo")]
+    fn test_buffer_text_roundtrip(#[case] text: &str) {
+        assert_eq!(Buffer::new(text).text(), text);
+    }
+
+    #[rstest]
+    fn test_buffer_insert(mut buffer: Buffer) {
+        buffer.end();
+        buffer.insert("!");
+        assert_eq!(buffer.lines()[0], "hello!");
+        assert_eq!(buffer.cursor(), (0, 6));
+    }
+
+    #[rstest]
+    fn test_buffer_insert_multiline(mut buffer: Buffer) {
+        buffer.right();
+        buffer.insert("a\nb");
+        assert_eq!(&buffer.lines()[..2], &["ha", "bello"]);
+        assert_eq!(buffer.cursor(), (1, 1));
+    }
+
+    #[rstest]
+    fn test_buffer_newline(mut buffer: Buffer) {
+        buffer.right();
+        buffer.right();
+        buffer.newline();
+        assert_eq!(&buffer.lines()[..2], &["he", "llo"]);
+        assert_eq!(buffer.cursor(), (1, 0));
+    }
+
+    #[rstest]
+    fn test_buffer_backspace_grapheme(mut buffer: Buffer) {
+        buffer.down();
+        buffer.right();
+        buffer.right();
+        buffer.backspace();
+        assert_eq!(buffer.lines()[1], "wrld");
+        assert_eq!(buffer.cursor(), (1, 1));
+    }
+
+    #[rstest]
+    fn test_buffer_backspace_joins_lines(mut buffer: Buffer) {
+        buffer.down();
+        buffer.backspace();
+        assert_eq!(buffer.lines()[0], "hellowörld");
+        assert_eq!(buffer.cursor(), (0, 5));
+    }
+
+    #[rstest]
+    fn test_buffer_backspace_at_start(mut buffer: Buffer) {
+        let before = buffer.clone();
+        buffer.backspace();
+        assert_eq!(buffer, before);
+    }
+
+    #[rstest]
+    fn test_buffer_delete(mut buffer: Buffer) {
+        buffer.down();
+        buffer.right();
+        buffer.delete();
+        assert_eq!(buffer.lines()[1], "wrld");
+    }
+
+    #[rstest]
+    fn test_buffer_delete_joins_lines(mut buffer: Buffer) {
+        buffer.end();
+        buffer.delete();
+        assert_eq!(buffer.lines()[0], "hellowörld");
+        assert_eq!(buffer.cursor(), (0, 5));
+    }
+
+    #[rstest]
+    fn test_buffer_left_right_wrap(mut buffer: Buffer) {
+        buffer.left();
+        assert_eq!(buffer.cursor(), (0, 0));
+
+        buffer.end();
+        buffer.right();
+        assert_eq!(buffer.cursor(), (1, 0));
+
+        buffer.left();
+        assert_eq!(buffer.cursor(), (0, 5));
+    }
+
+    #[rstest]
+    fn test_buffer_up_down_keep_column(mut buffer: Buffer) {
+        buffer.end();
+        buffer.down();
+        // "wörld" has a two-byte grapheme, so column 5 is at byte 6
+        assert_eq!(buffer.cursor(), (1, 6));
+
+        buffer.down();
+        // the empty line clamps the column
+        assert_eq!(buffer.cursor(), (2, 0));
+
+        buffer.up();
+        buffer.up();
+        assert_eq!(buffer.cursor(), (0, 0));
+    }
+
+    #[rstest]
+    fn test_buffer_find(mut buffer: Buffer) {
+        assert!(buffer.find("foo"));
+        assert_eq!(buffer.cursor(), (3, 0));
+
+        assert!(buffer.find("foo"));
+        assert_eq!(buffer.cursor(), (3, 8));
+
+        // wraps around to the first match
+        assert!(buffer.find("foo"));
+        assert_eq!(buffer.cursor(), (3, 0));
+    }
+
+    #[rstest]
+    fn test_buffer_find_wraps_to_earlier_line(mut buffer: Buffer) {
+        buffer.down();
+        assert!(buffer.find("ell"));
+        assert_eq!(buffer.cursor(), (0, 1));
+    }
+
+    #[rstest]
+    #[case::missing("baz")]
+    #[case::empty("")]
+    fn test_buffer_find_none(mut buffer: Buffer, #[case] query: &str) {
+        assert!(!buffer.find(query));
+        assert_eq!(buffer.cursor(), (0, 0));
+    }
+}
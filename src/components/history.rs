@@ -1,23 +1,49 @@
+use leptos::either::Either;
 use leptos::prelude::*;
-use leptos::reactive::wrappers::write::SignalSetter;
 
 use super::prompt::Prompt;
 use crate::shell::dispatch;
-use crate::stores::history::use_history;
+use crate::shell::process::Process;
+use crate::stores::history::{Kind, use_history};
 
 #[component]
-pub fn History(#[prop(into)] set_pending: SignalSetter<bool>) -> impl IntoView {
+pub fn History(
+    /// Receives the process of the latest command, so it can be waited on and interrupted
+    set_foreground: WriteSignal<Option<Process>>,
+) -> impl IntoView {
     let (history, _set_history) = use_history().expect("not yet created");
     view! {
         <For each=move || history.read().buffer().to_vec() key=move |entry| entry.id() let(entry)>
-            {
-                view! {
-                    <article>
-                        <Prompt value=entry.input.clone() />
-                        {dispatch(entry.input, set_pending)}
-                    </article>
+            {match entry.kind {
+                Kind::Command => {
+                    let process = Process::new();
+                    set_foreground.set(Some(process));
+                    let cancel = process.cancel;
+                    Either::Left(
+                        view! {
+                            <article>
+                                <Prompt value=entry.input.clone() />
+                                // an interrupted command's output is taken down, along with its requests
+                                <Show
+                                    when=move || !cancel.is_cancelled()
+                                    fallback=|| view! { <p data-testid="interrupted">"^C"</p> }
+                                >
+                                    {dispatch(entry.input.clone(), process)}
+                                </Show>
+                            </article>
+                        },
+                    )
                 }
-            }
+                Kind::Notice => {
+                    Either::Right(
+                        view! {
+                            <p class="text-foreground" data-testid="notice">
+                                {entry.input}
+                            </p>
+                        },
+                    )
+                }
+            }}
         </For>
     }
 }
@@ -4,9 +4,11 @@ use leptos::ev::Targeted;
 use leptos::html;
 use leptos::prelude::*;
 use leptos::wasm_bindgen::JsCast;
+use unicode_segmentation::UnicodeSegmentation;
 use web_sys::{Event, HtmlInputElement, KeyboardEvent};
 
 use crate::shell::Palette;
+use crate::shell::lang::{is_keyword, use_env};
 
 const INPUT_ID: &str = "sole-input";
 
@@ -98,9 +100,13 @@ pub(super) fn Input(
                 <span node_ref=span_ref_before data-testid="before-cursor">
                     {
                         let before = split_first(before);
+                        let env = use_env();
                         view! {
                             <span class=move || {
-                                if Palette::contains(before.read().0.as_str()) {
+                                let word = before.read().0.clone();
+                                let is_function = env
+                                    .is_some_and(|env| env.with_value(|env| env.is_function(&word)));
+                                if Palette::contains(&word) || is_keyword(&word) || is_function {
                                     "text-pass"
                                 } else {
                                     "text-fail"
@@ -110,19 +116,7 @@ pub(super) fn Input(
                         }
                     }
                 </span>
-                // top-1/2 moves the top-left corner down to the middle of the parent's height
-                // -translate-y-1/2 moves the element up by haft of its height
-                <span
-                    class=move || {
-                        format!(
-                            "inline-block absolute top-1/2 bg-foreground -translate-y-1/2 h-[1.125em] {}",
-                            if is_blinking.get() { "animate-blink" } else { "" },
-                        )
-                    }
-                    data-testid="cursor"
-                >
-                    " "
-                </span>
+                <Cursor blinking=is_blinking attr:data-testid="cursor" />
                 <span data-testid="after-cursor">
                     {after}<span class="opacity-60" data-testid="typeahead">
                         {typeahead}
@@ -157,9 +151,11 @@ pub(super) fn Input(
                     {
                         let len = value.read().len();
                         match kbe.key().as_str() {
-                            "ArrowLeft" => set_position.update(|p| { *p = p.saturating_sub(1) }),
+                            "ArrowLeft" => {
+                                set_position.update(|p| *p = prev_grapheme(&value.read(), *p))
+                            }
                             "ArrowRight" => {
-                                set_position.update(|p| { *p = p.saturating_add(1).min(len) })
+                                set_position.update(|p| *p = next_grapheme(&value.read(), *p))
                             }
                             "Home" => {
                                 set_position.set(0);
@@ -182,6 +178,44 @@ pub(super) fn Input(
     }
 }
 
+/// Terminal-style block cursor, shared by the prompt input and the editor
+#[component]
+pub(super) fn Cursor(
+    /// Whether the cursor is blinking, usually tied to the focus state of its owner
+    #[prop(into)]
+    blinking: Signal<bool>,
+) -> impl IntoView {
+    // top-1/2 moves the top-left corner down to the middle of the parent's height
+    // -translate-y-1/2 moves the element up by haft of its height
+    view! {
+        <span class=move || {
+            format!(
+                "inline-block absolute top-1/2 bg-foreground -translate-y-1/2 h-[1.125em] {}",
+                if blinking.get() { "animate-blink" } else { "" },
+            )
+        }>" "</span>
+    }
+}
+
+/// Returns the byte offset of the grapheme boundary right before `pos`,
+/// or 0 if `pos` is already at the start of the string.
+pub(super) fn prev_grapheme(s: &str, pos: usize) -> usize {
+    s.grapheme_indices(true)
+        .map(|(i, _)| i)
+        .take_while(|i| *i < pos)
+        .last()
+        .unwrap_or(0)
+}
+
+/// Returns the byte offset of the grapheme boundary right after `pos`,
+/// or the length of the string if `pos` is already at the end.
+pub(super) fn next_grapheme(s: &str, pos: usize) -> usize {
+    s.grapheme_indices(true)
+        .map(|(i, _)| i)
+        .find(|i| *i > pos)
+        .unwrap_or(s.len())
+}
+
 /// Returns a derived signal that splits a string at a specified position
 /// and return the parts before and after that position as derived signals.
 fn split_at(s: ReadSignal<String>, mid: ReadSignal<usize>) -> (Signal<String>, Signal<String>) {
@@ -201,3 +235,30 @@ fn split_first(s: Signal<String>) -> Signal<(String, String)> {
             .unwrap_or_else(|| (s.get(), "".to_owned()))
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case::start("hello", 0, 0)]
+    #[case::ascii("hello", 3, 2)]
+    #[case::multibyte("héllo", 3, 1)]
+    #[case::combining("e\u{301}x", 3, 0)]
+    #[case::emoji("a👍🏽b", 9, 1)]
+    fn test_prev_grapheme(#[case] s: &str, #[case] pos: usize, #[case] expected: usize) {
+        assert_eq!(prev_grapheme(s, pos), expected);
+    }
+
+    #[rstest]
+    #[case::end("hello", 5, 5)]
+    #[case::ascii("hello", 2, 3)]
+    #[case::multibyte("héllo", 1, 3)]
+    #[case::combining("e\u{301}x", 0, 3)]
+    #[case::emoji("a👍🏽b", 1, 9)]
+    fn test_next_grapheme(#[case] s: &str, #[case] pos: usize, #[case] expected: usize) {
+        assert_eq!(next_grapheme(s, pos), expected);
+    }
+}
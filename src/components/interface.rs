@@ -10,6 +10,12 @@ use super::history::History;
 use super::input::{Input, get_input_element};
 use super::prompt::Prompt;
 use crate::shell::Palette;
+use crate::shell::jobs::create_jobs;
+use crate::shell::lang::create_env;
+use crate::shell::process::Process;
+use crate::shell::raw::create_keyboard;
+use crate::shell::script::create_script;
+use crate::stores::fs::create_fs;
 use crate::stores::history::{History, create_history};
 
 static PRE_HISTORY: LazyLock<Vec<String>> = LazyLock::new(Palette::suggest);
@@ -24,8 +30,25 @@ pub fn Interface() -> impl IntoView {
     let div_ref: NodeRef<html::Main> = NodeRef::new();
     // history of input entries
     let (history, set_history) = create_history();
-    // whether history is still loading
-    let (pending, set_pending) = signal(false);
+    // user-writable files, persisted to localStorage
+    create_fs();
+    // variables and functions defined at the prompt
+    create_env();
+    // commands started with `&`, which run without holding up the prompt
+    create_jobs();
+    // process of the latest command, to wait on it and interrupt it with Ctrl+C
+    let (foreground, set_foreground) = signal(None::<Process>);
+    // whether the latest command is still loading
+    let pending = Memo::new(move |_| {
+        foreground
+            .get()
+            .and_then(|p| p.pending.try_get())
+            .unwrap_or(false)
+    });
+    // routes keystrokes to a command in raw mode instead of the prompt
+    let keyboard = create_keyboard();
+    // commands from startup and sourced scripts, waiting to run
+    let script = create_script();
     // current index of history
     let (current, set_current) = signal(0);
     // typeahead value used for auto-completion
@@ -45,7 +68,13 @@ pub fn Interface() -> impl IntoView {
     });
 
     let focus = move || {
-        if let Some(e) = get_input_element() {
+        // in raw mode, keystrokes are caught by the container and forwarded to the command,
+        // and while a command is pending, the container catches Ctrl+C to interrupt it
+        if keyboard.is_claimed() || pending.get_untracked() {
+            if let Some(div) = div_ref.get_untracked() {
+                div.focus().expect("should be focusable");
+            }
+        } else if let Some(e) = get_input_element() {
             e.focus().expect("should be focusable");
         }
     };
@@ -81,14 +110,45 @@ pub fn Interface() -> impl IntoView {
     });
 
     // scroll to the bottom and focus on the input
-    // when history is fully loaded
+    // when history is fully loaded or a raw-mode command exits
     Effect::new(move || {
-        if !pending.get() {
+        if !keyboard.is_claimed() && !pending.get() {
             scroll_bottom();
-            focus();
         }
+        focus();
+    });
+
+    // run scripted commands one at a time, each once the previous one is done,
+    // in the next task, as a command only becomes pending once its first effects have run
+    let scheduled = StoredValue::new(false);
+    Effect::new(move || {
+        foreground.track();
+        if pending.get() || keyboard.is_claimed() || scheduled.get_value() {
+            return;
+        }
+        scheduled.set_value(true);
+        set_timeout(
+            move || {
+                scheduled.set_value(false);
+                if pending.get_untracked() || untrack(|| keyboard.is_claimed()) {
+                    return;
+                }
+                match script.try_update(|queue| queue.next()).flatten() {
+                    Some(Ok(line)) => set_history.write().replay(line),
+                    Some(Err(e)) => set_history.write().notify(e.to_string()),
+                    None => {},
+                }
+            },
+            Duration::ZERO,
+        );
     });
 
+    let interrupt = move || {
+        if let Some(process) = foreground.get_untracked() {
+            process.interrupt();
+        }
+    };
+
     view! {
         <main
             class="flex overflow-auto flex-col gap-6 p-4 h-screen text-xs transition-colors duration-100 ease-in sm:text-sm md:text-base text-foreground font-terminal border-3 bg-surface box-border border-unfocus scroll-smooth focus-within:border-primary"
@@ -96,14 +156,24 @@ pub fn Interface() -> impl IntoView {
             // to make the div focusable and can receive keyboard events
             // without placing it in the tab order
             tabindex="-1"
-            on:keydown=move |_| focus()
+            on:keydown=move |e| {
+                if keyboard.dispatch(e.clone()) {
+                    return;
+                }
+                if pending.get_untracked() && e.ctrl_key() && e.key() == "c" {
+                    e.prevent_default();
+                    interrupt();
+                } else {
+                    focus();
+                }
+            }
             on:mouseenter=move |_| focus()
             on:mouseleave=move |_| blur()
         >
             <Banner visible=visible />
-            <History set_pending=set_pending />
+            <History set_foreground=set_foreground />
             {move || {
-                let show = !pending.get();
+                let show = !pending.get() && !keyboard.is_claimed();
                 show.then(move || {
                     view! {
                         <div class="flex gap-4 items-center pb-8">
@@ -1,5 +1,7 @@
 pub mod banner;
+pub mod editor;
 pub mod history;
 pub mod input;
 pub mod interface;
 pub mod prompt;
+pub mod screen;
@@ -0,0 +1,197 @@
+use std::time::Duration;
+
+use leptos::either::Either;
+use leptos::leptos_dom::helpers::IntervalHandle;
+use leptos::prelude::*;
+
+use crate::shell::raw::{
+    Animation, Cell, Flow, Frames, Grid, Key, Keyboard, Program, prefers_reduced_motion, runs,
+    use_keyboard,
+};
+
+/// Runs a raw-mode program full-screen until it exits.
+/// The program owns the keyboard while running, and is ticked on a timer if it asks for one.
+/// Once it exits, the keyboard goes back to the prompt
+/// and the program's summary, if any, is left in the history buffer.
+#[component]
+pub fn Screen<P: Program>(program: P) -> impl IntoView {
+    let keyboard = use_keyboard().expect("not yet created");
+    let (rows, cols) = program.size();
+    let (summary, set_summary) = signal(None::<String>);
+    let (running, set_running) = signal(true);
+
+    let runtime = Runtime {
+        program: StoredValue::new_local(program),
+        grid: RwSignal::new(Grid::new(rows, cols)),
+        timer: StoredValue::new_local(None),
+        keyboard,
+        summary: set_summary,
+        running: set_running,
+    };
+    let grid = runtime.grid;
+
+    keyboard.claim(move |e| {
+        e.prevent_default();
+        let key = Key::from(&e);
+        let flow = runtime
+            .program
+            .try_update_value(|p| p.on_key(&key))
+            .unwrap_or(Flow::Exit);
+        runtime.step(flow);
+    });
+    runtime.redraw();
+    runtime.schedule();
+
+    // the history buffer can be wiped while the program is running,
+    // so make sure no timer outlives the screen
+    on_cleanup(move || {
+        if running.try_get_untracked().unwrap_or(false) {
+            runtime.stop_timer();
+            keyboard.release();
+        }
+    });
+
+    view! {
+        <Show
+            when=move || running.get()
+            fallback=move || summary.get().map(|s| view! { <p class="text-foreground">{s}</p> })
+        >
+            <div
+                class="flex fixed inset-0 z-10 justify-center items-center bg-surface"
+                data-testid="screen"
+            >
+                <GridView grid=grid />
+            </div>
+        </Show>
+    }
+}
+
+/// Plays an animation full-screen, or shows a still frame of it inline
+/// if the user prefers reduced motion
+#[component]
+pub fn Animate<A: Animation>(animation: A) -> impl IntoView {
+    if !prefers_reduced_motion() {
+        return Either::Left(view! { <Screen program=Frames(animation) /> });
+    }
+
+    let mut animation = animation;
+    let (rows, cols) = animation.size();
+    let mut grid = Grid::new(rows, cols);
+    animation.still();
+    animation.draw(&mut grid);
+    Either::Right(view! { <GridView grid=Signal::stored(grid) attr:data-testid="still" /> })
+}
+
+/// Renders a grid of cells, one line per row
+#[component]
+fn GridView(#[prop(into)] grid: Signal<Grid>) -> impl IntoView {
+    view! {
+        <div class="leading-tight whitespace-pre">
+            {move || {
+                grid.with(|g| {
+                    g.lines()
+                        .map(|line| {
+                            let spans = runs(line)
+                                .into_iter()
+                                .map(|(cell, text)| {
+                                    view! { <span class=classes(&cell)>{text}</span> }
+                                })
+                                .collect_view();
+                            view! { <div>{spans}</div> }
+                        })
+                        .collect_view()
+                })
+            }}
+        </div>
+    }
+}
+
+fn classes(cell: &Cell) -> String {
+    let mut classes = vec![cell.fg.text_class()];
+    if let Some(bg) = cell.bg {
+        classes.push(bg.bg_class());
+    }
+    if cell.bold {
+        classes.push("font-bold");
+    }
+    classes.join(" ")
+}
+
+/// Reactive handles shared by the keyboard handler and the tick timer of a `Screen`
+struct Runtime<P: Program> {
+    program: StoredValue<P, LocalStorage>,
+    grid: RwSignal<Grid>,
+    /// The running tick timer and its interval
+    timer: StoredValue<Option<(Duration, IntervalHandle)>, LocalStorage>,
+    keyboard: Keyboard,
+    summary: WriteSignal<Option<String>>,
+    running: WriteSignal<bool>,
+}
+
+// derive would require `P: Copy`, but only the handles are copied
+impl<P: Program> Clone for Runtime<P> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<P: Program> Copy for Runtime<P> {}
+
+impl<P: Program> Runtime<P> {
+    fn redraw(self) {
+        self.program.with_value(|p| {
+            self.grid.update(|g| {
+                g.clear();
+                p.draw(g);
+            })
+        });
+    }
+
+    fn step(self, flow: Flow) {
+        match flow {
+            Flow::Continue => {
+                self.redraw();
+                self.schedule();
+            },
+            Flow::Exit => self.exit(),
+        }
+    }
+
+    /// Starts, restarts or stops the tick timer to match the program's current tick rate
+    fn schedule(self) {
+        let Some(rate) = self.program.try_with_value(Program::tick_rate) else {
+            return;
+        };
+        let current = self.timer.with_value(|t| t.as_ref().map(|(d, _)| *d));
+        if rate == current {
+            return;
+        }
+
+        self.stop_timer();
+        if let Some(rate) = rate {
+            let tick = move || {
+                let flow = self
+                    .program
+                    .try_update_value(Program::on_tick)
+                    .unwrap_or(Flow::Exit);
+                self.step(flow);
+            };
+            if let Ok(handle) = set_interval_with_handle(tick, rate) {
+                self.timer.set_value(Some((rate, handle)));
+            }
+        }
+    }
+
+    fn stop_timer(self) {
+        if let Some((_, handle)) = self.timer.try_update_value(Option::take).flatten() {
+            handle.clear();
+        }
+    }
+
+    fn exit(self) {
+        self.stop_timer();
+        self.keyboard.release();
+        self.summary.set(self.program.with_value(Program::summary));
+        self.running.set(false);
+    }
+}
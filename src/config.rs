@@ -5,7 +5,7 @@ use icondata::Icon;
 use serde::{Deserialize, Deserializer, Serialize};
 
 // This is generated by build.rs
-// Used to add ICON_MAP to the config.rs file
+// Used to add ICON_MAP, LANGUAGE_COLORS and SNAPSHOT to the config.rs file
 include!(concat!(env!("OUT_DIR"), "/codegen.rs"));
 
 const CONFIG_STR: &str = include_str!("../config.toml");
@@ -32,6 +32,40 @@ pub struct Config {
     pub linkedin: Option<Linkedin>,
     /// Optional YouTube channel configuration
     pub youtube: Option<Youtube>,
+    /// Commands run on boot, as if typed at the prompt
+    #[serde(default)]
+    pub startup: Vec<String>,
+    /// Colors of languages by name, over those GitHub uses
+    #[serde(default)]
+    pub languages: HashMap<String, String>,
+    /// Where `projects` lists repositories from (empty = the GitHub user in `github`)
+    #[serde(default)]
+    pub sources: Vec<Source>,
+    /// Optional snapshot of repositories for `projects` to show without the network
+    pub snapshot: Option<Snapshot>,
+}
+
+impl Config {
+    /// Returns the color of a language, regardless of case, from the config if it's there,
+    /// or else from the colors GitHub uses, generated by build.rs
+    pub fn language_color(&self, name: &str) -> Option<&str> {
+        self.languages
+            .iter()
+            .find(|(lang, _)| lang.eq_ignore_ascii_case(name))
+            .map(|(_, color)| color.as_str())
+            .or_else(|| LANGUAGE_COLORS.get(name.to_lowercase().as_str()).copied())
+    }
+
+    /// Returns the sources of projects, or the GitHub user if there are none
+    pub fn sources(&self) -> Vec<Source> {
+        if !self.sources.is_empty() {
+            return self.sources.clone();
+        }
+        vec![Source::Github {
+            username: self.github.username.clone(),
+            url: None,
+        }]
+    }
 }
 
 /// Represents a technology stack item with display information
@@ -72,12 +106,19 @@ pub struct Github {
     /// GitHub username for profile information in `fetch` command
     /// and API calls in `projects` command
     pub username: String,
-    /// List of specific repositories to display (empty = show all from API)
+    /// List of specific repositories to display, from any source (empty = show all)
     #[serde(default)]
     pub repos: Vec<String>,
     /// List of in-progress projects not yet published to GitHub
     #[serde(default)]
     pub in_progress: Vec<InProgress>,
+    /// Seconds a response from a source of projects is reused before it's checked for changes
+    #[serde(default = "default_cache_ttl")]
+    pub cache_ttl: u64,
+}
+
+fn default_cache_ttl() -> u64 {
+    600
 }
 
 impl Github {
@@ -90,13 +131,104 @@ impl Github {
     pub fn short_url(&self) -> String {
         format!("github.com/{}", self.username)
     }
+}
+
+/// A place repositories are listed from, in the `[[sources]]` of the config
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum Source {
+    /// GitHub, or GitHub Enterprise when `url` is its API, e.g. https://github.example.com/api/v3
+    Github {
+        username: String,
+        url: Option<String>,
+    },
+    /// GitLab, or a self-managed instance at `url`
+    Gitlab {
+        username: String,
+        url: Option<String>,
+    },
+    /// A Gitea or Forgejo instance at `url`, which share an API
+    #[serde(alias = "forgejo")]
+    Gitea { username: String, url: String },
+    /// A JSON array of repositories in the shape GitHub lists them, served with the site
+    File { path: String },
+}
+
+const GITHUB_API: &str = "https://api.github.com";
+const GITLAB: &str = "https://gitlab.com";
 
-    /// Returns the GitHub API URL for fetching user repositories
+impl Source {
+    /// Returns the URL of the first page of repositories, with as many on it as allowed
     pub fn api_url(&self) -> String {
-        format!("https://api.github.com/users/{}/repos", self.username)
+        match self {
+            Self::Github { username, url } => format!(
+                "{}/users/{username}/repos?per_page=100",
+                base(url, GITHUB_API)
+            ),
+            Self::Gitlab { username, url } => format!(
+                "{}/api/v4/users/{username}/projects?per_page=100",
+                base(url, GITLAB)
+            ),
+            Self::Gitea { username, url } => format!(
+                "{}/api/v1/users/{username}/repos?limit=50",
+                url.trim_end_matches('/')
+            ),
+            Self::File { path } => path.clone(),
+        }
+    }
+
+    /// Returns the URL of a repository of the user, or of `owner/name`, on GitHub sources
+    pub fn repo_url(&self, name: &str) -> Option<String> {
+        let Self::Github { username, url } = self else {
+            return None;
+        };
+        let name = if name.contains('/') { name.to_owned() } else { format!("{username}/{name}") };
+        Some(format!("{}/repos/{name}", base(url, GITHUB_API)))
+    }
+
+    /// Returns the URL of the languages of the project `id` on GitLab sources,
+    /// which don't list them along with the projects
+    pub fn languages_url(&self, id: u64) -> Option<String> {
+        let Self::Gitlab { url, .. } = self else {
+            return None;
+        };
+        Some(format!(
+            "{}/api/v4/projects/{id}/languages",
+            base(url, GITLAB)
+        ))
     }
 }
 
+/// The URL of an instance without a trailing slash, or `default` if there's none
+fn base(url: &Option<String>, default: &str) -> String {
+    url.as_deref()
+        .unwrap_or(default)
+        .trim_end_matches('/')
+        .to_owned()
+}
+
+/// A snapshot of repositories, whose file build.rs embeds as SNAPSHOT
+#[derive(Default, Debug, Clone, Deserialize)]
+pub struct Snapshot {
+    /// Path of a JSON array of repositories in the shape GitHub lists them, from the root
+    pub path: String,
+    /// Whether the snapshot is shown instead of the sources, or when they fail
+    #[serde(default)]
+    pub mode: SnapshotMode,
+    /// When the snapshot was taken, in ISO 8601, to tell how old it is
+    pub taken_at: Option<String>,
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SnapshotMode {
+    /// Only when fetching from the sources fails
+    #[default]
+    Fallback,
+    /// Always, without fetching anything
+    Only,
+}
+
 /// Represents an in-progress project not yet published to GitHub
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct InProgress {
@@ -145,3 +277,134 @@ impl Youtube {
         format!("youtube.com/{}", self.channel_id)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_language_color() {
+        let config = Config {
+            languages: HashMap::from([("nix".to_owned(), "#000000".to_owned())]),
+            ..Config::default()
+        };
+
+        assert_eq!(config.language_color("Rust"), Some("#dea584"));
+        assert_eq!(config.language_color("rust"), Some("#dea584"));
+        assert_eq!(config.language_color("JavaScript"), Some("#f1e05a"));
+        assert_eq!(config.language_color("javascript"), Some("#f1e05a"));
+        assert_eq!(config.language_color("C++"), Some("#f34b7d"));
+        assert_eq!(config.language_color("Nix"), Some("#000000"));
+        assert_eq!(config.language_color("Nope"), None);
+    }
+
+    #[test]
+    fn test_sources() {
+        let config: Config = toml::from_str(
+            r#"
+            title = ""
+            name = ""
+            email = ""
+            stack = {}
+            prompt = { hostname = "", username = "" }
+            github = { username = "ada" }
+
+            [[sources]]
+            kind = "github"
+            username = "ada"
+            url = "https://github.example.com/api/v3/"
+
+            [[sources]]
+            kind = "gitlab"
+            username = "ada"
+
+            [[sources]]
+            kind = "forgejo"
+            username = "ada"
+            url = "https://codeberg.org"
+
+            [[sources]]
+            kind = "file"
+            path = "/projects.json"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config
+                .sources()
+                .iter()
+                .map(Source::api_url)
+                .collect::<Vec<_>>(),
+            [
+                "https://github.example.com/api/v3/users/ada/repos?per_page=100",
+                "https://gitlab.com/api/v4/users/ada/projects?per_page=100",
+                "https://codeberg.org/api/v1/users/ada/repos?limit=50",
+                "/projects.json",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sources_default_to_github() {
+        let config = Config {
+            github: Github {
+                username: "ada".to_owned(),
+                ..Github::default()
+            },
+            ..Config::default()
+        };
+
+        assert_eq!(
+            config.sources(),
+            [Source::Github {
+                username: "ada".to_owned(),
+                url: None,
+            }]
+        );
+        assert_eq!(
+            config.sources()[0].api_url(),
+            "https://api.github.com/users/ada/repos?per_page=100"
+        );
+    }
+
+    #[test]
+    fn test_repo_url() {
+        let github = Source::Github {
+            username: "ada".to_owned(),
+            url: Some("https://github.example.com/api/v3/".to_owned()),
+        };
+        let gitlab = Source::Gitlab {
+            username: "ada".to_owned(),
+            url: None,
+        };
+
+        assert_eq!(
+            github.repo_url("wcli").as_deref(),
+            Some("https://github.example.com/api/v3/repos/ada/wcli")
+        );
+        assert_eq!(
+            github.repo_url("rust-lang/rust").as_deref(),
+            Some("https://github.example.com/api/v3/repos/rust-lang/rust")
+        );
+        assert_eq!(gitlab.repo_url("wcli"), None);
+    }
+
+    #[test]
+    fn test_languages_url() {
+        let gitlab = Source::Gitlab {
+            username: "ada".to_owned(),
+            url: Some("https://git.example.com/".to_owned()),
+        };
+        let github = Source::Github {
+            username: "ada".to_owned(),
+            url: None,
+        };
+
+        assert_eq!(
+            gitlab.languages_url(7).as_deref(),
+            Some("https://git.example.com/api/v4/projects/7/languages")
+        );
+        assert_eq!(github.languages_url(7), None);
+    }
+}
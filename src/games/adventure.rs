@@ -0,0 +1,897 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::sync::LazyLock;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::{CONFIG, Config};
+
+const WORLD_STR: &str = include_str!("../../adventure.toml");
+
+/// The world defined in adventure.toml, furnished from config.toml
+pub static WORLD: LazyLock<World> =
+    LazyLock::new(|| World::parse(WORLD_STR, &CONFIG).expect("adventure.toml should be valid"));
+
+/// Number of save slots, numbered from 1
+pub const SLOTS: u8 = 3;
+
+/// Words dropped from commands, so "take the key" works like "take key"
+const ARTICLES: [&str; 3] = ["a", "an", "the"];
+
+/// Layout of adventure.toml
+#[derive(Debug, Clone, Deserialize)]
+struct WorldFile {
+    start: String,
+    intro: String,
+    rooms: Vec<Room>,
+    items: Vec<Item>,
+    goal: Option<Goal>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Room {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    /// Direction -> id of the room it leads to
+    #[serde(default)]
+    exits: BTreeMap<String, String>,
+    /// Direction -> id of the item needed to go that way
+    #[serde(default)]
+    locked: BTreeMap<String, String>,
+    /// Ids of the items in the room at the start of the game
+    #[serde(default)]
+    items: Vec<String>,
+    generate: Option<Generate>,
+}
+
+/// Sections of config.toml a room can be furnished from
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Generate {
+    Stack,
+    Projects,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Item {
+    pub id: String,
+    pub name: String,
+    /// Other words the item can be referred to by
+    #[serde(default)]
+    aliases: Vec<String>,
+    pub description: String,
+    /// Whether the item is too heavy to be taken
+    #[serde(default)]
+    fixed: bool,
+    /// Whether the item is listed only after another item reveals it
+    #[serde(default)]
+    hidden: bool,
+    /// Id of a hidden item that examining this one reveals
+    reveals: Option<String>,
+}
+
+impl Item {
+    fn matches(&self, noun: &str) -> bool {
+        self.id.eq_ignore_ascii_case(noun)
+            || self.name.eq_ignore_ascii_case(noun)
+            || self.aliases.iter().any(|a| a.eq_ignore_ascii_case(noun))
+    }
+}
+
+/// Taking this item wins the game
+#[derive(Debug, Clone, Deserialize)]
+struct Goal {
+    item: String,
+    message: String,
+}
+
+/// Rooms and items of the game, with placeholders filled in from a `Config`
+#[derive(Debug, Clone)]
+pub struct World {
+    start: String,
+    intro: String,
+    rooms: BTreeMap<String, Room>,
+    items: BTreeMap<String, Item>,
+    goal: Option<Goal>,
+}
+
+impl World {
+    /// Parses a world file and weaves in data from `config`.
+    /// Fails if the file is malformed or refers to rooms or items that don't exist.
+    pub fn parse(s: &str, config: &Config) -> Result<Self, WorldError> {
+        let file: WorldFile = toml::from_str(s).map_err(|e| WorldError::Toml(e.to_string()))?;
+        let fill = |s: &str| {
+            s.replace("{name}", &config.name)
+                .replace("{email}", &config.email)
+                .replace("{github}", &config.github.short_url())
+                .replace("{title}", &config.title)
+        };
+
+        let mut items = file
+            .items
+            .into_iter()
+            .map(|item| {
+                let description = fill(&item.description);
+                (
+                    item.id.clone(),
+                    Item {
+                        description,
+                        ..item
+                    },
+                )
+            })
+            .collect::<BTreeMap<_, _>>();
+
+        let mut rooms = BTreeMap::new();
+        for mut room in file.rooms {
+            room.description = fill(&room.description);
+            let generated = match room.generate {
+                Some(Generate::Stack) => stack_items(config),
+                Some(Generate::Projects) => project_items(config),
+                None => Vec::new(),
+            };
+            for item in generated {
+                room.items.push(item.id.clone());
+                items.insert(item.id.clone(), item);
+            }
+            rooms.insert(room.id.clone(), room);
+        }
+
+        let goal = file.goal.map(|goal| Goal {
+            message: fill(&goal.message),
+            ..goal
+        });
+
+        let world = Self {
+            start: file.start,
+            intro: fill(&file.intro),
+            rooms,
+            items,
+            goal,
+        };
+        world.validate()?;
+        Ok(world)
+    }
+
+    /// Checks that every id the world refers to exists
+    fn validate(&self) -> Result<(), WorldError> {
+        let room = |id: &String| match self.rooms.contains_key(id) {
+            true => Ok(()),
+            false => Err(WorldError::UnknownRoom(id.clone())),
+        };
+        let item = |id: &String| match self.items.contains_key(id) {
+            true => Ok(()),
+            false => Err(WorldError::UnknownItem(id.clone())),
+        };
+
+        room(&self.start)?;
+        for r in self.rooms.values() {
+            r.exits.values().try_for_each(room)?;
+            r.locked.values().try_for_each(item)?;
+            r.items.iter().try_for_each(item)?;
+        }
+        for i in self.items.values() {
+            i.reveals.iter().try_for_each(item)?;
+        }
+        self.goal.iter().try_for_each(|g| item(&g.item))
+    }
+
+    pub fn intro(&self) -> &str {
+        &self.intro
+    }
+
+    fn room(&self, id: &str) -> &Room {
+        &self.rooms[id]
+    }
+
+    fn item(&self, id: &str) -> &Item {
+        &self.items[id]
+    }
+}
+
+/// One tool per entry of the `stack` section, grouped by category
+fn stack_items(config: &Config) -> Vec<Item> {
+    let mut categories = config.stack.iter().collect::<Vec<_>>();
+    categories.sort_by_key(|(category, _)| *category);
+
+    categories
+        .into_iter()
+        .flat_map(|(category, items)| {
+            items.iter().map(move |item| Item {
+                id: format!("stack:{}", item.name),
+                name: item.name.clone(),
+                aliases: Vec::new(),
+                description: format!(
+                    "A well-worn {}, hanging on the pegboard labeled '{category}'.",
+                    item.name
+                ),
+                fixed: false,
+                hidden: false,
+                reveals: None,
+            })
+        })
+        .collect()
+}
+
+/// One frame per repository and in-progress project of the `github` section
+fn project_items(config: &Config) -> Vec<Item> {
+    let github = &config.github;
+    let released = github.repos.iter().map(|repo| Item {
+        id: format!("project:{repo}"),
+        name: repo.clone(),
+        aliases: Vec::new(),
+        description: format!(
+            "A framed screenshot of {repo}. The caption says you can find it at {}/{repo}.",
+            github.short_url()
+        ),
+        fixed: true,
+        hidden: false,
+        reveals: None,
+    });
+    let in_progress = github.in_progress.iter().map(|project| Item {
+        id: format!("project:{}", project.name),
+        name: project.name.clone(),
+        aliases: Vec::new(),
+        description: format!(
+            "An empty frame labeled '{}'. A sticky note on it says: {} (coming soon)",
+            project.name,
+            project.description.as_deref().unwrap_or("work in progress")
+        ),
+        fixed: true,
+        hidden: false,
+        reveals: None,
+    });
+
+    released.chain(in_progress).collect()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WorldError {
+    Toml(String),
+    UnknownRoom(String),
+    UnknownItem(String),
+}
+
+impl fmt::Display for WorldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Toml(e) => write!(f, "{e}"),
+            Self::UnknownRoom(id) => write!(f, "unknown room '{id}'"),
+            Self::UnknownItem(id) => write!(f, "unknown item '{id}'"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Action {
+    Go(String),
+    Look,
+    Examine(String),
+    Take(String),
+    Drop(String),
+    Inventory,
+    Help,
+    Save(u8),
+    Load(u8),
+    Quit,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    Empty,
+    UnknownVerb(String),
+    /// The verb needs an object, e.g. "take" on its own
+    MissingObject(String),
+    BadSlot(String),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "Say something."),
+            Self::UnknownVerb(verb) => write!(f, "I don't know how to '{verb}'."),
+            Self::MissingObject(verb) => write!(f, "What do you want to {verb}?"),
+            Self::BadSlot(slot) => write!(f, "There's no save slot '{slot}'. Pick 1 to {SLOTS}."),
+        }
+    }
+}
+
+/// Expands abbreviated directions, returning `None` for anything else
+fn direction(word: &str) -> Option<&'static str> {
+    match word {
+        "n" | "north" => Some("north"),
+        "s" | "south" => Some("south"),
+        "e" | "east" => Some("east"),
+        "w" | "west" => Some("west"),
+        "u" | "up" => Some("up"),
+        "d" | "down" => Some("down"),
+        _ => None,
+    }
+}
+
+fn slot(word: Option<&str>) -> Result<u8, ParseError> {
+    match word {
+        None => Ok(1),
+        Some(w) => w
+            .parse()
+            .ok()
+            .filter(|n| (1..=SLOTS).contains(n))
+            .ok_or_else(|| ParseError::BadSlot(w.to_owned())),
+    }
+}
+
+/// Parses a command typed by the player
+pub fn parse(input: &str) -> Result<Action, ParseError> {
+    let input = input.to_lowercase();
+    let words = input
+        .split_whitespace()
+        .filter(|w| !ARTICLES.contains(w))
+        .collect::<Vec<_>>();
+
+    let Some((&verb, rest)) = words.split_first() else {
+        return Err(ParseError::Empty);
+    };
+
+    // "pick up", "look at" and "go to" are verbs of two words
+    let (verb, rest) = match (verb, rest) {
+        ("pick", ["up", rest @ ..]) => ("take", rest),
+        ("look", ["at", rest @ ..]) => ("examine", rest),
+        _ => (verb, rest),
+    };
+    let object = || match rest.join(" ") {
+        o if o.is_empty() => Err(ParseError::MissingObject(verb.to_owned())),
+        o => Ok(o),
+    };
+
+    if let Some(dir) = direction(verb) {
+        return Ok(Action::Go(dir.to_owned()));
+    }
+
+    match verb {
+        "go" | "walk" => {
+            let dir = object()?;
+            direction(&dir)
+                .map(|d| Action::Go(d.to_owned()))
+                .ok_or(ParseError::UnknownVerb(format!("go {dir}")))
+        },
+        "look" | "l" if rest.is_empty() => Ok(Action::Look),
+        "look" | "l" | "examine" | "x" | "read" => object().map(Action::Examine),
+        "take" | "get" | "grab" => object().map(Action::Take),
+        "drop" => object().map(Action::Drop),
+        "inventory" | "inv" | "i" => Ok(Action::Inventory),
+        "help" | "?" => Ok(Action::Help),
+        "save" => slot(rest.first().copied()).map(Action::Save),
+        "load" | "restore" => slot(rest.first().copied()).map(Action::Load),
+        "quit" | "exit" | "q" => Ok(Action::Quit),
+        _ => Err(ParseError::UnknownVerb(verb.to_owned())),
+    }
+}
+
+/// Progress of a game, saved to and loaded from a slot
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct State {
+    room: String,
+    /// Ids of the items carried, in the order they were taken
+    inventory: Vec<String>,
+    /// Item id -> id of the room it's in, for items not carried
+    locations: BTreeMap<String, String>,
+    /// Ids of hidden items that have been revealed
+    revealed: BTreeSet<String>,
+    moves: u32,
+    won: bool,
+}
+
+/// Something the game asks its host to do, as it can't do it itself
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Effect {
+    Save(u8),
+    Load(u8),
+    Quit,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Line {
+    /// Name of a room
+    Title(String),
+    Text(String),
+}
+
+/// What the game says back to a command
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Reply {
+    pub lines: Vec<Line>,
+    pub effect: Option<Effect>,
+}
+
+impl Reply {
+    fn text(s: impl Into<String>) -> Self {
+        Self {
+            lines: vec![Line::Text(s.into())],
+            effect: None,
+        }
+    }
+
+    fn effect(effect: Effect) -> Self {
+        Self {
+            lines: Vec::new(),
+            effect: Some(effect),
+        }
+    }
+}
+
+/// A game in progress
+#[derive(Debug, Clone)]
+pub struct Adventure<'w> {
+    world: &'w World,
+    state: State,
+}
+
+impl<'w> Adventure<'w> {
+    pub fn new(world: &'w World) -> Self {
+        let locations = world
+            .rooms
+            .values()
+            .flat_map(|room| {
+                room.items
+                    .iter()
+                    .map(|item| (item.clone(), room.id.clone()))
+            })
+            .collect();
+
+        Self {
+            world,
+            state: State {
+                room: world.start.clone(),
+                inventory: Vec::new(),
+                locations,
+                revealed: BTreeSet::new(),
+                moves: 0,
+                won: false,
+            },
+        }
+    }
+
+    pub fn state(&self) -> &State {
+        &self.state
+    }
+
+    /// Replaces the current game with a saved one.
+    /// Returns `false` and keeps the current game if the save doesn't fit this world,
+    /// e.g. because adventure.toml has changed since.
+    pub fn restore(&mut self, state: State) -> bool {
+        let world = self.world;
+        let fits = world.rooms.contains_key(&state.room)
+            && state
+                .inventory
+                .iter()
+                .all(|id| world.items.contains_key(id))
+            && state.locations.iter().all(|(item, room)| {
+                world.items.contains_key(item) && world.rooms.contains_key(room)
+            });
+        if fits {
+            self.state = state;
+        }
+        fits
+    }
+
+    pub fn moves(&self) -> u32 {
+        self.state.moves
+    }
+
+    pub fn is_won(&self) -> bool {
+        self.state.won
+    }
+
+    /// Describes the current room, its items and its exits
+    pub fn look(&self) -> Vec<Line> {
+        let room = self.world.room(&self.state.room);
+        let mut lines = vec![
+            Line::Title(room.name.clone()),
+            Line::Text(room.description.clone()),
+        ];
+
+        let items = self
+            .visible()
+            .map(|item| item.name.as_str())
+            .collect::<Vec<_>>();
+        if !items.is_empty() {
+            lines.push(Line::Text(format!("You see: {}.", items.join(", "))));
+        }
+        let exits = room.exits.keys().map(String::as_str).collect::<Vec<_>>();
+        if !exits.is_empty() {
+            lines.push(Line::Text(format!("Exits: {}.", exits.join(", "))));
+        }
+        lines
+    }
+
+    /// Runs a command typed by the player
+    pub fn handle(&mut self, input: &str) -> Reply {
+        let action = match parse(input) {
+            Ok(action) => action,
+            Err(e) => return Reply::text(e.to_string()),
+        };
+
+        match action {
+            Action::Go(dir) => self.go(&dir),
+            Action::Look => {
+                self.state.moves += 1;
+                Reply {
+                    lines: self.look(),
+                    effect: None,
+                }
+            },
+            Action::Examine(noun) => self.examine(&noun),
+            Action::Take(noun) => self.take(&noun),
+            Action::Drop(noun) => self.drop(&noun),
+            Action::Inventory => {
+                match self.carried().map(|i| i.name.as_str()).collect::<Vec<_>>() {
+                    names if names.is_empty() => Reply::text("You are empty-handed."),
+                    names => Reply::text(format!("You are carrying: {}.", names.join(", "))),
+                }
+            },
+            Action::Help => Reply {
+                lines: [
+                    "Directions: north, south, east, west (or n, s, e, w)",
+                    "look, examine <thing>, take <thing>, drop <thing>, inventory",
+                    "save [1-3], load [1-3], quit",
+                ]
+                .map(|s| Line::Text(s.to_owned()))
+                .into(),
+                effect: None,
+            },
+            Action::Save(slot) => Reply::effect(Effect::Save(slot)),
+            Action::Load(slot) => Reply::effect(Effect::Load(slot)),
+            Action::Quit => Reply::effect(Effect::Quit),
+        }
+    }
+
+    fn go(&mut self, dir: &str) -> Reply {
+        let room = self.world.room(&self.state.room);
+        let Some(next) = room.exits.get(dir) else {
+            return Reply::text("You can't go that way.");
+        };
+
+        let mut lines = Vec::new();
+        if let Some(key) = room.locked.get(dir) {
+            if !self.state.inventory.contains(key) {
+                return Reply::text("It's locked.");
+            }
+            let key = self.world.item(key);
+            lines.push(Line::Text(format!(
+                "You unlock the door with the {}.",
+                key.name
+            )));
+        }
+
+        self.state.room = next.clone();
+        self.state.moves += 1;
+        lines.extend(self.look());
+        Reply {
+            lines,
+            effect: None,
+        }
+    }
+
+    fn examine(&mut self, noun: &str) -> Reply {
+        let Some(item) = self.find(noun, self.visible().chain(self.carried())) else {
+            return Reply::text(format!("You don't see any {noun} here."));
+        };
+
+        self.state.moves += 1;
+        let mut reply = Reply::text(item.description.clone());
+        if let Some(hidden) = &item.reveals
+            && self.state.revealed.insert(hidden.clone())
+        {
+            let found = self.world.item(hidden);
+            reply
+                .lines
+                .push(Line::Text(format!("You found: {}.", found.name)));
+        }
+        reply
+    }
+
+    fn take(&mut self, noun: &str) -> Reply {
+        if self.find(noun, self.carried()).is_some() {
+            return Reply::text("You already have it.");
+        }
+        let Some(item) = self.find(noun, self.visible()) else {
+            return Reply::text(format!("You don't see any {noun} here."));
+        };
+        if item.fixed {
+            return Reply::text(format!("The {} won't budge.", item.name));
+        }
+
+        self.state.moves += 1;
+        self.state.locations.remove(&item.id);
+        self.state.inventory.push(item.id.clone());
+
+        let mut reply = Reply::text(format!("Taken: {}.", item.name));
+        if let Some(goal) = self.world.goal.as_ref().filter(|g| g.item == item.id)
+            && !self.state.won
+        {
+            self.state.won = true;
+            reply.lines.push(Line::Text(goal.message.clone()));
+        }
+        reply
+    }
+
+    fn drop(&mut self, noun: &str) -> Reply {
+        let Some(item) = self.find(noun, self.carried()) else {
+            return Reply::text(format!("You don't have any {noun}."));
+        };
+
+        self.state.moves += 1;
+        self.state.inventory.retain(|id| *id != item.id);
+        self.state
+            .locations
+            .insert(item.id.clone(), self.state.room.clone());
+        Reply::text(format!("Dropped: {}.", item.name))
+    }
+
+    fn find(&self, noun: &str, mut items: impl Iterator<Item = &'w Item>) -> Option<&'w Item> {
+        items.find(|item| item.matches(noun))
+    }
+
+    /// Items in the current room the player can see
+    fn visible(&self) -> impl Iterator<Item = &'w Item> + '_ {
+        let world = self.world;
+        let state = &self.state;
+        let furnished = &world.room(&state.room).items;
+        // items are listed in the room's order, with dropped ones at the end
+        let dropped = state.locations.keys().filter(|id| !furnished.contains(id));
+
+        furnished
+            .iter()
+            .chain(dropped)
+            .filter(|id| state.locations.get(*id) == Some(&state.room))
+            .map(|id| world.item(id))
+            .filter(|item| !item.hidden || state.revealed.contains(&item.id))
+    }
+
+    fn carried(&self) -> impl Iterator<Item = &'w Item> + '_ {
+        let world = self.world;
+        self.state.inventory.iter().map(|id| world.item(id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use rstest::{fixture, rstest};
+
+    use super::*;
+    use crate::config::{Github, InProgress, StackItem};
+
+    #[fixture]
+    fn world() -> World {
+        let config = Config {
+            title: "ada.dev".to_owned(),
+            name: "Ada".to_owned(),
+            email: "ada@example.com".to_owned(),
+            stack: HashMap::from([(
+                "backend".to_owned(),
+                vec![StackItem {
+                    name: "rust".to_owned(),
+                    icon: icondata::FaRustBrands,
+                    color: None,
+                }],
+            )]),
+            github: Github {
+                username: "ada".to_owned(),
+                repos: vec!["engine".to_owned()],
+                in_progress: vec![InProgress {
+                    name: "notes".to_owned(),
+                    description: Some("Annotated programs".to_owned()),
+                    language: None,
+                }],
+                ..Github::default()
+            },
+            ..Default::default()
+        };
+        World::parse(WORLD_STR, &config).unwrap()
+    }
+
+    fn text(reply: &Reply) -> String {
+        reply
+            .lines
+            .iter()
+            .map(|line| match line {
+                Line::Title(s) | Line::Text(s) => s.as_str(),
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Plays a sequence of commands, returning the reply to the last one
+    fn play(game: &mut Adventure, commands: &[&str]) -> Reply {
+        commands
+            .iter()
+            .map(|c| game.handle(c))
+            .last()
+            .unwrap_or_default()
+    }
+
+    #[rstest]
+    #[case::direction("north", Action::Go("north".to_owned()))]
+    #[case::abbreviated("n", Action::Go("north".to_owned()))]
+    #[case::go("go w", Action::Go("west".to_owned()))]
+    #[case::look("look", Action::Look)]
+    #[case::look_at("look at the desk", Action::Examine("desk".to_owned()))]
+    #[case::examine("x brass key", Action::Examine("brass key".to_owned()))]
+    #[case::take("Take the Key", Action::Take("key".to_owned()))]
+    #[case::pick_up("pick up card", Action::Take("card".to_owned()))]
+    #[case::drop("drop a card", Action::Drop("card".to_owned()))]
+    #[case::inventory("i", Action::Inventory)]
+    #[case::save_default("save", Action::Save(1))]
+    #[case::load_slot("load 3", Action::Load(3))]
+    #[case::quit("quit", Action::Quit)]
+    fn test_parse(#[case] input: &str, #[case] expected: Action) {
+        assert_eq!(parse(input), Ok(expected));
+    }
+
+    #[rstest]
+    #[case::empty("  ", ParseError::Empty)]
+    #[case::unknown("dance", ParseError::UnknownVerb("dance".to_owned()))]
+    #[case::bad_direction("go home", ParseError::UnknownVerb("go home".to_owned()))]
+    #[case::missing_object("take the", ParseError::MissingObject("take".to_owned()))]
+    #[case::slot_out_of_range("save 4", ParseError::BadSlot("4".to_owned()))]
+    #[case::slot_not_a_number("load x", ParseError::BadSlot("x".to_owned()))]
+    fn test_parse_error(#[case] input: &str, #[case] expected: ParseError) {
+        assert_eq!(parse(input), Err(expected));
+    }
+
+    #[rstest]
+    fn test_world_fills_placeholders(world: World) {
+        assert!(world.intro().contains("Ada"));
+        assert_eq!(
+            world.item("card").description,
+            "It reads: Ada <ada@example.com>, github.com/ada."
+        );
+    }
+
+    #[test]
+    fn test_embedded_world() {
+        assert!(World::parse(WORLD_STR, &CONFIG).is_ok());
+    }
+
+    #[test]
+    fn test_world_rejects_unknown_ids() {
+        let s = r#"
+            start = "nowhere"
+            intro = ""
+            rooms = []
+            items = []
+        "#;
+        let result = World::parse(s, &Config::default());
+        assert_eq!(
+            result.unwrap_err(),
+            WorldError::UnknownRoom("nowhere".to_owned())
+        );
+    }
+
+    #[rstest]
+    fn test_look(world: World) {
+        let game = Adventure::new(&world);
+        let lines = game.look();
+
+        assert_eq!(lines[0], Line::Title("Front Porch".to_owned()));
+        assert!(lines.contains(&Line::Text("You see: doormat.".to_owned())));
+        assert!(lines.contains(&Line::Text("Exits: north.".to_owned())));
+    }
+
+    #[rstest]
+    fn test_go(world: World) {
+        let mut game = Adventure::new(&world);
+
+        assert_eq!(text(&game.handle("south")), "You can't go that way.");
+        assert!(text(&game.handle("n")).starts_with("Hallway"));
+        assert_eq!(game.moves(), 1);
+    }
+
+    #[rstest]
+    fn test_generated_rooms(world: World) {
+        let mut game = Adventure::new(&world);
+
+        let reply = play(&mut game, &["n", "e"]);
+        assert!(text(&reply).contains("You see: toolbox, rust."));
+        let reply = game.handle("take rust");
+        assert_eq!(text(&reply), "Taken: rust.");
+
+        let reply = play(&mut game, &["w", "w"]);
+        assert!(text(&reply).contains("You see: engine, notes."));
+        let reply = game.handle("examine notes");
+        assert!(text(&reply).contains("Annotated programs"));
+    }
+
+    #[rstest]
+    fn test_locked_door(world: World) {
+        let mut game = Adventure::new(&world);
+
+        assert_eq!(text(&play(&mut game, &["n", "n"])), "It's locked.");
+        assert_eq!(game.state().room, "hall");
+    }
+
+    #[rstest]
+    fn test_hidden_item_revealed(world: World) {
+        let mut game = Adventure::new(&world);
+
+        assert_eq!(
+            text(&game.handle("take key")),
+            "You don't see any key here."
+        );
+
+        let reply = game.handle("examine doormat");
+        assert!(text(&reply).ends_with("You found: brass key."));
+        // revealed only once
+        assert!(!text(&game.handle("examine doormat")).contains("You found"));
+
+        assert_eq!(text(&game.handle("take key")), "Taken: brass key.");
+    }
+
+    #[rstest]
+    fn test_fixed_item(world: World) {
+        let mut game = Adventure::new(&world);
+        assert_eq!(
+            text(&game.handle("take doormat")),
+            "The doormat won't budge."
+        );
+    }
+
+    #[rstest]
+    fn test_win(world: World) {
+        let mut game = Adventure::new(&world);
+        let reply = play(
+            &mut game,
+            &["x doormat", "take key", "n", "n", "look at the desk"],
+        );
+        assert!(text(&reply).contains("cargo build"));
+        assert!(!game.is_won());
+
+        let reply = game.handle("take card");
+        assert!(text(&reply).contains("Thanks for playing!"));
+        assert!(game.is_won());
+        assert_eq!(game.moves(), 6);
+    }
+
+    #[rstest]
+    fn test_drop_and_inventory(world: World) {
+        let mut game = Adventure::new(&world);
+
+        assert_eq!(text(&game.handle("inventory")), "You are empty-handed.");
+        play(&mut game, &["x doormat", "take key"]);
+        assert_eq!(text(&game.handle("i")), "You are carrying: brass key.");
+
+        play(&mut game, &["n", "drop key"]);
+        assert_eq!(text(&game.handle("i")), "You are empty-handed.");
+        assert!(text(&game.handle("look")).contains("You see: brass key."));
+    }
+
+    #[rstest]
+    #[case::save("save 2", Effect::Save(2))]
+    #[case::load("load", Effect::Load(1))]
+    #[case::quit("q", Effect::Quit)]
+    fn test_effects(world: World, #[case] input: &str, #[case] effect: Effect) {
+        let mut game = Adventure::new(&world);
+        assert_eq!(game.handle(input).effect, Some(effect));
+    }
+
+    #[rstest]
+    fn test_restore(world: World) {
+        let mut game = Adventure::new(&world);
+        play(&mut game, &["x doormat", "take key", "n"]);
+        let saved = game.state().clone();
+
+        let mut other = Adventure::new(&world);
+        assert!(other.restore(saved.clone()));
+        assert_eq!(other.state(), &saved);
+
+        let stale = State {
+            room: "attic".to_owned(),
+            ..saved
+        };
+        assert!(!other.restore(stale));
+        assert_eq!(other.state().room, "hall");
+    }
+}
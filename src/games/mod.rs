@@ -0,0 +1,2 @@
+pub mod adventure;
+pub mod snake;
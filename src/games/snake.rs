@@ -0,0 +1,431 @@
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+/// Interval between steps at the start of a game
+const START_INTERVAL: Duration = Duration::from_millis(150);
+/// The fastest the game can get
+const MIN_INTERVAL: Duration = Duration::from_millis(60);
+/// How much faster the game gets with each food eaten
+const SPEEDUP: Duration = Duration::from_millis(5);
+/// Number of entries kept in the high-score table
+const HIGH_SCORES: usize = 5;
+
+/// A position on the board as (row, col)
+pub type Point = (usize, usize);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    /// Maps arrow keys and WASD to a direction
+    pub fn from_key(key: &str) -> Option<Self> {
+        match key {
+            "ArrowUp" | "w" | "W" => Some(Self::Up),
+            "ArrowDown" | "s" | "S" => Some(Self::Down),
+            "ArrowLeft" | "a" | "A" => Some(Self::Left),
+            "ArrowRight" | "d" | "D" => Some(Self::Right),
+            _ => None,
+        }
+    }
+
+    fn opposite(self) -> Self {
+        match self {
+            Self::Up => Self::Down,
+            Self::Down => Self::Up,
+            Self::Left => Self::Right,
+            Self::Right => Self::Left,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum State {
+    Running,
+    Paused,
+    /// The snake hit a wall or itself, or filled the whole board
+    Over,
+}
+
+#[derive(Debug, Clone)]
+pub struct Game {
+    rows: usize,
+    cols: usize,
+    /// Cells of the snake, head first
+    snake: VecDeque<Point>,
+    /// Direction the snake moved in on the last step
+    direction: Direction,
+    /// Turns requested since the last step, applied one per step
+    /// so quick key presses aren't lost and can't reverse the snake
+    turns: VecDeque<Direction>,
+    food: Option<Point>,
+    score: u32,
+    state: State,
+    rng: fastrand::Rng,
+}
+
+impl Game {
+    /// Creates a game with a three-cell snake in the middle of the board, heading right.
+    /// The seed makes food placement reproducible.
+    pub fn new(rows: usize, cols: usize, seed: u64) -> Self {
+        let (row, col) = (rows / 2, cols / 2);
+        let snake = (0..3).map(|i| (row, col.saturating_sub(i))).collect();
+
+        let mut game = Self {
+            rows,
+            cols,
+            snake,
+            direction: Direction::Right,
+            turns: VecDeque::new(),
+            food: None,
+            score: 0,
+            state: State::Running,
+            rng: fastrand::Rng::with_seed(seed),
+        };
+        game.place_food();
+        game
+    }
+
+    pub fn snake(&self) -> &VecDeque<Point> {
+        &self.snake
+    }
+
+    pub fn food(&self) -> Option<Point> {
+        self.food
+    }
+
+    pub fn score(&self) -> u32 {
+        self.score
+    }
+
+    pub fn state(&self) -> State {
+        self.state
+    }
+
+    /// Interval until the next step, which shrinks as the score grows
+    pub fn interval(&self) -> Duration {
+        START_INTERVAL
+            .saturating_sub(SPEEDUP * self.score)
+            .max(MIN_INTERVAL)
+    }
+
+    /// Queues a turn for an upcoming step.
+    /// Reversing into the snake's own body is ignored.
+    pub fn turn(&mut self, direction: Direction) {
+        let last = self.turns.back().copied().unwrap_or(self.direction);
+        if direction != last && direction != last.opposite() && self.turns.len() < 2 {
+            self.turns.push_back(direction);
+        }
+    }
+
+    pub fn toggle_pause(&mut self) {
+        self.state = match self.state {
+            State::Running => State::Paused,
+            State::Paused => State::Running,
+            State::Over => State::Over,
+        };
+    }
+
+    /// Moves the snake one cell, eating the food if it's there.
+    /// Does nothing unless the game is running.
+    pub fn step(&mut self) {
+        if self.state != State::Running {
+            return;
+        }
+
+        if let Some(turn) = self.turns.pop_front() {
+            self.direction = turn;
+        }
+
+        let Some(head) = self.next_head() else {
+            self.state = State::Over;
+            return;
+        };
+
+        let eats = self.food == Some(head);
+        if !eats {
+            self.snake.pop_back();
+        }
+
+        // checked after the tail moves, so the snake can follow its own tail
+        if self.snake.contains(&head) {
+            self.state = State::Over;
+            return;
+        }
+
+        self.snake.push_front(head);
+        if eats {
+            self.score += 1;
+            self.place_food();
+        }
+    }
+
+    /// Returns the next position of the head, or `None` if it would hit a wall
+    fn next_head(&self) -> Option<Point> {
+        let (row, col) = *self.snake.front().expect("snake is never empty");
+        match self.direction {
+            Direction::Up => Some((row.checked_sub(1)?, col)),
+            Direction::Down => Some((row + 1, col)).filter(|(r, _)| *r < self.rows),
+            Direction::Left => Some((row, col.checked_sub(1)?)),
+            Direction::Right => Some((row, col + 1)).filter(|(_, c)| *c < self.cols),
+        }
+    }
+
+    /// Puts food on a random free cell, ending the game if there is none left
+    fn place_food(&mut self) {
+        let free = (0..self.rows)
+            .flat_map(|r| (0..self.cols).map(move |c| (r, c)))
+            .filter(|p| !self.snake.contains(p))
+            .collect::<Vec<_>>();
+
+        self.food = self.rng.choice(free);
+        if self.food.is_none() {
+            self.state = State::Over;
+        }
+    }
+}
+
+/// Best scores, highest first
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HighScores(Vec<u32>);
+
+impl HighScores {
+    pub fn scores(&self) -> &[u32] {
+        &self.0
+    }
+
+    pub fn best(&self) -> Option<u32> {
+        self.0.first().copied()
+    }
+
+    /// Records a score, keeping only the best few.
+    /// Returns its rank (0 being the best) if it made it into the table.
+    pub fn record(&mut self, score: u32) -> Option<usize> {
+        if score == 0 {
+            return None;
+        }
+
+        // ties rank below existing entries
+        let rank = self
+            .0
+            .iter()
+            .position(|s| score > *s)
+            .unwrap_or(self.0.len());
+        if rank >= HIGH_SCORES {
+            return None;
+        }
+
+        self.0.insert(rank, score);
+        self.0.truncate(HIGH_SCORES);
+        Some(rank)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    /// Places the food right in front of the snake's head
+    fn feed(game: &mut Game) {
+        game.food = game.next_head();
+    }
+
+    #[test]
+    fn test_new_game() {
+        let game = Game::new(10, 10, 42);
+
+        assert_eq!(game.snake(), &[(5, 5), (5, 4), (5, 3)]);
+        assert_eq!(game.state(), State::Running);
+        assert_eq!(game.score(), 0);
+
+        let food = game.food().unwrap();
+        assert!(!game.snake().contains(&food));
+    }
+
+    #[test]
+    fn test_step_moves_forward() {
+        let mut game = Game::new(10, 10, 42);
+        game.food = Some((0, 0));
+        game.step();
+
+        assert_eq!(game.snake(), &[(5, 6), (5, 5), (5, 4)]);
+    }
+
+    #[test]
+    fn test_step_eats_and_grows() {
+        let mut game = Game::new(10, 10, 42);
+        feed(&mut game);
+        game.step();
+
+        assert_eq!(game.score(), 1);
+        assert_eq!(game.snake().len(), 4);
+        assert_ne!(game.food(), Some((5, 6)));
+    }
+
+    #[rstest]
+    #[case::up(Direction::Up, (4, 5))]
+    #[case::down(Direction::Down, (6, 5))]
+    fn test_turn(#[case] direction: Direction, #[case] head: Point) {
+        let mut game = Game::new(10, 10, 42);
+        game.food = Some((0, 0));
+        game.turn(direction);
+        game.step();
+
+        assert_eq!(game.snake().front(), Some(&head));
+    }
+
+    #[test]
+    fn test_turn_ignores_reverse() {
+        let mut game = Game::new(10, 10, 42);
+        game.food = Some((0, 0));
+        game.turn(Direction::Left);
+        game.step();
+
+        assert_eq!(game.snake().front(), Some(&(5, 6)));
+    }
+
+    #[test]
+    fn test_quick_turns_cannot_reverse() {
+        let mut game = Game::new(10, 10, 42);
+        game.food = Some((0, 0));
+        // up then left within one step is a legal u-turn over two steps
+        game.turn(Direction::Up);
+        game.turn(Direction::Left);
+        game.step();
+        game.step();
+
+        assert_eq!(game.snake().front(), Some(&(4, 4)));
+        assert_eq!(game.state(), State::Running);
+    }
+
+    #[rstest]
+    #[case::right(Direction::Right, 5)]
+    #[case::up(Direction::Up, 6)]
+    fn test_hits_wall(#[case] direction: Direction, #[case] steps: usize) {
+        let mut game = Game::new(10, 10, 42);
+        game.food = Some((9, 0));
+        game.turn(direction);
+        for _ in 0..steps {
+            game.step();
+        }
+
+        assert_eq!(game.state(), State::Over);
+    }
+
+    #[test]
+    fn test_hits_itself() {
+        let mut game = Game::new(10, 10, 42);
+        for _ in 0..2 {
+            feed(&mut game);
+            game.step();
+        }
+        // a five-cell snake turning in a tight circle bites its own body
+        game.turn(Direction::Down);
+        game.step();
+        game.turn(Direction::Left);
+        game.step();
+        game.turn(Direction::Up);
+        game.step();
+
+        assert_eq!(game.state(), State::Over);
+    }
+
+    #[test]
+    fn test_can_follow_tail() {
+        let mut game = Game::new(10, 10, 42);
+        feed(&mut game);
+        game.step();
+        game.food = Some((0, 0));
+        // a four-cell snake turning in a tight circle moves into the cell its tail just left
+        game.turn(Direction::Down);
+        game.step();
+        game.turn(Direction::Left);
+        game.step();
+        game.turn(Direction::Up);
+        game.step();
+
+        assert_eq!(game.state(), State::Running);
+    }
+
+    #[test]
+    fn test_pause() {
+        let mut game = Game::new(10, 10, 42);
+        game.toggle_pause();
+        game.step();
+
+        assert_eq!(game.state(), State::Paused);
+        assert_eq!(game.snake().front(), Some(&(5, 5)));
+
+        game.toggle_pause();
+        assert_eq!(game.state(), State::Running);
+    }
+
+    #[test]
+    fn test_fills_board() {
+        let mut game = Game::new(1, 4, 42);
+        feed(&mut game);
+        game.step();
+
+        assert_eq!(game.score(), 1);
+        assert_eq!(game.food(), None);
+        assert_eq!(game.state(), State::Over);
+    }
+
+    #[test]
+    fn test_interval_speeds_up() {
+        let mut game = Game::new(10, 10, 42);
+        assert_eq!(game.interval(), START_INTERVAL);
+
+        game.score = 2;
+        assert_eq!(game.interval(), Duration::from_millis(140));
+
+        game.score = 100;
+        assert_eq!(game.interval(), MIN_INTERVAL);
+    }
+
+    #[rstest]
+    #[case::arrow("ArrowUp", Some(Direction::Up))]
+    #[case::wasd("a", Some(Direction::Left))]
+    #[case::wasd_upper("D", Some(Direction::Right))]
+    #[case::other("x", None)]
+    fn test_direction_from_key(#[case] key: &str, #[case] expected: Option<Direction>) {
+        assert_eq!(Direction::from_key(key), expected);
+    }
+
+    #[test]
+    fn test_high_scores_record() {
+        let mut scores = HighScores::default();
+        assert_eq!(scores.record(3), Some(0));
+        assert_eq!(scores.record(5), Some(0));
+        assert_eq!(scores.record(4), Some(1));
+        assert_eq!(scores.record(4), Some(2));
+
+        assert_eq!(scores.scores(), &[5, 4, 4, 3]);
+        assert_eq!(scores.best(), Some(5));
+    }
+
+    #[test]
+    fn test_high_scores_truncate() {
+        let mut scores = HighScores(vec![10, 9, 8, 7, 6]);
+        assert_eq!(scores.record(1), None);
+        assert_eq!(scores.record(6), None);
+        assert_eq!(scores.record(11), Some(0));
+
+        assert_eq!(scores.scores(), &[11, 10, 9, 8, 7]);
+    }
+
+    #[test]
+    fn test_high_scores_ignore_zero() {
+        let mut scores = HighScores::default();
+        assert_eq!(scores.record(0), None);
+        assert!(scores.scores().is_empty());
+    }
+}
@@ -7,6 +7,7 @@ use stores::theme::create_theme;
 // Modules
 mod components;
 mod config;
+mod games;
 mod shell;
 mod stores;
 
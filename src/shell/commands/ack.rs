@@ -1,7 +1,6 @@
-use leptos::prelude::*;
-use leptos::reactive::wrappers::write::SignalSetter;
-
 use crate::shell::Command;
+use crate::shell::output::{Block, CommandOutput, Line, Span, Style};
+use crate::shell::process::Process;
 
 pub struct Ack;
 
@@ -11,39 +10,62 @@ impl Command for Ack {
     const USAGE: &'static str = "\t\
     ack";
 
-    fn run(_: Vec<String>, _: SignalSetter<bool>) -> Option<impl IntoView> {
-        Some(view! {
-            <div>
-                <p>"Special thanks to:"</p>
-                <ul class="pl-4 list-disc">
-                    <li>
-                        <span class="font-semibold text-primary">"Niklas Ziermann"</span>
-                        " - for making "
-                        <a
-                            class="underline"
-                            href="https://www.youtube.com/watch?v=KCcU15nvFbI"
-                            target="_blank"
-                            rel="noopener noreferrer"
-                        >
-                            "this video"
-                        </a>
-                        " that helped me get started"
-                    </li>
-                    <li>
-                        <span class="font-semibold text-primary">"Wensen (Vincent) Wu"</span>
-                        " - for making "
-                        <a
-                            class="underline"
-                            href="https://github.com/Cveinnt/LiveTerm"
-                            target="_blank"
-                            rel="noopener noreferrer"
-                        >
-                            "LiveTerm"
-                        </a>
-                        " that inspired this project"
-                    </li>
-                </ul>
-            </div>
-        })
+    fn run(_: Vec<String>, _: Process) -> CommandOutput {
+        CommandOutput::new(vec![
+            Block::Line("Special thanks to:".into()),
+            Block::List(vec![
+                thanks(
+                    "Niklas Ziermann",
+                    "this video",
+                    "https://www.youtube.com/watch?v=KCcU15nvFbI",
+                    " that helped me get started",
+                ),
+                thanks(
+                    "Wensen (Vincent) Wu",
+                    "LiveTerm",
+                    "https://github.com/Cveinnt/LiveTerm",
+                    " that inspired this project",
+                ),
+            ]),
+        ])
+    }
+}
+
+fn thanks(name: &str, work: &str, url: &str, why: &str) -> Line {
+    Line(vec![
+        Span::styled(name, Style::Strong),
+        Span::new(" - for making "),
+        Span::new(work).with_link(url),
+        Span::new(why),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run() {
+        let output = Ack::run(vec![], Process::new());
+        let [Block::Line(heading), Block::List(items)] = output.blocks.as_slice() else {
+            panic!("should be a heading and a list");
+        };
+
+        assert_eq!(heading.text(), "Special thanks to:");
+        let links = items
+            .iter()
+            .map(|l| l.0.iter().find_map(|s| s.link.as_deref()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            links,
+            [
+                Some("https://www.youtube.com/watch?v=KCcU15nvFbI"),
+                Some("https://github.com/Cveinnt/LiveTerm")
+            ]
+        );
+        assert_eq!(
+            items[1].text(),
+            "Wensen (Vincent) Wu - for making LiveTerm that inspired this project"
+        );
     }
 }
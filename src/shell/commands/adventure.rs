@@ -0,0 +1,168 @@
+use leptos::prelude::*;
+
+use super::Command;
+use crate::components::screen::Screen;
+use crate::games::adventure::{Adventure as Game, Effect, Line, State, WORLD};
+use crate::shell::output::CommandOutput;
+use crate::shell::process::Process;
+use crate::shell::raw::{Cell, Color, Flow, Grid, Key, Program, wrap};
+use crate::stores::storage;
+
+/// Size of the screen
+const ROWS: usize = 24;
+const COLS: usize = 80;
+/// Number of transcript lines kept, well beyond what fits on the screen
+const SCROLLBACK: usize = 500;
+
+fn storage_key(slot: u8) -> String {
+    format!("wcli:adventure:{slot}")
+}
+
+pub struct Adventure;
+
+impl Command for Adventure {
+    const NAME: &'static str = "adventure";
+    const DESCRIPTION: &'static str = "explore a small text adventure";
+    const USAGE: &'static str = "\t\
+    adventure  type commands like `go north`, `look` or `take key`
+               type `help` in the game for more, or press Ctrl+C to leave";
+
+    fn run(_: Vec<String>, _: Process) -> CommandOutput {
+        CommandOutput::view(|| view! { <Screen program=AdventureProgram::new() /> })
+    }
+}
+
+struct AdventureProgram {
+    game: Game<'static>,
+    /// Everything said so far, before wrapping
+    transcript: Vec<(Color, String)>,
+    input: String,
+}
+
+impl AdventureProgram {
+    fn new() -> Self {
+        let game = Game::new(&WORLD);
+        let look = game.look();
+
+        let mut program = Self {
+            game,
+            transcript: Vec::new(),
+            input: String::new(),
+        };
+        program.say(Color::Foreground, WORLD.intro());
+        program.say(Color::Foreground, "");
+        program.print(look);
+        program
+    }
+
+    fn say(&mut self, color: Color, s: impl Into<String>) {
+        self.transcript.push((color, s.into()));
+        let excess = self.transcript.len().saturating_sub(SCROLLBACK);
+        self.transcript.drain(..excess);
+    }
+
+    fn print(&mut self, lines: Vec<Line>) {
+        for line in lines {
+            match line {
+                Line::Title(s) => self.say(Color::Primary, s),
+                Line::Text(s) => self.say(Color::Foreground, s),
+            }
+        }
+    }
+
+    /// Runs the typed command, carrying out whatever the game can't do itself
+    fn submit(&mut self) -> Flow {
+        let input = std::mem::take(&mut self.input);
+        self.say(Color::Foreground, "");
+        self.say(Color::Green, format!("> {input}"));
+
+        let reply = self.game.handle(&input);
+        self.print(reply.lines);
+
+        match reply.effect {
+            None => {},
+            Some(Effect::Quit) => return Flow::Exit,
+            Some(Effect::Save(slot)) => {
+                storage::save(&storage_key(slot), self.game.state());
+                self.say(Color::Foreground, format!("Saved to slot {slot}."));
+            },
+            Some(Effect::Load(slot)) => match storage::load::<State>(&storage_key(slot)) {
+                None => self.say(Color::Foreground, format!("Slot {slot} is empty.")),
+                Some(state) => {
+                    if self.game.restore(state) {
+                        self.say(Color::Foreground, format!("Loaded slot {slot}."));
+                        self.print(self.game.look());
+                    } else {
+                        let msg = format!("The game in slot {slot} no longer fits this world.");
+                        self.say(Color::Red, msg);
+                    }
+                },
+            },
+        }
+
+        Flow::Continue
+    }
+}
+
+impl Program for AdventureProgram {
+    fn size(&self) -> (usize, usize) {
+        (ROWS, COLS)
+    }
+
+    fn on_key(&mut self, key: &Key) -> Flow {
+        if key.is_interrupt() {
+            return Flow::Exit;
+        }
+
+        match key.name.as_str() {
+            "Enter" => return self.submit(),
+            "Backspace" => {
+                self.input.pop();
+            },
+            name if !key.ctrl && name.chars().count() == 1 => self.input.push_str(name),
+            _ => {},
+        }
+
+        Flow::Continue
+    }
+
+    fn draw(&self, grid: &mut Grid) {
+        let title = format!(" ADVENTURE  moves: {}", self.game.moves());
+        grid.print(0, 0, &title, Color::Primary);
+
+        // the transcript fills the screen between the title and the input line,
+        // scrolled to the bottom
+        let height = ROWS - 3;
+        let lines = self
+            .transcript
+            .iter()
+            .flat_map(|(color, s)| wrap(s, COLS).into_iter().map(move |line| (*color, line)))
+            .collect::<Vec<_>>();
+        let start = lines.len().saturating_sub(height);
+        for (row, (color, line)) in lines[start..].iter().enumerate() {
+            grid.print(row + 1, 0, line, *color);
+        }
+
+        grid.print(ROWS - 2, 0, &"─".repeat(COLS), Color::Primary);
+
+        // keep the end of a long input in view, leaving room for the prompt and cursor
+        let input = self.input.chars().collect::<Vec<_>>();
+        let visible = &input[input.len().saturating_sub(COLS - 3)..];
+        let line = format!("> {}", visible.iter().collect::<String>());
+        grid.print(ROWS - 1, 0, &line, Color::Green);
+        grid.set(
+            ROWS - 1,
+            line.chars().count(),
+            Cell::default().on(Color::Foreground),
+        );
+    }
+
+    fn summary(&self) -> Option<String> {
+        let moves = self.game.moves();
+        let summary = match self.game.is_won() {
+            true => format!("adventure: finished in {moves} moves"),
+            false => format!("adventure: left after {moves} moves"),
+        };
+        Some(summary)
+    }
+}
@@ -0,0 +1,89 @@
+use super::{Command, input, printed};
+use crate::shell::output::CommandOutput;
+use crate::shell::process::Process;
+
+pub struct Cat;
+
+impl Command for Cat {
+    const NAME: &'static str = "cat";
+    const DESCRIPTION: &'static str = "print files";
+    const USAGE: &'static str = "\t\
+    cat [file]...  print the files one after another, or what's piped in, as with `-`
+                   SGR sequences in them set the colors, e.g. from a pasted `cargo` run";
+
+    fn run(args: Vec<String>, process: Process) -> CommandOutput {
+        if args.is_empty() && process.stdin().is_none() {
+            return CommandOutput::error("cat: missing file operand", 1);
+        }
+
+        let (text, errors) = input(Self::NAME, &args, process);
+        printed(&text, errors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shell::commands::read;
+    use crate::shell::output::{Block, Line, Style};
+    use crate::stores::fs::Fs;
+
+    fn cat(paths: &[&str]) -> CommandOutput {
+        let mut fs = Fs::default();
+        fs.write("a.txt", "one\ntwo\n");
+        fs.write("build.log", "\x1b[1;32m   Compiling\x1b[0m wcli\n");
+
+        let paths = paths.iter().map(ToString::to_string).collect::<Vec<_>>();
+        let (text, errors) = read(&fs, Cat::NAME, &paths, None);
+        printed(&text, errors)
+    }
+
+    #[test]
+    fn test_cat() {
+        let output = cat(&["a.txt", "a.txt"]);
+        let lines = output
+            .blocks
+            .iter()
+            .map(|b| match b {
+                Block::Line(l) => l.text(),
+                b => panic!("should be a line, not {b:?}"),
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(lines, ["one", "two", "one", "two"]);
+        assert_eq!(output.status, 0);
+    }
+
+    #[test]
+    fn test_cat_colors() {
+        let output = cat(&["build.log"]);
+        let [Block::Line(Line(spans))] = output.blocks.as_slice() else {
+            panic!("should be a single line");
+        };
+
+        assert_eq!(spans[0].text, "   Compiling");
+        assert!(matches!(spans[0].style, Style::Ansi(sgr) if sgr.bold));
+        assert_eq!(spans[1].style, Style::Plain);
+    }
+
+    #[test]
+    fn test_cat_missing() {
+        let output = cat(&["nope", "a.txt"]);
+
+        assert_eq!(
+            output.blocks.last(),
+            Some(&Block::Error(
+                "cat: nope: No such file or directory".to_owned()
+            ))
+        );
+        assert_eq!(output.status, 1);
+    }
+
+    #[test]
+    fn test_cat_piped() {
+        let process = Process::new();
+        process.pipe(Some("piped\n".to_owned()));
+
+        assert_eq!(Cat::run(vec![], process), CommandOutput::line("piped"));
+    }
+}
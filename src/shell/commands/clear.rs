@@ -1,8 +1,9 @@
 use leptos::prelude::*;
-use leptos::reactive::wrappers::write::SignalSetter;
 
 use super::Command;
 use crate::components::banner::use_banner_toggle;
+use crate::shell::output::CommandOutput;
+use crate::shell::process::Process;
 use crate::stores::history::use_history;
 
 pub struct Clear;
@@ -13,14 +14,14 @@ impl Command for Clear {
     const USAGE: &'static str = "\t\
     clear";
 
-    fn run(_: Vec<String>, _: SignalSetter<bool>) -> Option<impl IntoView> {
+    fn run(_: Vec<String>, _: Process) -> CommandOutput {
         let (_history, set_history) = use_history().expect("not yet created");
         set_history.write().clear();
 
         let (_visible, set_visible) = use_banner_toggle().expect("not yet created");
         set_visible.write().0 = false;
 
-        // clear doesn't return anything
-        None::<()>
+        // clear doesn't print anything
+        CommandOutput::default()
     }
 }
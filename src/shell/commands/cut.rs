@@ -0,0 +1,128 @@
+use super::{Command, getopt, input, printed, unlines};
+use crate::shell::output::CommandOutput;
+use crate::shell::process::Process;
+
+pub struct Cut;
+
+impl Command for Cut {
+    const NAME: &'static str = "cut";
+    const DESCRIPTION: &'static str = "print fields of lines";
+    const USAGE: &'static str = "\t\
+    cut -f LIST [file]...         print the fields in LIST of each line of the files,
+                                  or of what's piped in, where fields are separated by tabs
+    cut -d DELIM -f LIST [file]...  separate fields by DELIM instead
+
+    LIST is made of numbers and ranges, counted from 1, separated by commas, as in 1,3-5 or 2-
+    Lines without the delimiter are printed as they are";
+
+    fn run(args: Vec<String>, process: Process) -> CommandOutput {
+        let (flags, files) = match getopt(args, "d:f:", Self::USAGE) {
+            Ok(parsed) => parsed,
+            Err(output) => return output,
+        };
+        let mut delim = '\t';
+        let mut list = None;
+        for (flag, value) in flags {
+            let value = value.unwrap_or_default();
+            match flag {
+                'd' => {
+                    let mut chars = value.chars();
+                    match (chars.next(), chars.next()) {
+                        (Some(c), None) => delim = c,
+                        _ => {
+                            return CommandOutput::error(
+                                "cut: the delimiter must be a single character",
+                                1,
+                            );
+                        },
+                    }
+                },
+                _ => list = Some(value),
+            }
+        }
+
+        let Some(list) = list else {
+            return CommandOutput::error("cut: you must specify a list of fields", 1);
+        };
+        let fields = match fields(&list) {
+            Ok(fields) => fields,
+            Err(e) => return CommandOutput::error(format!("cut: {e}"), 1),
+        };
+
+        let (text, errors) = input(Self::NAME, &files, process);
+        let lines = text.lines().map(|line| cut(line, delim, &fields));
+        printed(&unlines(lines), errors)
+    }
+}
+
+/// Parses a list of fields like `1,3-5,7-` into inclusive ranges
+fn fields(list: &str) -> Result<Vec<(usize, usize)>, String> {
+    let number = |s: &str| match s.parse::<usize>() {
+        Ok(0) => Err("fields are numbered from 1".to_owned()),
+        Ok(n) => Ok(n),
+        Err(_) => Err(format!("invalid field value '{s}'")),
+    };
+
+    list.split(',')
+        .map(|range| match range.split_once('-') {
+            Some(("", "")) => Err(format!("invalid range with no endpoint: {range}")),
+            Some(("", end)) => Ok((1, number(end)?)),
+            Some((start, "")) => Ok((number(start)?, usize::MAX)),
+            Some((start, end)) => Ok((number(start)?, number(end)?)),
+            None => number(range).map(|n| (n, n)),
+        })
+        .collect()
+}
+
+/// The fields of `line` within `ranges`, in the order they appear in the line.
+/// A line without `delim` has a single field, which is kept.
+fn cut(line: &str, delim: char, ranges: &[(usize, usize)]) -> String {
+    if !line.contains(delim) {
+        return line.to_owned();
+    }
+
+    line.split(delim)
+        .enumerate()
+        .filter(|(i, _)| {
+            ranges
+                .iter()
+                .any(|&(start, end)| (start..=end).contains(&(i + 1)))
+        })
+        .map(|(_, field)| field)
+        .collect::<Vec<_>>()
+        .join(&delim.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case::single("2", &[(2, 2)])]
+    #[case::list("1,3-4", &[(1, 1), (3, 4)])]
+    #[case::open_end("2-", &[(2, usize::MAX)])]
+    #[case::open_start("-2", &[(1, 2)])]
+    fn test_fields(#[case] list: &str, #[case] expected: &[(usize, usize)]) {
+        assert_eq!(fields(list).as_deref(), Ok(expected));
+    }
+
+    #[rstest]
+    #[case::zero("0", "fields are numbered from 1")]
+    #[case::letters("x", "invalid field value 'x'")]
+    #[case::no_endpoint("-", "invalid range with no endpoint: -")]
+    fn test_fields_error(#[case] list: &str, #[case] expected: &str) {
+        assert_eq!(fields(list), Err(expected.to_owned()));
+    }
+
+    #[rstest]
+    #[case::fields("a:b:c", "1,3-", "a:c")]
+    #[case::input_order("a:b:c", "3,1", "a:c")]
+    #[case::without_delim("none", "1", "none")]
+    #[case::past_end("x:y", "1,3", "x")]
+    #[case::empty_fields("a::c", "2-3", ":c")]
+    fn test_cut(#[case] line: &str, #[case] list: &str, #[case] expected: &str) {
+        assert_eq!(cut(line, ':', &fields(list).unwrap()), expected);
+    }
+}
@@ -1,8 +1,10 @@
-use leptos::either::Either;
-use leptos::prelude::*;
-use leptos::reactive::wrappers::write::SignalSetter;
+use std::iter::Peekable;
+use std::ops::ControlFlow;
+use std::str::Chars;
 
 use super::Command;
+use crate::shell::output::{Block, CommandOutput};
+use crate::shell::process::Process;
 
 pub struct Echo;
 
@@ -10,15 +12,208 @@ impl Command for Echo {
     const NAME: &'static str = "echo";
     const DESCRIPTION: &'static str = "display a line of text";
     const USAGE: &'static str = "\t\
-    echo [string]";
+    echo [string]     display the string, where SGR sequences set its colors
+    echo -n [string]  don't end the line
+    echo -e [string]  interpret backslash escapes, e.g. \\n or \\e[31m, where \\c stops the output
+    echo -E [string]  don't interpret backslash escapes, the default
 
-    fn run(args: Vec<String>, _: SignalSetter<bool>) -> Option<impl IntoView> {
-        let result = if args.is_empty() {
-            Either::Left(view! { <br data-testid="echo-empty" /> })
-        } else {
-            Either::Right(view! { <div class="text-foreground">{args.join(" ")}</div> })
+    Flags combine, as in -ne";
+
+    fn run(args: Vec<String>, _: Process) -> CommandOutput {
+        let text = echo(&args);
+        if text == "\n" {
+            return CommandOutput::new(vec![Block::group("echo-empty", vec![Block::Blank])]);
+        }
+        CommandOutput::printed(&text)
+    }
+}
+
+/// What `echo` prints, newline included
+fn echo(args: &[String]) -> String {
+    let (flags, args) = Flags::parse(args);
+
+    let text = args.join(" ");
+    let text = if flags.escapes { unescape(&text) } else { ControlFlow::Continue(text) };
+    match text {
+        ControlFlow::Continue(text) if flags.newline => text + "\n",
+        ControlFlow::Continue(text) | ControlFlow::Break(text) => text,
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Flags {
+    /// Whether the line ends with a newline
+    newline: bool,
+    /// Whether backslash escapes are interpreted
+    escapes: bool,
+}
+
+impl Flags {
+    /// Reads the leading flags, like bash does: only arguments made entirely of known flags count,
+    /// so `-x` or `--` are printed as they are
+    fn parse(args: &[String]) -> (Self, &[String]) {
+        let mut flags = Self {
+            newline: true,
+            escapes: false,
+        };
+        let mut args = args;
+        while let Some((arg, rest)) = args.split_first()
+            && let Some(letters) = arg.strip_prefix('-')
+            && !letters.is_empty()
+            && letters.chars().all(|c| matches!(c, 'n' | 'e' | 'E'))
+        {
+            for c in letters.chars() {
+                match c {
+                    'n' => flags.newline = false,
+                    'e' => flags.escapes = true,
+                    _ => flags.escapes = false,
+                }
+            }
+            args = rest;
+        }
+        (flags, args)
+    }
+}
+
+/// Interprets the backslash escapes of `echo -e`.
+/// Breaks with the text before `\c`, as it stops all further output.
+pub fn unescape(s: &str) -> ControlFlow<String, String> {
+    let mut out = String::new();
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+        } else if escape(&mut chars, &mut out).is_break() {
+            return ControlFlow::Break(out);
+        }
+    }
+    ControlFlow::Continue(out)
+}
+
+/// Interprets the escape after a backslash, pushing what it stands for to `out`.
+/// Breaks on `\c`, as it stops all further output.
+pub fn escape(chars: &mut Peekable<Chars>, out: &mut String) -> ControlFlow<()> {
+    let Some(e) = chars.next() else {
+        out.push('\\');
+        return ControlFlow::Continue(());
+    };
+    let decoded = match e {
+        '\\' => '\\',
+        'a' => '\x07',
+        'b' => '\x08',
+        'c' => return ControlFlow::Break(()),
+        'e' | 'E' => '\x1b',
+        'f' => '\x0c',
+        'n' => '\n',
+        'r' => '\r',
+        't' => '\t',
+        'v' => '\x0b',
+        '0' => char::from(number(chars, 8, 3).unwrap_or_default() as u8),
+        'x' | 'u' | 'U' => {
+            let max = match e {
+                'x' => 2,
+                'u' => 4,
+                _ => 8,
+            };
+            match number(chars, 16, max) {
+                Some(n) => char::from_u32(n).unwrap_or(char::REPLACEMENT_CHARACTER),
+                // without digits, it's not an escape
+                None => {
+                    out.push('\\');
+                    e
+                },
+            }
+        },
+        // not an escape, so it's kept as is
+        e => {
+            out.push('\\');
+            e
+        },
+    };
+    out.push(decoded);
+    ControlFlow::Continue(())
+}
+
+/// Reads up to `max` digits in `radix`, if there's at least one
+pub fn number(chars: &mut Peekable<Chars>, radix: u32, max: usize) -> Option<u32> {
+    let mut n = None;
+    for _ in 0..max {
+        let Some(d) = chars.peek().and_then(|d| d.to_digit(radix)) else {
+            break;
+        };
+        chars.next();
+        n = Some(n.unwrap_or(0) * radix + d);
+    }
+    n
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+    use crate::shell::output::Style;
+
+    #[rstest]
+    #[case::words(&["hello", "world"], "hello world")]
+    #[case::spaces(&["a  b", "c"], "a  b c")]
+    fn test_echo(#[case] args: &[&str], #[case] expected: &str) {
+        let args = args.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            Echo::run(args, Process::new()),
+            CommandOutput::line(expected)
+        );
+    }
+
+    #[rstest]
+    #[case::no_newline(&["-n", "a"], "a")]
+    #[case::escapes(&["-e", r"a\tb"], "a\tb\n")]
+    #[case::combined(&["-ne", r"a\tb"], "a\tb")]
+    #[case::last_wins(&["-eE", r"a\tb"], "a\\tb\n")]
+    #[case::separate(&["-e", "-n", r"x\c", "y"], "x")]
+    #[case::stop_keeps_no_newline(&["-e", r"a\cb"], "a")]
+    #[case::unknown_flag(&["-x", "a"], "-x a\n")]
+    #[case::unknown_letter(&["-nq", "a"], "-nq a\n")]
+    #[case::dashes(&["--", "a"], "-- a\n")]
+    #[case::dash(&["-", "a"], "- a\n")]
+    #[case::flag_after_text(&["a", "-n"], "a -n\n")]
+    #[case::nothing(&["-n"], "")]
+    #[case::empty(&[], "\n")]
+    fn test_echo_flags(#[case] args: &[&str], #[case] expected: &str) {
+        let args = args.iter().map(ToString::to_string).collect::<Vec<_>>();
+        assert_eq!(echo(&args), expected);
+    }
+
+    #[rstest]
+    #[case::newline(r"a\nb", "a\nb")]
+    #[case::tab(r"a\tb", "a\tb")]
+    #[case::backslash(r"a\\b", r"a\b")]
+    #[case::escape(r"\e[31m", "\x1b[31m")]
+    #[case::octal(r"\033[0m", "\x1b[0m")]
+    #[case::octal_nul(r"a\0b", "a\0b")]
+    #[case::hex(r"\x41\x4a", "AJ")]
+    #[case::hex_without_digits(r"\xg", r"\xg")]
+    #[case::unicode(r"\u263a", "☺")]
+    #[case::unknown(r"\q", r"\q")]
+    #[case::trailing(r"a\", r"a\")]
+    fn test_unescape(#[case] s: &str, #[case] expected: &str) {
+        assert_eq!(unescape(s), ControlFlow::Continue(expected.to_owned()));
+    }
+
+    #[test]
+    fn test_unescape_stop() {
+        assert_eq!(unescape(r"a\cb"), ControlFlow::Break("a".to_owned()));
+    }
+
+    #[test]
+    fn test_echo_colors() {
+        let args = vec!["-e".to_owned(), r"\e[31mred\e[0m".to_owned()];
+        let output = Echo::run(args, Process::new());
+        let [Block::Line(line)] = output.blocks.as_slice() else {
+            panic!("should be a single line");
         };
 
-        Some(result)
+        assert_eq!(line.text(), "red");
+        assert!(matches!(line.0[0].style, Style::Ansi(sgr) if sgr.fg.is_some()));
     }
 }
@@ -0,0 +1,42 @@
+use leptos::prelude::*;
+
+use super::Command;
+use crate::components::editor::Editor;
+use crate::shell::output::CommandOutput;
+use crate::shell::process::Process;
+use crate::stores::fs;
+
+pub struct Edit;
+
+impl Command for Edit {
+    const NAME: &'static str = "edit";
+    const DESCRIPTION: &'static str = "edit a file";
+    const USAGE: &'static str = "\t\
+    edit [file]  open a file in the editor, creating it on save";
+
+    fn run(args: Vec<String>, _: Process) -> CommandOutput {
+        let Some(arg) = args.first() else {
+            return CommandOutput::error("edit: missing file operand", 1);
+        };
+
+        let path = match fs::normalize(arg) {
+            Ok(path) => path,
+            Err(e) => return CommandOutput::error(format!("edit: {e}"), 1),
+        };
+
+        // the editor claims the keyboard, which hides the prompt until it exits
+        CommandOutput::view(move || {
+            let (open, set_open) = signal(true);
+            move || {
+                open.get().then(|| {
+                    view! {
+                        <Editor
+                            path=path.clone()
+                            on_exit=move || set_open.set(false)
+                        />
+                    }
+                })
+            }
+        })
+    }
+}
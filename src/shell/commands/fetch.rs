@@ -1,10 +1,9 @@
 use icondata as i;
-use leptos::prelude::*;
-use leptos::reactive::wrappers::write::SignalSetter;
-use leptos_icons::Icon;
 
 use super::Command;
 use crate::config::{CONFIG, Config};
+use crate::shell::output::{Block, CommandOutput, Glyph, Line, Span, Style};
+use crate::shell::process::Process;
 
 pub struct Fetch;
 
@@ -33,27 +32,15 @@ impl Command for Fetch {
     const USAGE: &'static str = "\t\
     fetch";
 
-    fn run(_: Vec<String>, _: SignalSetter<bool>) -> Option<impl IntoView> {
-        Some(view! {
-            <div class="flex flex-col gap-6 items-start lg:flex-row lg:gap-12 lg:items-center">
-                <FetchLogo />
-                <FetchDetails />
-            </div>
-        })
+    fn run(_: Vec<String>, _: Process) -> CommandOutput {
+        CommandOutput::new(vec![Block::Figure {
+            art: ASCII_LOGO.to_owned(),
+            blocks: details(&CONFIG),
+        }])
     }
 }
 
-#[component]
-fn FetchLogo() -> impl IntoView {
-    view! {
-        <p class="text-xs whitespace-pre text-orange-theme" aria-hidden="true">
-            {ASCII_LOGO}
-        </p>
-    }
-}
-
-#[component]
-fn FetchDetails() -> impl IntoView {
+fn details(config: &Config) -> Vec<Block> {
     let Config {
         name,
         email,
@@ -61,73 +48,110 @@ fn FetchDetails() -> impl IntoView {
         linkedin,
         youtube,
         ..
-    } = CONFIG.clone();
-
-    view! {
-        <div class="flex flex-col gap-0 py-4 border-y">
-            <p class="flex gap-2 items-center">
-                <Icon icon=i::FaIdCardRegular height="1.125em" width="1.125em" />
-                <span>"name: "</span>
-                <span class="text-orange-theme">{name}</span>
-            </p>
-            <p class="flex gap-2 items-center">
-                <Icon icon=i::FaEnvelopeSolid height="1.125em" width="1.125em" />
-                <span>"email: "</span>
-                <a
-                    href=format!("mailto:{}", email)
-                    target="_blank"
-                    class="hover:underline text-orange-theme"
-                >
-                    {email.clone()}
-                </a>
-            </p>
-            <p class="flex gap-2 items-center">
-                <Icon icon=i::FaGithubBrands height="1.125em" width="1.125em" />
-                <span>"github: "</span>
-                <a
-                    href=github.url()
-                    target="_blank"
-                    rel="noopener noreferrer"
-                    class="hover:underline text-orange-theme"
-                >
-                    {github.short_url()}
-                </a>
-            </p>
-            {linkedin
-                .map(|linkedin| {
-                    view! {
-                        <p class="flex gap-2 items-center">
-                            <Icon icon=i::FaLinkedinBrands height="1.125em" width="1.125em" />
-                            <span>"linkedin: "</span>
-                            <a
-                                href=linkedin.url()
-                                target="_blank"
-                                rel="noopener noreferrer"
-                                class="hover:underline text-orange-theme"
-                            >
-                                {linkedin.short_url()}
-                            </a>
-                        </p>
-                    }
-                })}
-            {youtube
-                .map(|youtube| {
-
-                    view! {
-                        <p class="flex gap-2 items-center">
-                            <Icon icon=i::FaYoutubeBrands height="1.125em" width="1.125em" />
-                            <span>"youtube: "</span>
-                            <a
-                                href=youtube.url()
-                                target="_blank"
-                                rel="noopener noreferrer"
-                                class="hover:underline text-orange-theme"
-                            >
-                                {youtube.short_url()}
-                            </a>
-                        </p>
-                    }
-                })}
-        </div>
+    } = config.clone();
+
+    let mut lines = vec![
+        detail(i::FaIdCardRegular, "name", Span::new(name)),
+        detail(
+            i::FaEnvelopeSolid,
+            "email",
+            Span::new(email.clone()).with_link(format!("mailto:{email}")),
+        ),
+        detail(
+            i::FaGithubBrands,
+            "github",
+            Span::new(github.short_url()).with_link(github.url()),
+        ),
+    ];
+    if let Some(linkedin) = linkedin {
+        lines.push(detail(
+            i::FaLinkedinBrands,
+            "linkedin",
+            Span::new(linkedin.short_url()).with_link(linkedin.url()),
+        ));
+    }
+    if let Some(youtube) = youtube {
+        lines.push(detail(
+            i::FaYoutubeBrands,
+            "youtube",
+            Span::new(youtube.short_url()).with_link(youtube.url()),
+        ));
+    }
+    lines
+}
+
+fn detail(icon: icondata::Icon, label: &str, value: Span) -> Block {
+    Block::Line(Line(vec![
+        Span::glyph(Glyph::Icon(icon)),
+        Span::new(format!(" {label}: ")),
+        value.with_style(Style::Accent),
+    ]))
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+    use crate::config::{Github, Linkedin};
+
+    fn config(linkedin: Option<&str>) -> Config {
+        Config {
+            name: "Ada".to_owned(),
+            email: "ada@example.com".to_owned(),
+            github: Github {
+                username: "ada".to_owned(),
+                ..Github::default()
+            },
+            linkedin: linkedin.map(|username| Linkedin {
+                username: username.to_owned(),
+            }),
+            ..Config::default()
+        }
+    }
+
+    fn lines(blocks: &[Block]) -> Vec<(String, Option<String>)> {
+        blocks
+            .iter()
+            .map(|b| match b {
+                Block::Line(line) => (line.text(), line.0.last().and_then(|s| s.link.clone())),
+                b => panic!("should be a line, not {b:?}"),
+            })
+            .collect()
+    }
+
+    #[rstest]
+    #[case::without_socials(None, 3)]
+    #[case::with_linkedin(Some("ada"), 4)]
+    fn test_details(#[case] linkedin: Option<&str>, #[case] expected: usize) {
+        let lines = lines(&details(&config(linkedin)));
+
+        assert_eq!(lines.len(), expected);
+        assert_eq!(lines[0], (" name: Ada".to_owned(), None));
+        assert_eq!(
+            lines[1],
+            (
+                " email: ada@example.com".to_owned(),
+                Some("mailto:ada@example.com".to_owned())
+            )
+        );
+        assert_eq!(
+            lines[2],
+            (
+                " github: github.com/ada".to_owned(),
+                Some("https://github.com/ada".to_owned())
+            )
+        );
+    }
+
+    #[test]
+    fn test_run() {
+        let output = Fetch::run(vec![], Process::new());
+        let [Block::Figure { art, blocks }] = output.blocks.as_slice() else {
+            panic!("should be a figure");
+        };
+
+        assert_eq!(art, ASCII_LOGO);
+        assert_eq!(blocks, &details(&CONFIG));
     }
 }
@@ -0,0 +1,68 @@
+use leptos::html;
+use leptos::prelude::*;
+
+use super::Command;
+use crate::shell::jobs::{JobSpec, use_jobs};
+use crate::shell::output::CommandOutput;
+use crate::shell::process::Process;
+
+pub struct Fg;
+
+impl Command for Fg {
+    const NAME: &'static str = "fg";
+    const DESCRIPTION: &'static str = "bring a background job to the foreground";
+    const USAGE: &'static str = "\t\
+    fg     bring the current job to the foreground
+    fg %n  bring job n to the foreground";
+
+    fn run(args: Vec<String>, process: Process) -> CommandOutput {
+        let arg = args.first().map(String::as_str);
+        let jobs = use_jobs().expect("not yet created");
+        let found = JobSpec::parse(arg)
+            .and_then(|spec| jobs.find(spec))
+            .and_then(|(job, _)| jobs.take(job.number));
+        let Some((job, output)) = found else {
+            let msg = format!("fg: {}: no such job", arg.unwrap_or("current"));
+            return CommandOutput::error(msg, 1);
+        };
+
+        // the job now holds up the prompt until it's done,
+        // and is cancelled along with this command
+        let pending = job.process.pending;
+        process.pending.set(pending.get_untracked());
+        Effect::new(move |_| {
+            if let Some(pending) = pending.try_get() {
+                process.pending.set(pending);
+            }
+        });
+        let cancel = job.process.cancel;
+        Effect::new(move |_| {
+            if process.cancel.is_cancelled() {
+                cancel.cancel();
+            }
+        });
+
+        let output = StoredValue::new_local(Some(output));
+        let node_ref = NodeRef::<html::Div>::new();
+        node_ref.on_load(move |div| {
+            output.with_value(|output| {
+                if let Some(output) = output {
+                    div.append_child(&output.container)
+                        .expect("should append output");
+                }
+            });
+        });
+        on_cleanup(move || {
+            if let Some(output) = output.try_update_value(Option::take).flatten() {
+                output.dispose();
+            }
+        });
+
+        CommandOutput::view(move || {
+            view! {
+                <p class="text-foreground">{job.input}</p>
+                <div node_ref=node_ref></div>
+            }
+        })
+    }
+}
@@ -0,0 +1,91 @@
+use super::{Command, getopt, input, printed, unlines};
+use crate::shell::output::CommandOutput;
+use crate::shell::process::Process;
+
+/// Lines printed without `-n`
+pub const LINES: usize = 10;
+
+pub struct Head;
+
+impl Command for Head {
+    const NAME: &'static str = "head";
+    const DESCRIPTION: &'static str = "print the first lines";
+    const USAGE: &'static str = "\t\
+    head [file]...       print the first 10 lines of the files, or of what's piped in
+    head -n N [file]...  print the first N lines, also written -N";
+
+    fn run(args: Vec<String>, process: Process) -> CommandOutput {
+        let (flags, files) = match getopt(shorthand(args), "n:", Self::USAGE) {
+            Ok(parsed) => parsed,
+            Err(output) => return output,
+        };
+        let n = match flags.last() {
+            Some((_, Some(n))) => match n.parse() {
+                Ok(n) => n,
+                Err(_) => {
+                    return CommandOutput::error(
+                        format!("head: invalid number of lines: '{n}'"),
+                        1,
+                    );
+                },
+            },
+            _ => LINES,
+        };
+
+        let (text, errors) = input(Self::NAME, &files, process);
+        let lines = text.lines().collect::<Vec<_>>();
+        printed(&unlines(head(&lines, n)), errors)
+    }
+}
+
+/// Rewrites `-N` as `-n N`, as in `head -3`
+pub fn shorthand(mut args: Vec<String>) -> Vec<String> {
+    if let Some(first) = args.first_mut()
+        && let Some(n) = first.strip_prefix('-')
+        && !n.is_empty()
+        && n.chars().all(|c| c.is_ascii_digit())
+    {
+        let n = n.to_owned();
+        *first = "-n".to_owned();
+        args.insert(1, n);
+    }
+    args
+}
+
+/// The first `n` lines
+fn head<'a>(lines: &[&'a str], n: usize) -> Vec<&'a str> {
+    lines.iter().take(n).copied().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case::fewer(&["a", "b", "c"], 2, &["a", "b"])]
+    #[case::more(&["a"], 3, &["a"])]
+    #[case::none(&["a"], 0, &[])]
+    fn test_head(#[case] lines: &[&str], #[case] n: usize, #[case] expected: &[&str]) {
+        assert_eq!(head(lines, n), expected);
+    }
+
+    #[test]
+    fn test_shorthand() {
+        let args = vec!["-3".to_owned(), "a".to_owned()];
+        assert_eq!(shorthand(args), ["-n", "3", "a"]);
+    }
+
+    #[test]
+    fn test_run() {
+        let process = Process::new();
+        process.pipe(Some((1..=20).map(|i| format!("{i}\n")).collect()));
+
+        let output = Head::run(vec!["-n".to_owned(), "2".to_owned()], process);
+        assert_eq!(output, CommandOutput::text("1\n2"));
+
+        let output = Head::run(vec!["-n".to_owned(), "x".to_owned()], process);
+        assert_eq!(output.status, 1);
+    }
+}
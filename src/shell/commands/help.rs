@@ -1,10 +1,18 @@
 use std::str::FromStr;
 
-use leptos::prelude::*;
-use leptos::reactive::wrappers::write::SignalSetter;
 use strum::{IntoEnumIterator, VariantNames};
 
 use super::{Command, Palette};
+use crate::shell::output::{Block, CommandOutput, Span, Style};
+use crate::shell::process::Process;
+
+const KEYBINDINGS: [(&str, &str); 5] = [
+    ("[arrow up]", "previous command"),
+    ("[arrow down]", "next command"),
+    ("[ctrl+c]", "clear input"),
+    ("[ctrl+l]", "clear screen"),
+    ("[tab]", "trigger completion"),
+];
 
 #[derive(Debug, Clone, Copy)]
 pub struct Help;
@@ -16,50 +24,44 @@ impl Command for Help {
     help            show the overview help
     help [command]  show help for a specific command";
 
-    fn run(args: Vec<String>, _: SignalSetter<bool>) -> Option<impl IntoView> {
-        let result = if args.is_empty() {
-            let msg = Palette::iter().map(|c| c.one_line()).collect_view();
-            view! {
-                <div class="flex flex-col gap-4">
-                    <div data-testid="help-commands">
-                        <p>"Commands:"</p>
-                        <div class="grid gap-x-6 grid-cols-[max-content_auto]">{msg}</div>
-                    </div>
-                    <div data-testid="help-keybindings">
-                        <p>"Keybindings:"</p>
-                        <div class="grid gap-x-6 grid-cols-[max-content_auto]">
-                            <Keybinding key="[arrow up]" desc="previous command" />
-                            <Keybinding key="[arrow down]" desc="next command" />
-                            <Keybinding key="[ctrl+c]" desc="clear input" />
-                            <Keybinding key="[ctrl+l]" desc="clear screen" />
-                            <Keybinding key="[tab]" desc="trigger completion" />
-                        </div>
-                    </div>
-                </div>
-            }
-            .into_any()
-        } else {
-            let cmd = args.first().expect("has at least 1 item");
-
-            match Palette::from_str(cmd.as_str()) {
-                Ok(cmd) => {
-                    view! { <div data-testid="help-command-each">{cmd.help()}</div> }.into_any()
+    fn run(args: Vec<String>, _: Process) -> CommandOutput {
+        let Some(cmd) = args.first() else {
+            return CommandOutput::new(vec![
+                Block::Group {
+                    id: Some("help-commands"),
+                    title: Some("Commands:".into()),
+                    blocks: vec![Block::Terms {
+                        id: Some("help-oneline"),
+                        items: Palette::iter().map(Palette::one_line).collect(),
+                    }],
                 },
-                Err(_) => {
-                    return Some(
-                        view! {
-                            <div class="text-fail">
-                                <p>{format!("command '{cmd}' is not supported")}</p>
-                                <p>"available commands: " {Palette::VARIANTS.join(", ")}</p>
-                            </div>
-                        }
-                        .into_any(),
-                    );
+                Block::Group {
+                    id: Some("help-keybindings"),
+                    title: Some("Keybindings:".into()),
+                    blocks: vec![Block::Terms {
+                        id: Some("help-keybinding-item"),
+                        items: KEYBINDINGS
+                            .iter()
+                            .map(|&(key, desc)| {
+                                (Span::styled(key, Style::Label).into(), desc.into())
+                            })
+                            .collect(),
+                    }],
                 },
-            }
+            ]);
         };
 
-        Some(result)
+        match Palette::from_str(cmd.as_str()) {
+            Ok(cmd) => CommandOutput::new(vec![Block::group("help-command-each", cmd.help())]),
+            Err(_) => CommandOutput::new(vec![
+                Block::Error(format!("command '{cmd}' is not supported")),
+                Block::Error(format!(
+                    "available commands: {}",
+                    Palette::VARIANTS.join(", ")
+                )),
+            ])
+            .with_status(1),
+        }
     }
 
     fn suggest() -> Vec<String> {
@@ -67,12 +69,51 @@ impl Command for Help {
     }
 }
 
-#[component]
-fn Keybinding(#[prop(into)] key: &'static str, #[prop(into)] desc: &'static str) -> impl IntoView {
-    view! {
-        <span class="contents" data-testid="help-keybinding-item">
-            <span class="pl-8 text-info">{key}</span>
-            <span>{desc}</span>
-        </span>
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shell::output::Line;
+
+    #[test]
+    fn test_overview() {
+        let output = Help::run(vec![], Process::new());
+        let [Block::Group { blocks, .. }, _] = output.blocks.as_slice() else {
+            panic!("should have commands and keybindings");
+        };
+        let [Block::Terms { items, .. }] = blocks.as_slice() else {
+            panic!("should list the commands");
+        };
+
+        let names = items
+            .iter()
+            .map(|(name, _)| name.text())
+            .collect::<Vec<_>>();
+        assert_eq!(names, Palette::VARIANTS);
+    }
+
+    #[test]
+    fn test_command() {
+        let output = Help::run(vec!["echo".to_owned()], Process::new());
+        let [Block::Group { blocks, .. }] = output.blocks.as_slice() else {
+            panic!("should be a single group");
+        };
+
+        assert_eq!(
+            blocks[0],
+            Block::Line(Line(vec![
+                Span::styled("echo", Style::Command),
+                Span::new(" - display a line of text"),
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_not_supported() {
+        let output = Help::run(vec!["nope".to_owned()], Process::new());
+        assert_eq!(
+            output.blocks[0],
+            Block::Error("command 'nope' is not supported".to_owned())
+        );
+        assert_eq!(output.status, 1);
     }
 }
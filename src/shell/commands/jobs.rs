@@ -0,0 +1,31 @@
+use super::Command;
+use crate::shell::jobs::use_jobs;
+use crate::shell::output::{Block, CommandOutput};
+use crate::shell::process::Process;
+
+pub struct Jobs;
+
+impl Command for Jobs {
+    const NAME: &'static str = "jobs";
+    const DESCRIPTION: &'static str = "list background jobs";
+    const USAGE: &'static str = "\t\
+    jobs  list jobs started with `&`, with their statuses
+          `+` marks the current job and `-` the previous one.
+          Jobs that are done are listed once, then forgotten";
+
+    fn run(_: Vec<String>, _: Process) -> CommandOutput {
+        let jobs = use_jobs().expect("not yet created");
+        let lines = jobs
+            .list()
+            .into_iter()
+            .map(|(job, marker)| format!("[{}]{marker} {:<8}{}", job.number, job.status, job.input))
+            .collect::<Vec<_>>();
+        jobs.reap();
+
+        let blocks = (!lines.is_empty())
+            .then(|| Block::Pre(lines.join("\n")))
+            .into_iter()
+            .collect();
+        CommandOutput::new(vec![Block::group("jobs", blocks)])
+    }
+}
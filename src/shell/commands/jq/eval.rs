@@ -0,0 +1,435 @@
+use std::cmp::Ordering;
+
+use serde_json::{Map, Value};
+
+use super::parser::{Filter, Op, number};
+
+/// Runs a filter on a value, giving all of its outputs, or the first error, like jq does
+pub fn eval(filter: &Filter, input: &Value) -> Result<Vec<Value>, String> {
+    match filter {
+        Filter::Identity => Ok(vec![input.clone()]),
+        Filter::Literal(value) => Ok(vec![value.clone()]),
+        Filter::Index(target, key) => {
+            let mut out = Vec::new();
+            for key in eval(key, input)? {
+                for value in eval(target, input)? {
+                    out.push(index(&value, &key)?);
+                }
+            }
+            Ok(out)
+        },
+        Filter::Iterate(target) => {
+            let mut out = Vec::new();
+            for value in eval(target, input)? {
+                out.extend(iterate(&value)?);
+            }
+            Ok(out)
+        },
+        Filter::Pipe(first, then) => {
+            let mut out = Vec::new();
+            for value in eval(first, input)? {
+                out.extend(eval(then, &value)?);
+            }
+            Ok(out)
+        },
+        Filter::Comma(first, second) => {
+            let mut out = eval(first, input)?;
+            out.extend(eval(second, input)?);
+            Ok(out)
+        },
+        Filter::Array(None) => Ok(vec![Value::Array(Vec::new())]),
+        Filter::Array(Some(filter)) => Ok(vec![Value::Array(eval(filter, input)?)]),
+        Filter::Object(entries) => object(entries, input),
+        Filter::Binary(op, left, right) => binary(*op, left, right, input),
+        Filter::Call(name, args) => call(name, args, input),
+    }
+}
+
+/// The values of `{k: f, ...}`, one for each combination of the outputs of its entries
+fn object(entries: &[(Filter, Filter)], input: &Value) -> Result<Vec<Value>, String> {
+    let mut objects = vec![Map::new()];
+    for (key, value) in entries {
+        let mut next = Vec::new();
+        for key in eval(key, input)? {
+            let Value::String(key) = key else {
+                return Err(format!(
+                    "Object keys must be strings, not {}",
+                    describe(&key)
+                ));
+            };
+            for value in eval(value, input)? {
+                for object in &objects {
+                    let mut object = object.clone();
+                    object.insert(key.clone(), value.clone());
+                    next.push(object);
+                }
+            }
+        }
+        objects = next;
+    }
+    Ok(objects.into_iter().map(Value::Object).collect())
+}
+
+fn index(value: &Value, key: &Value) -> Result<Value, String> {
+    match (value, key) {
+        (Value::Object(map), Value::String(k)) => Ok(map.get(k).cloned().unwrap_or(Value::Null)),
+        (Value::Array(items), Value::Number(n)) => {
+            let n = n.as_f64().unwrap_or_default().floor() as i64;
+            let i = if n < 0 { items.len() as i64 + n } else { n };
+            Ok(usize::try_from(i)
+                .ok()
+                .and_then(|i| items.get(i))
+                .cloned()
+                .unwrap_or(Value::Null))
+        },
+        (Value::Null, Value::String(_) | Value::Number(_)) => Ok(Value::Null),
+        (value, Value::String(k)) => Err(format!(
+            "Cannot index {} with {}",
+            kind(value),
+            Value::String(k.clone())
+        )),
+        (value, key) => Err(format!("Cannot index {} with {}", kind(value), kind(key))),
+    }
+}
+
+fn iterate(value: &Value) -> Result<Vec<Value>, String> {
+    match value {
+        Value::Array(items) => Ok(items.clone()),
+        Value::Object(map) => Ok(map.values().cloned().collect()),
+        value => Err(format!("Cannot iterate over {}", describe(value))),
+    }
+}
+
+fn binary(op: Op, left: &Filter, right: &Filter, input: &Value) -> Result<Vec<Value>, String> {
+    let mut out = Vec::new();
+    if matches!(op, Op::And | Op::Or) {
+        for l in eval(left, input)? {
+            // `false and f` and `true or f` don't need f
+            if truthy(&l) == (op == Op::Or) {
+                out.push(Value::Bool(truthy(&l)));
+                continue;
+            }
+            for r in eval(right, input)? {
+                out.push(Value::Bool(truthy(&r)));
+            }
+        }
+        return Ok(out);
+    }
+
+    for r in eval(right, input)? {
+        for l in eval(left, input)? {
+            out.push(arithmetic(op, l, &r)?);
+        }
+    }
+    Ok(out)
+}
+
+fn arithmetic(op: Op, l: Value, r: &Value) -> Result<Value, String> {
+    let ordering = compare(&l, r);
+    let value = match (op, l, r) {
+        (Op::Eq, ..) => Value::Bool(ordering == Ordering::Equal),
+        (Op::Ne, ..) => Value::Bool(ordering != Ordering::Equal),
+        (Op::Lt, ..) => Value::Bool(ordering == Ordering::Less),
+        (Op::Le, ..) => Value::Bool(ordering != Ordering::Greater),
+        (Op::Gt, ..) => Value::Bool(ordering == Ordering::Greater),
+        (Op::Ge, ..) => Value::Bool(ordering != Ordering::Less),
+        (Op::Add, Value::Null, r) => r.clone(),
+        (Op::Add, l, Value::Null) => l,
+        (Op::Add, Value::Number(a), Value::Number(b)) => number(float(&a) + float(b)),
+        (Op::Add, Value::String(a), Value::String(b)) => Value::String(a + b),
+        (Op::Add, Value::Array(mut a), Value::Array(b)) => {
+            a.extend(b.iter().cloned());
+            Value::Array(a)
+        },
+        (Op::Add, Value::Object(mut a), Value::Object(b)) => {
+            a.extend(b.clone());
+            Value::Object(a)
+        },
+        (Op::Sub, Value::Number(a), Value::Number(b)) => number(float(&a) - float(b)),
+        (Op::Sub, Value::Array(a), Value::Array(b)) => {
+            Value::Array(a.into_iter().filter(|v| !b.contains(v)).collect())
+        },
+        (Op::Mul, Value::Number(a), Value::Number(b)) => number(float(&a) * float(b)),
+        (Op::Div, Value::Number(a), Value::Number(b)) => {
+            if float(b) == 0.0 {
+                return Err(format!(
+                    "{} and {} cannot be divided because the divisor is zero",
+                    describe(&Value::Number(a)),
+                    describe(r)
+                ));
+            }
+            number(float(&a) / float(b))
+        },
+        (op, l, r) => {
+            let verb = match op {
+                Op::Add => "added",
+                Op::Sub => "subtracted",
+                Op::Mul => "multiplied",
+                _ => "divided",
+            };
+            return Err(format!(
+                "{} and {} cannot be {verb}",
+                describe(&l),
+                describe(r)
+            ));
+        },
+    };
+    Ok(value)
+}
+
+fn call(name: &str, args: &[Filter], input: &Value) -> Result<Vec<Value>, String> {
+    let value = match (name, args) {
+        ("empty", []) => return Ok(Vec::new()),
+        ("not", []) => Value::Bool(!truthy(input)),
+        ("type", []) => Value::String(kind(input).to_owned()),
+        ("length", []) => match input {
+            Value::Null => number(0.0),
+            Value::Number(n) => number(float(n).abs()),
+            Value::String(s) => Value::from(s.chars().count()),
+            Value::Array(items) => Value::from(items.len()),
+            Value::Object(map) => Value::from(map.len()),
+            Value::Bool(_) => return Err(format!("{} has no length", describe(input))),
+        },
+        ("keys", []) => match input {
+            Value::Object(map) => {
+                let mut keys = map.keys().cloned().collect::<Vec<_>>();
+                keys.sort();
+                Value::from(keys)
+            },
+            Value::Array(items) => Value::from((0..items.len()).collect::<Vec<_>>()),
+            _ => return Err(format!("{} has no keys", describe(input))),
+        },
+        ("add", []) => {
+            let mut sum = Value::Null;
+            for value in iterate(input)? {
+                sum = arithmetic(Op::Add, sum, &value)?;
+            }
+            sum
+        },
+        ("reverse", []) => match input {
+            Value::Array(items) => Value::Array(items.iter().rev().cloned().collect()),
+            Value::String(s) => Value::String(s.chars().rev().collect()),
+            Value::Null => Value::Array(Vec::new()),
+            _ => return Err(format!("Cannot reverse {}", describe(input))),
+        },
+        ("sort", []) => {
+            let mut items = array(input, "sorted")?.clone();
+            items.sort_by(compare);
+            Value::Array(items)
+        },
+        ("sort_by", [f]) => {
+            let items = array(input, "sorted")?;
+            let mut keyed = items
+                .iter()
+                .map(|item| Ok((Value::Array(eval(f, item)?), item.clone())))
+                .collect::<Result<Vec<_>, String>>()?;
+            // a stable sort keeps items with equal keys in order, as jq does
+            keyed.sort_by(|(a, _), (b, _)| compare(a, b));
+            Value::Array(keyed.into_iter().map(|(_, item)| item).collect())
+        },
+        ("map", [f]) => {
+            let mut out = Vec::new();
+            for item in iterate(input)? {
+                out.extend(eval(f, &item)?);
+            }
+            Value::Array(out)
+        },
+        ("select", [f]) => {
+            let selected = eval(f, input)?.iter().filter(|v| truthy(v)).count();
+            return Ok(vec![input.clone(); selected]);
+        },
+        (name, args) => return Err(format!("{name}/{} is not defined", args.len())),
+    };
+    Ok(vec![value])
+}
+
+fn array<'a>(value: &'a Value, verb: &str) -> Result<&'a Vec<Value>, String> {
+    match value {
+        Value::Array(items) => Ok(items),
+        value => Err(format!(
+            "{} cannot be {verb}, as it is not an array",
+            describe(value)
+        )),
+    }
+}
+
+/// Whether jq takes a value as true, which all but `false` and `null` are
+fn truthy(value: &Value) -> bool {
+    !matches!(value, Value::Null | Value::Bool(false))
+}
+
+fn float(n: &serde_json::Number) -> f64 {
+    n.as_f64().unwrap_or_default()
+}
+
+/// The order jq sorts values in: null, false, true, numbers, strings, arrays, then objects
+pub fn compare(a: &Value, b: &Value) -> Ordering {
+    fn rank(value: &Value) -> u8 {
+        match value {
+            Value::Null => 0,
+            Value::Bool(false) => 1,
+            Value::Bool(true) => 2,
+            Value::Number(_) => 3,
+            Value::String(_) => 4,
+            Value::Array(_) => 5,
+            Value::Object(_) => 6,
+        }
+    }
+
+    match (a, b) {
+        (Value::Number(a), Value::Number(b)) => float(a).total_cmp(&float(b)),
+        (Value::String(a), Value::String(b)) => a.cmp(b),
+        (Value::Array(a), Value::Array(b)) => a
+            .iter()
+            .zip(b)
+            .map(|(a, b)| compare(a, b))
+            .find(|o| o.is_ne())
+            .unwrap_or(a.len().cmp(&b.len())),
+        // objects compare by their sorted keys first, then by the values of those keys
+        (Value::Object(a), Value::Object(b)) => {
+            let mut keys = a.keys().collect::<Vec<_>>();
+            let mut other = b.keys().collect::<Vec<_>>();
+            keys.sort();
+            other.sort();
+            keys.cmp(&other).then_with(|| {
+                keys.iter()
+                    .map(|k| compare(&a[k.as_str()], &b[k.as_str()]))
+                    .find(|o| o.is_ne())
+                    .unwrap_or(Ordering::Equal)
+            })
+        },
+        (a, b) => rank(a).cmp(&rank(b)),
+    }
+}
+
+/// The type of a value, as `type` gives it
+fn kind(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// A value with its type, as jq names it in errors, like `number (5)`
+fn describe(value: &Value) -> String {
+    const MAX: usize = 11;
+
+    let text = value.to_string();
+    let text = match text.char_indices().nth(MAX) {
+        Some((i, _)) => format!("{}...", &text[..i]),
+        None => text,
+    };
+    format!("{} ({text})", kind(value))
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+    use serde_json::json;
+
+    use super::*;
+    use crate::shell::commands::jq::parser::parse;
+
+    fn repos() -> Value {
+        json!([
+            {"name": "wcli", "stars": 12, "language": "Rust", "topics": ["cli", "wasm"]},
+            {"name": "dotfiles", "stars": 3, "language": null, "topics": []},
+            {"name": "blog", "stars": 7, "language": "Rust", "topics": ["web"]},
+        ])
+    }
+
+    #[rstest]
+    #[case::identity(".", &[repos()])]
+    #[case::path(".[0].name", &[json!("wcli")])]
+    #[case::negative_index(".[-1].name", &[json!("blog")])]
+    #[case::out_of_range(".[9]", &[json!(null)])]
+    #[case::null_path(".[1].language.name", &[json!(null)])]
+    #[case::string_key(r#".[0]["stars"]"#, &[json!(12)])]
+    #[case::iterate(".[].stars", &[json!(12), json!(3), json!(7)])]
+    #[case::nested(".[].topics[]", &[json!("cli"), json!("wasm"), json!("web")])]
+    #[case::comma(".[0] | .name, .stars", &[json!("wcli"), json!(12)])]
+    #[case::length("length", &[json!(3)])]
+    #[case::string_length(".[0].name | length", &[json!(4)])]
+    #[case::keys(".[0] | keys", &[json!(["language", "name", "stars", "topics"])])]
+    #[case::map("map(.stars)", &[json!([12, 3, 7])])]
+    #[case::select(
+        r#".[] | select(.language == "Rust") | .name"#,
+        &[json!("wcli"), json!("blog")]
+    )]
+    #[case::select_and(
+        ".[] | select(.stars > 5 and .language) | .name",
+        &[json!("wcli"), json!("blog")]
+    )]
+    #[case::sort_by("sort_by(.stars) | map(.name)", &[json!(["dotfiles", "blog", "wcli"])])]
+    #[case::sort_by_null("sort_by(.language) | .[0].name", &[json!("dotfiles")])]
+    #[case::sort("map(.stars) | sort", &[json!([3, 7, 12])])]
+    #[case::add("map(.stars) | add", &[json!(22)])]
+    #[case::arithmetic(".[0].stars * 2 - 4 / 8", &[json!(23.5)])]
+    #[case::array("[.[].name]", &[json!(["wcli", "dotfiles", "blog"])])]
+    #[case::object(
+        ".[0] | {name, count: .topics | length}",
+        &[json!({"name": "wcli", "count": 2})]
+    )]
+    #[case::object_product(
+        "{n: (1, 2), m: 3}",
+        &[json!({"n": 1, "m": 3}), json!({"n": 2, "m": 3})]
+    )]
+    #[case::type_("map(.language | type)", &[json!(["string", "null", "string"])])]
+    #[case::not(".[1].language | not", &[json!(true)])]
+    #[case::empty("[.[] | empty]", &[json!([])])]
+    fn test_eval(#[case] filter: &str, #[case] expected: &[Value]) {
+        let filter = parse(filter).unwrap();
+        assert_eq!(eval(&filter, &repos()).unwrap(), expected);
+    }
+
+    #[rstest]
+    #[case::index_array(".name", r#"Cannot index array with "name""#)]
+    #[case::index_string(".[0].name[0]", "Cannot index string with number")]
+    #[case::iterate(".[0].stars[]", "Cannot iterate over number (12)")]
+    #[case::length(".[0].stars > 1 | length", "boolean (true) has no length")]
+    #[case::keys(".[0].name | keys", r#"string ("wcli") has no keys"#)]
+    #[case::sort(
+        ".[0] | sort",
+        r#"object ({"name":"wc...) cannot be sorted, as it is not an array"#
+    )]
+    #[case::add(".[0].name + 1", r#"string ("wcli") and number (1) cannot be added"#)]
+    #[case::divide(
+        "1 / 0",
+        "number (1) and number (0) cannot be divided because the divisor is zero"
+    )]
+    #[case::undefined("nope(.)", "nope/1 is not defined")]
+    fn test_eval_error(#[case] filter: &str, #[case] expected: &str) {
+        let filter = parse(filter).unwrap();
+        assert_eq!(eval(&filter, &repos()), Err(expected.to_owned()));
+    }
+
+    #[test]
+    fn test_compare() {
+        let mut values = vec![
+            json!({"a": 1}),
+            json!([1]),
+            json!("a"),
+            json!(1),
+            json!(true),
+            json!(false),
+            json!(null),
+        ];
+        values.sort_by(compare);
+        assert_eq!(
+            values,
+            [
+                json!(null),
+                json!(false),
+                json!(true),
+                json!(1),
+                json!("a"),
+                json!([1]),
+                json!({"a": 1})
+            ]
+        );
+    }
+}
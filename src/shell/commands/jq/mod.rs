@@ -0,0 +1,227 @@
+use serde_json::Value;
+
+use self::eval::eval;
+use self::parser::parse;
+use super::{Command, getopt, input, printed, usage_error};
+use crate::shell::output::CommandOutput;
+use crate::shell::process::Process;
+
+mod eval;
+mod parser;
+
+/// SGR parameters of each kind of value, as jq colors them
+mod colors {
+    pub const NULL: &str = "0;90";
+    pub const SCALAR: &str = "0;39";
+    pub const STRING: &str = "0;32";
+    /// Brackets, braces and the punctuation between values
+    pub const CONTAINER: &str = "1;39";
+    pub const KEY: &str = "34;1";
+}
+
+/// Spaces a level of pretty-printed JSON is indented by
+const INDENT: usize = 2;
+
+pub struct Jq;
+
+impl Command for Jq {
+    const NAME: &'static str = "jq";
+    const DESCRIPTION: &'static str = "query JSON";
+    const USAGE: &'static str = "\t\
+    jq filter [file]...     run the filter on the JSON in the files, or piped in,
+                            as in `projects --json | jq '.[0].name'`
+    jq -r filter [file]...  print strings as they are, without quotes
+    jq -c filter [file]...  print each value on a line of its own
+
+    .         the value         f | g        g on each value of f
+    .name     a key of it       f, g         the values of f, then those of g
+    .[0]      an item of it     [f], {k: f}  an array, an object
+    .[]       each item         length, keys, map(f), select(f), sort, sort_by(f),
+    ==, <, +  compare, add      add, reverse, type, not, empty";
+
+    fn run(args: Vec<String>, process: Process) -> CommandOutput {
+        let (flags, operands) = match getopt(args, "rc", Self::USAGE) {
+            Ok(parsed) => parsed,
+            Err(output) => return output,
+        };
+        let Some((filter, files)) = operands.split_first() else {
+            return usage_error("jq: missing filter".to_owned(), Self::USAGE);
+        };
+        let filter = match parse(filter) {
+            Ok(filter) => filter,
+            Err(e) => return CommandOutput::error(format!("jq: compile error: {e}"), 3),
+        };
+        let format = Format {
+            raw: flags.iter().any(|(c, _)| *c == 'r'),
+            compact: flags.iter().any(|(c, _)| *c == 'c'),
+        };
+
+        let (text, mut errors) = input(Self::NAME, files, process);
+        let status = u8::from(!errors.is_empty());
+        let mut out = String::new();
+        for value in serde_json::Deserializer::from_str(&text).into_iter::<Value>() {
+            let value = match value {
+                Ok(value) => value,
+                Err(e) => {
+                    errors.push(format!("jq: parse error: {e}"));
+                    return printed(&out, errors).with_status(2);
+                },
+            };
+            match eval(&filter, &value) {
+                Ok(values) => values.iter().for_each(|v| format.print(v, &mut out)),
+                Err(e) => {
+                    errors.push(format!("jq: error: {e}"));
+                    return printed(&out, errors).with_status(5);
+                },
+            }
+        }
+        printed(&out, errors).with_status(status)
+    }
+}
+
+struct Format {
+    /// Prints strings without quotes, with `-r`
+    raw: bool,
+    /// Prints values on one line, with `-c`
+    compact: bool,
+}
+
+impl Format {
+    /// Prints an output of the filter on a line of its own
+    fn print(&self, value: &Value, out: &mut String) {
+        match value {
+            Value::String(s) if self.raw => out.push_str(s),
+            value => self.write(value, 0, out),
+        }
+        out.push('\n');
+    }
+
+    /// Writes a value colored as jq does, indented by `depth` levels unless compact
+    fn write(&self, value: &Value, depth: usize, out: &mut String) {
+        let (open, close, items): (_, _, Vec<(Option<&String>, &Value)>) = match value {
+            Value::Null => return paint(colors::NULL, "null", out),
+            Value::String(_) => return paint(colors::STRING, &value.to_string(), out),
+            Value::Bool(_) | Value::Number(_) => {
+                return paint(colors::SCALAR, &value.to_string(), out);
+            },
+            Value::Array(items) => ('[', ']', items.iter().map(|v| (None, v)).collect()),
+            Value::Object(map) => ('{', '}', map.iter().map(|(k, v)| (Some(k), v)).collect()),
+        };
+
+        paint(colors::CONTAINER, &open.to_string(), out);
+        for (i, (key, value)) in items.iter().enumerate() {
+            if i > 0 {
+                paint(colors::CONTAINER, ",", out);
+            }
+            self.newline(depth + 1, out);
+            if let Some(key) = key {
+                paint(colors::KEY, &Value::String((*key).clone()).to_string(), out);
+                paint(colors::CONTAINER, ":", out);
+                if !self.compact {
+                    out.push(' ');
+                }
+            }
+            self.write(value, depth + 1, out);
+        }
+        if !items.is_empty() {
+            self.newline(depth, out);
+        }
+        paint(colors::CONTAINER, &close.to_string(), out);
+    }
+
+    fn newline(&self, depth: usize, out: &mut String) {
+        if !self.compact {
+            out.push('\n');
+            out.push_str(&" ".repeat(depth * INDENT));
+        }
+    }
+}
+
+/// Writes `text` in the color of the SGR parameters, resetting it after
+fn paint(params: &str, text: &str, out: &mut String) {
+    out.push_str(&format!("\x1b[{params}m{text}\x1b[0m"));
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+    use crate::shell::output::{Block, COLUMNS, plain};
+
+    fn jq(args: &[&str], stdin: &str) -> CommandOutput {
+        let process = Process::new();
+        process.pipe(Some(stdin.to_owned()));
+        Jq::run(args.iter().map(ToString::to_string).collect(), process)
+    }
+
+    fn text(output: &CommandOutput) -> String {
+        let text = plain(output, COLUMNS);
+        text.strip_suffix('\n').unwrap_or(&text).to_owned()
+    }
+
+    #[rstest]
+    #[case::pretty(
+        &["."],
+        r#"{"name": "wcli", "topics": ["cli", "wasm"], "fork": false, "homepage": null, "x": {}}"#,
+        "{\n  \"name\": \"wcli\",\n  \"topics\": [\n    \"cli\",\n    \"wasm\"\n  ],\n  \
+         \"fork\": false,\n  \"homepage\": null,\n  \"x\": {}\n}"
+    )]
+    #[case::compact(&["-c", ".topics"], r#"{"topics": ["cli", "wasm"]}"#, r#"["cli","wasm"]"#)]
+    #[case::raw(&["-r", ".[]"], r#"["a", "b", 1]"#, "a\nb\n1")]
+    #[case::quoted(&[".[0]"], r#"["a"]"#, r#""a""#)]
+    #[case::stream(&[".n"], r#"{"n": 1} {"n": 2}"#, "1\n2")]
+    #[case::nothing(&["empty"], "{}", "")]
+    fn test_jq(#[case] args: &[&str], #[case] stdin: &str, #[case] expected: &str) {
+        let output = jq(args, stdin);
+        assert_eq!(text(&output), expected);
+        assert_eq!(output.status, 0);
+    }
+
+    #[test]
+    fn test_jq_colors() {
+        let mut out = String::new();
+        let format = Format {
+            raw: false,
+            compact: true,
+        };
+        format.write(&serde_json::json!({"a": [null]}), 0, &mut out);
+
+        assert_eq!(
+            out,
+            "\x1b[1;39m{\x1b[0m\x1b[34;1m\"a\"\x1b[0m\x1b[1;39m:\x1b[0m\
+             \x1b[1;39m[\x1b[0m\x1b[0;90mnull\x1b[0m\x1b[1;39m]\x1b[0m\x1b[1;39m}\x1b[0m"
+        );
+    }
+
+    #[rstest]
+    #[case::compile(&[".["], "[]", "jq: compile error: unexpected end of filter", 3)]
+    #[case::parse(&["."], "{", "jq: parse error: EOF while parsing an object at line 1 column 1", 2)]
+    #[case::runtime(&[".a"], "[1]", r#"jq: error: Cannot index array with "a""#, 5)]
+    fn test_jq_error(
+        #[case] args: &[&str],
+        #[case] stdin: &str,
+        #[case] expected: &str,
+        #[case] status: u8,
+    ) {
+        let output = jq(args, stdin);
+        assert_eq!(
+            output.blocks.last(),
+            Some(&Block::Error(expected.to_owned()))
+        );
+        assert_eq!(output.status, status);
+    }
+
+    #[test]
+    fn test_jq_keeps_values_before_an_error() {
+        let output = jq(&[".[0]"], "[1] 2");
+        assert_eq!(
+            output.blocks,
+            vec![
+                Block::Line(crate::shell::output::ansi::parse("\x1b[0;39m1\x1b[0m").remove(0)),
+                Block::Error("jq: error: Cannot index number with number".to_owned()),
+            ]
+        );
+        assert_eq!(output.status, 5);
+    }
+}
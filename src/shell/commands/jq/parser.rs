@@ -0,0 +1,582 @@
+use serde_json::Value;
+
+/// Most terms a filter may have within one another, like `[[.]]`
+const MAX_DEPTH: usize = 100;
+
+/// A jq filter, which turns a JSON value into any number of them
+#[derive(Debug, Clone, PartialEq)]
+pub enum Filter {
+    /// `.`
+    Identity,
+    /// `f.name`, `f["name"]` or `f[0]`, where the key is evaluated against `.`
+    Index(Box<Filter>, Box<Filter>),
+    /// `f[]`, which outputs each value of an array or an object
+    Iterate(Box<Filter>),
+    /// A number, a string, `true`, `false` or `null`
+    Literal(Value),
+    /// `f | g`, which runs `g` on each output of `f`
+    Pipe(Box<Filter>, Box<Filter>),
+    /// `f, g`, which outputs those of `f` then those of `g`
+    Comma(Box<Filter>, Box<Filter>),
+    /// `[f]`, which collects the outputs of `f`
+    Array(Option<Box<Filter>>),
+    /// `{key: f, ...}`, where a key is a name, a string or `(f)`
+    Object(Vec<(Filter, Filter)>),
+    Binary(Op, Box<Filter>, Box<Filter>),
+    /// `name` or `name(f; g)`
+    Call(String, Vec<Filter>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    And,
+    Or,
+}
+
+impl Op {
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Self::Add => "+",
+            Self::Sub => "-",
+            Self::Mul => "*",
+            Self::Div => "/",
+            Self::Eq => "==",
+            Self::Ne => "!=",
+            Self::Lt => "<",
+            Self::Le => "<=",
+            Self::Gt => ">",
+            Self::Ge => ">=",
+            Self::And => "and",
+            Self::Or => "or",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    /// `.` on its own
+    Dot,
+    /// `.name` or `."name"`
+    Field(String),
+    Ident(String),
+    Number(f64),
+    Str(String),
+    /// `[`, `]`, `{`, `}`, `(`, `)`, `|`, `,`, `:` or `;`
+    Punct(char),
+    Op(Op),
+}
+
+/// Parses a filter, like `.[] | select(.stars > 10) | .name`
+pub fn parse(src: &str) -> Result<Filter, String> {
+    let tokens = tokens(src)?;
+    let mut parser = Parser {
+        tokens,
+        idx: 0,
+        depth: 0,
+    };
+    let filter = parser.pipe()?;
+    match parser.peek() {
+        None => Ok(filter),
+        Some(token) => Err(format!("unexpected {}", describe(token))),
+    }
+}
+
+fn tokens(src: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = src.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '.' => match chars.peek() {
+                Some(&(_, c)) if c == '_' || c.is_ascii_alphabetic() => {
+                    Token::Field(ident(src, &mut chars))
+                },
+                Some((_, '"')) => {
+                    chars.next();
+                    Token::Field(string(&mut chars)?)
+                },
+                _ => Token::Dot,
+            },
+            '"' => Token::Str(string(&mut chars)?),
+            '0'..='9' => {
+                let mut end = start + 1;
+                while let Some(&(i, c)) = chars.peek() {
+                    if !c.is_ascii_digit() && c != '.' {
+                        break;
+                    }
+                    chars.next();
+                    end = i + 1;
+                }
+                let n = &src[start..end];
+                Token::Number(n.parse().map_err(|_| format!("invalid number {n}"))?)
+            },
+            c if c == '_' || c.is_ascii_alphabetic() => {
+                let mut name = c.to_string();
+                name.push_str(&ident(src, &mut chars));
+                match name.as_str() {
+                    "and" => Token::Op(Op::And),
+                    "or" => Token::Op(Op::Or),
+                    _ => Token::Ident(name),
+                }
+            },
+            '[' | ']' | '{' | '}' | '(' | ')' | '|' | ',' | ':' | ';' => Token::Punct(c),
+            '+' => Token::Op(Op::Add),
+            '-' => Token::Op(Op::Sub),
+            '*' => Token::Op(Op::Mul),
+            '/' => Token::Op(Op::Div),
+            '=' | '!' | '<' | '>' => {
+                let eq = chars.next_if(|&(_, c)| c == '=').is_some();
+                Token::Op(match (c, eq) {
+                    ('=', true) => Op::Eq,
+                    ('!', true) => Op::Ne,
+                    ('<', false) => Op::Lt,
+                    ('<', true) => Op::Le,
+                    ('>', false) => Op::Gt,
+                    ('>', true) => Op::Ge,
+                    _ => return Err(format!("unexpected `{c}`")),
+                })
+            },
+            c => return Err(format!("unexpected `{c}`")),
+        };
+        tokens.push(token);
+    }
+    Ok(tokens)
+}
+
+/// Reads the rest of a name, the first character of which has been read
+fn ident(src: &str, chars: &mut std::iter::Peekable<std::str::CharIndices>) -> String {
+    let mut name = String::new();
+    while let Some(&(i, c)) = chars.peek() {
+        if c != '_' && !c.is_ascii_alphanumeric() {
+            break;
+        }
+        chars.next();
+        name.push_str(&src[i..i + c.len_utf8()]);
+    }
+    name
+}
+
+/// Reads a string up to its closing quote, with the escapes of JSON
+fn string(chars: &mut std::iter::Peekable<std::str::CharIndices>) -> Result<String, String> {
+    let mut s = String::new();
+    loop {
+        let Some((_, c)) = chars.next() else {
+            return Err("unterminated string".to_owned());
+        };
+        match c {
+            '"' => return Ok(s),
+            '\\' => {
+                let Some((_, e)) = chars.next() else {
+                    return Err("unterminated string".to_owned());
+                };
+                s.push(match e {
+                    'n' => '\n',
+                    't' => '\t',
+                    'r' => '\r',
+                    'b' => '\x08',
+                    'f' => '\x0c',
+                    'u' => {
+                        let hex = chars.by_ref().take(4).map(|(_, c)| c).collect::<String>();
+                        u32::from_str_radix(&hex, 16)
+                            .ok()
+                            .and_then(char::from_u32)
+                            .ok_or_else(|| format!("invalid escape \\u{hex}"))?
+                    },
+                    '"' | '\\' | '/' => e,
+                    e => return Err(format!("invalid escape \\{e}")),
+                });
+            },
+            c => s.push(c),
+        }
+    }
+}
+
+fn describe(token: &Token) -> String {
+    match token {
+        Token::Dot => "`.`".to_owned(),
+        Token::Field(name) => format!("`.{name}`"),
+        Token::Ident(name) => format!("`{name}`"),
+        Token::Number(n) => format!("`{n}`"),
+        Token::Str(s) => format!("{s:?}"),
+        Token::Punct(c) => format!("`{c}`"),
+        Token::Op(op) => format!("`{}`", op.symbol()),
+    }
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    idx: usize,
+    /// How many terms the one being parsed is within, e.g. 2 for the `.a` in `[(.a)]`
+    depth: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.idx)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.idx).cloned();
+        self.idx += 1;
+        token
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        let found = self.peek() == Some(&Token::Punct(c));
+        if found {
+            self.idx += 1;
+        }
+        found
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), String> {
+        match self.eat(c) {
+            true => Ok(()),
+            false => Err(match self.peek() {
+                Some(token) => format!("unexpected {}, expected `{c}`", describe(token)),
+                None => format!("unexpected end of filter, expected `{c}`"),
+            }),
+        }
+    }
+
+    /// `f | g`, the loosest of all
+    fn pipe(&mut self) -> Result<Filter, String> {
+        let mut filter = self.comma()?;
+        while self.eat('|') {
+            filter = Filter::Pipe(Box::new(filter), Box::new(self.comma()?));
+        }
+        Ok(filter)
+    }
+
+    fn comma(&mut self) -> Result<Filter, String> {
+        let mut filter = self.or()?;
+        while self.eat(',') {
+            filter = Filter::Comma(Box::new(filter), Box::new(self.or()?));
+        }
+        Ok(filter)
+    }
+
+    fn or(&mut self) -> Result<Filter, String> {
+        self.binary(&[Op::Or], Self::and)
+    }
+
+    fn and(&mut self) -> Result<Filter, String> {
+        self.binary(&[Op::And], Self::comparison)
+    }
+
+    fn comparison(&mut self) -> Result<Filter, String> {
+        let left = self.additive()?;
+        match self.peek() {
+            Some(&Token::Op(op @ (Op::Eq | Op::Ne | Op::Lt | Op::Le | Op::Gt | Op::Ge))) => {
+                self.next();
+                let right = self.additive()?;
+                Ok(Filter::Binary(op, Box::new(left), Box::new(right)))
+            },
+            _ => Ok(left),
+        }
+    }
+
+    fn additive(&mut self) -> Result<Filter, String> {
+        self.binary(&[Op::Add, Op::Sub], Self::multiplicative)
+    }
+
+    fn multiplicative(&mut self) -> Result<Filter, String> {
+        self.binary(&[Op::Mul, Op::Div], Self::postfix)
+    }
+
+    /// Left-associative operators in `ops`, between what `operand` parses
+    fn binary(
+        &mut self,
+        ops: &[Op],
+        operand: fn(&mut Self) -> Result<Filter, String>,
+    ) -> Result<Filter, String> {
+        let mut filter = operand(self)?;
+        while let Some(&Token::Op(op)) = self.peek()
+            && ops.contains(&op)
+        {
+            self.next();
+            filter = Filter::Binary(op, Box::new(filter), Box::new(operand(self)?));
+        }
+        Ok(filter)
+    }
+
+    /// A term followed by any number of `.name`, `[...]` and `[]`
+    fn postfix(&mut self) -> Result<Filter, String> {
+        let mut filter = self.nested()?;
+        loop {
+            filter = match self.peek() {
+                Some(Token::Field(name)) => {
+                    let key = Filter::Literal(Value::String(name.clone()));
+                    self.next();
+                    Filter::Index(Box::new(filter), Box::new(key))
+                },
+                Some(Token::Punct('[')) => {
+                    self.next();
+                    self.brackets(filter)?
+                },
+                // `.[0]` after a term, as in `.a.[0]`
+                Some(Token::Dot) if self.tokens.get(self.idx + 1) == Some(&Token::Punct('[')) => {
+                    self.idx += 2;
+                    self.brackets(filter)?
+                },
+                _ => return Ok(filter),
+            };
+        }
+    }
+
+    /// What follows a `[` after a term: `]` to iterate, or a key and `]`
+    fn brackets(&mut self, filter: Filter) -> Result<Filter, String> {
+        if self.eat(']') {
+            return Ok(Filter::Iterate(Box::new(filter)));
+        }
+        let key = self.pipe()?;
+        self.expect(']')?;
+        Ok(Filter::Index(Box::new(filter), Box::new(key)))
+    }
+
+    /// A term, which may hold filters of its own, like `[f]`, so it can't be nested too deeply
+    /// without running out of stack
+    fn nested(&mut self) -> Result<Filter, String> {
+        if self.depth >= MAX_DEPTH {
+            return Err("filter nested too deeply".to_owned());
+        }
+        self.depth += 1;
+        let filter = self.term();
+        self.depth -= 1;
+        filter
+    }
+
+    fn term(&mut self) -> Result<Filter, String> {
+        let Some(token) = self.next() else {
+            return Err("unexpected end of filter".to_owned());
+        };
+        match token {
+            Token::Dot => match self.peek() {
+                Some(Token::Punct('[')) => {
+                    self.next();
+                    self.brackets(Filter::Identity)
+                },
+                _ => Ok(Filter::Identity),
+            },
+            Token::Field(name) => Ok(Filter::Index(
+                Box::new(Filter::Identity),
+                Box::new(Filter::Literal(Value::String(name))),
+            )),
+            Token::Number(n) => Ok(Filter::Literal(number(n))),
+            Token::Str(s) => Ok(Filter::Literal(Value::String(s))),
+            // a negative number
+            Token::Op(Op::Sub) => match self.next() {
+                Some(Token::Number(n)) => Ok(Filter::Literal(number(-n))),
+                _ => Err("unexpected `-`".to_owned()),
+            },
+            Token::Punct('(') => {
+                let filter = self.pipe()?;
+                self.expect(')')?;
+                Ok(filter)
+            },
+            Token::Punct('[') => {
+                if self.eat(']') {
+                    return Ok(Filter::Array(None));
+                }
+                let filter = self.pipe()?;
+                self.expect(']')?;
+                Ok(Filter::Array(Some(Box::new(filter))))
+            },
+            Token::Punct('{') => self.object(),
+            Token::Ident(name) => match name.as_str() {
+                "true" => Ok(Filter::Literal(Value::Bool(true))),
+                "false" => Ok(Filter::Literal(Value::Bool(false))),
+                "null" => Ok(Filter::Literal(Value::Null)),
+                _ => {
+                    let mut args = Vec::new();
+                    if self.eat('(') {
+                        args.push(self.pipe()?);
+                        while self.eat(';') {
+                            args.push(self.pipe()?);
+                        }
+                        self.expect(')')?;
+                    }
+                    Ok(Filter::Call(name, args))
+                },
+            },
+            token => Err(format!("unexpected {}", describe(&token))),
+        }
+    }
+
+    /// The value of an entry of an object, which can be a pipe but not a comma,
+    /// as that ends the entry
+    fn value(&mut self) -> Result<Filter, String> {
+        let mut filter = self.or()?;
+        while self.eat('|') {
+            filter = Filter::Pipe(Box::new(filter), Box::new(self.or()?));
+        }
+        Ok(filter)
+    }
+
+    /// The entries of `{...}`, after the `{`
+    fn object(&mut self) -> Result<Filter, String> {
+        let mut entries = Vec::new();
+        if self.eat('}') {
+            return Ok(Filter::Object(entries));
+        }
+
+        loop {
+            let (key, shorthand) = match self.next() {
+                Some(Token::Ident(name) | Token::Str(name)) => {
+                    (Filter::Literal(Value::String(name.clone())), Some(name))
+                },
+                Some(Token::Punct('(')) => {
+                    let key = self.pipe()?;
+                    self.expect(')')?;
+                    (key, None)
+                },
+                Some(token) => return Err(format!("unexpected {} in object", describe(&token))),
+                None => return Err("unexpected end of filter, expected `}`".to_owned()),
+            };
+
+            let value = match (self.eat(':'), shorthand) {
+                (true, _) => self.value()?,
+                // `{name}` is short for `{name: .name}`
+                (false, Some(name)) => Filter::Index(
+                    Box::new(Filter::Identity),
+                    Box::new(Filter::Literal(Value::String(name))),
+                ),
+                (false, None) => return Err("expected `:` after a computed key".to_owned()),
+            };
+            entries.push((key, value));
+
+            if !self.eat(',') {
+                self.expect('}')?;
+                return Ok(Filter::Object(entries));
+            }
+        }
+    }
+}
+
+/// A number as a JSON value, which is an integer when it can be
+pub fn number(n: f64) -> Value {
+    if n.fract() == 0.0 && n.abs() < 1e15 {
+        Value::from(n as i64)
+    } else {
+        serde_json::Number::from_f64(n).map_or(Value::Null, Value::Number)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+    use serde_json::json;
+
+    use super::*;
+
+    fn field(name: &str) -> Filter {
+        Filter::Index(
+            Box::new(Filter::Identity),
+            Box::new(Filter::Literal(json!(name))),
+        )
+    }
+
+    #[test]
+    fn test_parse_path() {
+        assert_eq!(
+            parse(".[0].name").unwrap(),
+            Filter::Index(
+                Box::new(Filter::Index(
+                    Box::new(Filter::Identity),
+                    Box::new(Filter::Literal(json!(0)))
+                )),
+                Box::new(Filter::Literal(json!("name"))),
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse_pipe() {
+        assert_eq!(
+            parse(".[] | .name").unwrap(),
+            Filter::Pipe(
+                Box::new(Filter::Iterate(Box::new(Filter::Identity))),
+                Box::new(field("name")),
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse_precedence() {
+        assert_eq!(
+            parse(".a > 1 and .b").unwrap(),
+            Filter::Binary(
+                Op::And,
+                Box::new(Filter::Binary(
+                    Op::Gt,
+                    Box::new(field("a")),
+                    Box::new(Filter::Literal(json!(1)))
+                )),
+                Box::new(field("b")),
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse_call() {
+        assert_eq!(
+            parse("sort_by(.stars)").unwrap(),
+            Filter::Call("sort_by".to_owned(), vec![field("stars")])
+        );
+    }
+
+    #[test]
+    fn test_parse_object() {
+        assert_eq!(
+            parse(r#"{name, "n": -1}"#).unwrap(),
+            Filter::Object(vec![
+                (Filter::Literal(json!("name")), field("name")),
+                (Filter::Literal(json!("n")), Filter::Literal(json!(-1))),
+            ])
+        );
+    }
+
+    #[rstest]
+    #[case::quoted_field(r#"."a""#, ".a")]
+    #[case::string_key(r#".["a"]"#, ".a")]
+    #[case::dot_bracket(".a.[0]", ".a[0]")]
+    #[case::parens("(.a)", ".a")]
+    fn test_parse_same(#[case] src: &str, #[case] same: &str) {
+        assert_eq!(parse(src), parse(same));
+    }
+
+    #[rstest]
+    #[case::unclosed(".[0", "unexpected end of filter, expected `]`")]
+    #[case::stray(".a )", "unexpected `)`")]
+    #[case::string(r#"."a"#, "unterminated string")]
+    #[case::empty("", "unexpected end of filter")]
+    #[case::char(".a @", "unexpected `@`")]
+    fn test_parse_error(#[case] src: &str, #[case] expected: &str) {
+        assert_eq!(parse(src), Err(expected.to_owned()));
+    }
+
+    #[rstest]
+    #[case::arrays("[", "]")]
+    #[case::parens("(", ")")]
+    #[case::objects("{a:", "}")]
+    #[case::brackets(".[", "]")]
+    fn test_parse_depth(#[case] open: &str, #[case] close: &str) {
+        let nested = |depth: usize| format!("{}.{}", open.repeat(depth), close.repeat(depth));
+
+        assert!(parse(&nested(MAX_DEPTH - 1)).is_ok());
+        assert_eq!(
+            parse(&nested(100_000)),
+            Err("filter nested too deeply".to_owned())
+        );
+    }
+}
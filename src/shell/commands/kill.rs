@@ -0,0 +1,34 @@
+use super::Command;
+use crate::shell::jobs::{JobSpec, Status, use_jobs};
+use crate::shell::output::CommandOutput;
+use crate::shell::process::Process;
+
+pub struct Kill;
+
+impl Command for Kill {
+    const NAME: &'static str = "kill";
+    const DESCRIPTION: &'static str = "cancel a background job";
+    const USAGE: &'static str = "\t\
+    kill %n  cancel job n
+    kill %%  cancel the current job";
+
+    fn run(args: Vec<String>, _: Process) -> CommandOutput {
+        let Some(arg) = args.first() else {
+            return CommandOutput::error("kill: usage: kill %job", 2);
+        };
+
+        let jobs = use_jobs().expect("not yet created");
+        let found = JobSpec::parse(Some(arg)).and_then(|spec| jobs.find(spec));
+        let Some((job, marker)) = found else {
+            return CommandOutput::error(format!("kill: {arg}: no such job"), 1);
+        };
+
+        jobs.kill(job.number);
+        // a job that's already done has nothing left to cancel
+        if job.status == Status::Running {
+            CommandOutput::line(format!("[{}]{marker} Terminated {}", job.number, job.input))
+        } else {
+            CommandOutput::default()
+        }
+    }
+}
@@ -0,0 +1,185 @@
+use std::ops::RangeInclusive;
+use std::time::Duration;
+
+use leptos::prelude::*;
+
+use super::Command;
+use crate::components::screen::Animate;
+use crate::shell::output::CommandOutput;
+use crate::shell::process::Process;
+use crate::shell::raw::{Animation, Cell, Color, Flow, Grid};
+
+const ROWS: usize = 24;
+const COLS: usize = 80;
+const FRAME_RATE: Duration = Duration::from_millis(60);
+/// Half-width katakana, which take up a single cell like the digits mixed in with them
+const KATAKANA: RangeInclusive<char> = '\u{FF66}'..='\u{FF9D}';
+
+pub struct Matrix;
+
+impl Command for Matrix {
+    const NAME: &'static str = "matrix";
+    const DESCRIPTION: &'static str = "digital rain";
+    const USAGE: &'static str = "\t\
+    matrix  watch glyphs rain down the screen
+            press Ctrl+C to stop it";
+
+    fn run(_: Vec<String>, _: Process) -> CommandOutput {
+        CommandOutput::view(|| view! { <Animate animation=Rain::new(fastrand::u64(..)) /> })
+    }
+}
+
+/// A trail of glyphs falling down a column
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Drop {
+    /// Row of the leading glyph, negative while it's still above the screen
+    head: isize,
+    len: usize,
+    /// Whether the drop falls at half speed
+    slow: bool,
+}
+
+impl Drop {
+    fn new(rng: &mut fastrand::Rng) -> Self {
+        Self {
+            head: -rng.isize(0..ROWS as isize),
+            len: rng.usize(4..ROWS / 2 + 4),
+            slow: rng.bool(),
+        }
+    }
+}
+
+/// Falls forever, until stopped
+#[derive(Debug, Clone)]
+struct Rain {
+    /// One drop per column
+    drops: Vec<Drop>,
+    /// The glyph in each cell, revealed as drops pass over it
+    glyphs: Vec<char>,
+    frame: u64,
+    rng: fastrand::Rng,
+}
+
+impl Rain {
+    fn new(seed: u64) -> Self {
+        let mut rng = fastrand::Rng::with_seed(seed);
+        let drops = (0..COLS).map(|_| Drop::new(&mut rng)).collect();
+        let glyphs = (0..ROWS * COLS).map(|_| Self::glyph(&mut rng)).collect();
+
+        Self {
+            drops,
+            glyphs,
+            frame: 0,
+            rng,
+        }
+    }
+
+    fn glyph(rng: &mut fastrand::Rng) -> char {
+        match rng.u8(..4) {
+            0 => rng.digit(10),
+            _ => rng.char(KATAKANA),
+        }
+    }
+}
+
+impl Animation for Rain {
+    fn size(&self) -> (usize, usize) {
+        (ROWS, COLS)
+    }
+
+    fn frame_rate(&self) -> Duration {
+        FRAME_RATE
+    }
+
+    fn advance(&mut self) -> Flow {
+        self.frame += 1;
+        for drop in &mut self.drops {
+            if drop.slow && self.frame % 2 == 1 {
+                continue;
+            }
+            drop.head += 1;
+            // start over from the top once the whole trail is off the screen
+            if drop.head - drop.len as isize >= ROWS as isize {
+                *drop = Drop::new(&mut self.rng);
+            }
+        }
+
+        // a few glyphs change on every frame, so trails shimmer as they fall
+        for _ in 0..COLS / 4 {
+            let i = self.rng.usize(..self.glyphs.len());
+            self.glyphs[i] = Self::glyph(&mut self.rng);
+        }
+
+        Flow::Continue
+    }
+
+    fn draw(&self, grid: &mut Grid) {
+        for (col, drop) in self.drops.iter().enumerate() {
+            for row in 0..ROWS {
+                let ch = self.glyphs[row * COLS + col];
+                let cell = match drop.head - row as isize {
+                    0 => Cell {
+                        bold: true,
+                        ..Cell::new(ch, Color::Foreground)
+                    },
+                    // the end of the trail fades into a dimmer color
+                    d if d > 0 && (d as usize) < drop.len * 2 / 3 => Cell::new(ch, Color::Green),
+                    d if d > 0 && (d as usize) < drop.len => Cell::new(ch, Color::Primary),
+                    _ => continue,
+                };
+                grid.set(row, col, cell);
+            }
+        }
+    }
+
+    fn still(&mut self) {
+        // let the rain fill the screen first
+        for _ in 0..ROWS {
+            self.advance();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_drops_fall() {
+        let mut rain = Rain::new(42);
+        let before = rain.drops.clone();
+        rain.advance();
+        rain.advance();
+
+        for (before, after) in before.iter().zip(&rain.drops) {
+            let fallen = if before.slow { 1 } else { 2 };
+            assert_eq!(after.head, before.head + fallen);
+        }
+    }
+
+    #[test]
+    fn test_drops_start_over() {
+        let mut rain = Rain::new(42);
+        rain.drops[0] = Drop {
+            head: (ROWS + 3) as isize,
+            len: 4,
+            slow: false,
+        };
+        rain.advance();
+
+        assert!(rain.drops[0].head <= 0);
+    }
+
+    #[test]
+    fn test_still_fills_the_screen() {
+        let mut rain = Rain::new(42);
+        let mut grid = Grid::new(ROWS, COLS);
+        rain.draw(&mut grid);
+        let blank = grid.lines().flatten().filter(|c| c.ch == ' ').count();
+
+        rain.still();
+        grid.clear();
+        rain.draw(&mut grid);
+        assert!(grid.lines().flatten().filter(|c| c.ch == ' ').count() < blank);
+    }
+}
@@ -1,36 +1,98 @@
-use leptos::prelude::*;
-use leptos::reactive::wrappers::write::SignalSetter;
+use leptos::prelude::WithUntracked;
 use strum::{Display, EnumIter, EnumString, VariantNames};
 
 use self::ack::Ack;
+use self::adventure::Adventure;
+use self::cat::Cat;
 use self::clear::Clear;
+use self::cut::Cut;
 use self::echo::Echo;
+use self::edit::Edit;
 use self::fetch::Fetch;
+use self::fg::Fg;
+use self::head::Head;
 use self::help::Help;
+use self::jobs::Jobs;
+use self::jq::Jq;
+use self::kill::Kill;
+use self::matrix::Matrix;
+use self::printf::Printf;
 use self::projects::Projects;
+use self::repo::Repo;
+use self::sl::Sl;
+use self::snake::Snake;
+use self::sort::Sort;
+use self::source::Source;
 use self::stack::Stack;
+use self::tail::Tail;
 use self::theme::Theme;
+use self::tr::Tr;
+use self::uniq::Uniq;
+use self::wc::Wc;
+use super::output::{Block, CommandOutput, Line, Span, Style};
+use super::process::Process;
+use crate::stores::fs::{self, Fs, use_fs};
 
 pub mod ack;
+pub mod adventure;
+pub mod cat;
 pub mod clear;
+pub mod cut;
 pub mod echo;
+pub mod edit;
 pub mod fetch;
+pub mod fg;
+pub mod head;
 pub mod help;
+pub mod jobs;
+pub mod jq;
+pub mod kill;
+pub mod matrix;
+pub mod printf;
 pub mod projects;
+pub mod repo;
+pub mod sl;
+pub mod snake;
+pub mod sort;
+pub mod source;
 pub mod stack;
+pub mod tail;
 pub mod theme;
+pub mod tr;
+pub mod uniq;
+pub mod wc;
 
 #[derive(Debug, Clone, Copy, EnumString, EnumIter, Display, VariantNames)]
 #[strum(serialize_all = "snake_case")]
 pub enum Palette {
     Ack,
+    Adventure,
+    Cat,
     Clear,
+    Cut,
     Echo,
+    Edit,
     Fetch,
+    Fg,
+    Head,
     Help,
+    Jobs,
+    Jq,
+    Kill,
+    Matrix,
+    Printf,
     Projects,
+    Repo,
+    Sl,
+    Snake,
+    Sort,
+    Source,
     Stack,
+    Tail,
     Theme,
+    Tr,
+    Uniq,
+    Wc,
 }
 
 impl Palette {
@@ -38,52 +100,125 @@ impl Palette {
         Self::VARIANTS.contains(&s)
     }
 
-    pub fn run(self, args: Vec<String>, set_pending: SignalSetter<bool>) -> impl IntoView {
+    /// Whether the command has to run in the foreground,
+    /// either because it takes over the screen or because it brings a job there
+    pub fn is_foreground_only(self) -> bool {
+        matches!(
+            self,
+            Self::Adventure | Self::Edit | Self::Fg | Self::Matrix | Self::Sl | Self::Snake
+        )
+    }
+
+    /// Whether the command shows more than text, e.g. a table, so `--plain` makes it print
+    /// plain text. Others take the flag as any other argument, e.g. `echo --plain`.
+    pub fn has_plain_output(self) -> bool {
+        matches!(
+            self,
+            Self::Ack | Self::Fetch | Self::Help | Self::Projects | Self::Repo | Self::Stack
+        )
+    }
+
+    pub fn run(self, args: Vec<String>, process: Process) -> CommandOutput {
         match self {
-            Self::Ack => Ack::run(args, set_pending).into_any(),
-            Self::Clear => Clear::run(args, set_pending).into_any(),
-            Self::Echo => Echo::run(args, set_pending).into_any(),
-            Self::Fetch => Fetch::run(args, set_pending).into_any(),
-            Self::Help => Help::run(args, set_pending).into_any(),
-            Self::Projects => Projects::run(args, set_pending).into_any(),
-            Self::Stack => Stack::run(args, set_pending).into_any(),
-            Self::Theme => Theme::run(args, set_pending).into_any(),
+            Self::Ack => Ack::run(args, process),
+            Self::Adventure => Adventure::run(args, process),
+            Self::Cat => Cat::run(args, process),
+            Self::Clear => Clear::run(args, process),
+            Self::Cut => Cut::run(args, process),
+            Self::Echo => Echo::run(args, process),
+            Self::Edit => Edit::run(args, process),
+            Self::Fetch => Fetch::run(args, process),
+            Self::Fg => Fg::run(args, process),
+            Self::Head => Head::run(args, process),
+            Self::Help => Help::run(args, process),
+            Self::Jobs => Jobs::run(args, process),
+            Self::Jq => Jq::run(args, process),
+            Self::Kill => Kill::run(args, process),
+            Self::Matrix => Matrix::run(args, process),
+            Self::Printf => Printf::run(args, process),
+            Self::Projects => Projects::run(args, process),
+            Self::Repo => Repo::run(args, process),
+            Self::Sl => Sl::run(args, process),
+            Self::Snake => Snake::run(args, process),
+            Self::Sort => Sort::run(args, process),
+            Self::Source => Source::run(args, process),
+            Self::Stack => Stack::run(args, process),
+            Self::Tail => Tail::run(args, process),
+            Self::Theme => Theme::run(args, process),
+            Self::Tr => Tr::run(args, process),
+            Self::Uniq => Uniq::run(args, process),
+            Self::Wc => Wc::run(args, process),
         }
     }
 
     /// Returns a help message
-    pub fn help(self) -> impl IntoView {
+    pub fn help(self) -> Vec<Block> {
         match self {
-            Self::Ack => Ack::help().into_any(),
-            Self::Clear => Clear::help().into_any(),
-            Self::Echo => Echo::help().into_any(),
-            Self::Fetch => Fetch::help().into_any(),
-            Self::Help => Help::help().into_any(),
-            Self::Projects => Projects::help().into_any(),
-            Self::Stack => Stack::help().into_any(),
-            Self::Theme => Theme::help().into_any(),
+            Self::Ack => Ack::help(),
+            Self::Adventure => Adventure::help(),
+            Self::Cat => Cat::help(),
+            Self::Clear => Clear::help(),
+            Self::Cut => Cut::help(),
+            Self::Echo => Echo::help(),
+            Self::Edit => Edit::help(),
+            Self::Fetch => Fetch::help(),
+            Self::Fg => Fg::help(),
+            Self::Head => Head::help(),
+            Self::Help => Help::help(),
+            Self::Jobs => Jobs::help(),
+            Self::Jq => Jq::help(),
+            Self::Kill => Kill::help(),
+            Self::Matrix => Matrix::help(),
+            Self::Printf => Printf::help(),
+            Self::Projects => Projects::help(),
+            Self::Repo => Repo::help(),
+            Self::Sl => Sl::help(),
+            Self::Snake => Snake::help(),
+            Self::Sort => Sort::help(),
+            Self::Source => Source::help(),
+            Self::Stack => Stack::help(),
+            Self::Tail => Tail::help(),
+            Self::Theme => Theme::help(),
+            Self::Tr => Tr::help(),
+            Self::Uniq => Uniq::help(),
+            Self::Wc => Wc::help(),
         }
     }
 
-    /// Returns a one-line description
-    pub fn one_line(self) -> impl IntoView {
+    /// Returns the name of the command with a one-line description
+    pub fn one_line(self) -> (Line, Line) {
         let (name, desc) = match self {
             Self::Ack => (Ack::NAME, Ack::DESCRIPTION),
+            Self::Adventure => (Adventure::NAME, Adventure::DESCRIPTION),
+            Self::Cat => (Cat::NAME, Cat::DESCRIPTION),
             Self::Clear => (Clear::NAME, Clear::DESCRIPTION),
+            Self::Cut => (Cut::NAME, Cut::DESCRIPTION),
             Self::Echo => (Echo::NAME, Echo::DESCRIPTION),
+            Self::Edit => (Edit::NAME, Edit::DESCRIPTION),
             Self::Fetch => (Fetch::NAME, Fetch::DESCRIPTION),
+            Self::Fg => (Fg::NAME, Fg::DESCRIPTION),
+            Self::Head => (Head::NAME, Head::DESCRIPTION),
             Self::Help => (Help::NAME, Help::DESCRIPTION),
-            Self::Theme => (Theme::NAME, Theme::DESCRIPTION),
+            Self::Jobs => (Jobs::NAME, Jobs::DESCRIPTION),
+            Self::Jq => (Jq::NAME, Jq::DESCRIPTION),
+            Self::Kill => (Kill::NAME, Kill::DESCRIPTION),
+            Self::Matrix => (Matrix::NAME, Matrix::DESCRIPTION),
+            Self::Printf => (Printf::NAME, Printf::DESCRIPTION),
             Self::Projects => (Projects::NAME, Projects::DESCRIPTION),
+            Self::Repo => (Repo::NAME, Repo::DESCRIPTION),
+            Self::Sl => (Sl::NAME, Sl::DESCRIPTION),
+            Self::Snake => (Snake::NAME, Snake::DESCRIPTION),
+            Self::Sort => (Sort::NAME, Sort::DESCRIPTION),
+            Self::Source => (Source::NAME, Source::DESCRIPTION),
             Self::Stack => (Stack::NAME, Stack::DESCRIPTION),
+            Self::Tail => (Tail::NAME, Tail::DESCRIPTION),
+            Self::Theme => (Theme::NAME, Theme::DESCRIPTION),
+            Self::Tr => (Tr::NAME, Tr::DESCRIPTION),
+            Self::Uniq => (Uniq::NAME, Uniq::DESCRIPTION),
+            Self::Wc => (Wc::NAME, Wc::DESCRIPTION),
         };
 
-        view! {
-            <span class="pl-8 text-green-theme" data-testid="help-oneline">
-                {name}
-            </span>
-            <span class="text-foreground">{desc}</span>
-        }
+        (Span::styled(name, Style::Command).into(), desc.into())
     }
 
     pub fn suggest() -> Vec<String> {
@@ -95,6 +230,7 @@ impl Palette {
         h.extend(Theme::suggest());
         h.extend(Help::suggest());
         h.extend(Projects::suggest());
+        h.extend(Repo::suggest());
         h
     }
 }
@@ -104,19 +240,18 @@ pub trait Command {
     const DESCRIPTION: &'static str;
     const USAGE: &'static str;
 
-    fn run(args: Vec<String>, set_pending: SignalSetter<bool>) -> Option<impl IntoView>;
-
-    fn help() -> impl IntoView {
-        view! {
-            <div class="text-foreground">
-                <p>
-                    <span class="text-green-theme">{Self::NAME}</span>
-                    <span>" - " {Self::DESCRIPTION}</span>
-                </p>
-                <p class="mt-4">"Usage:"</p>
-                <pre>{Self::USAGE}</pre>
-            </div>
-        }
+    fn run(args: Vec<String>, process: Process) -> CommandOutput;
+
+    fn help() -> Vec<Block> {
+        vec![
+            Block::Line(Line(vec![
+                Span::styled(Self::NAME, Style::Command),
+                Span::new(format!(" - {}", Self::DESCRIPTION)),
+            ])),
+            Block::Blank,
+            Block::Line("Usage:".into()),
+            Block::Pre(Self::USAGE.to_owned()),
+        ]
     }
 
     fn suggest() -> Vec<String> {
@@ -124,14 +259,176 @@ pub trait Command {
     }
 }
 
-#[component]
-fn UnexpectedOption(#[prop(into)] opt: String, #[prop(into)] usage: String) -> impl IntoView {
-    let msg = format!("unexpected flag: {}", opt);
-    view! {
-        <p class="text-fail">{msg}</p>
-        <p>
-            <p class="mt-4">"Usage:"</p>
-            <pre>{usage}</pre>
-        </p>
+/// Reports a flag the command doesn't know, with its usage
+fn unexpected_option(opt: &str, usage: &str) -> CommandOutput {
+    usage_error(format!("unexpected flag: {opt}"), usage)
+}
+
+/// Reports a command line the command can't make sense of, with its usage
+fn usage_error(msg: String, usage: &str) -> CommandOutput {
+    CommandOutput::new(vec![
+        Block::Error(msg),
+        Block::Blank,
+        Block::Line("Usage:".into()),
+        Block::Pre(usage.to_owned()),
+    ])
+    .with_status(2)
+}
+
+/// Flags of a command in the order they're given, with the value of those that take one
+type Flags = Vec<(char, Option<String>)>;
+
+/// Splits `args` into flags and operands, like `getopt`.
+/// `spec` lists the letters of the flags, each followed by `:` if it takes a value,
+/// as in `-n 5` or `-n5`. Flags can be grouped, as in `-rn`, and `--` ends them.
+fn getopt(
+    args: Vec<String>,
+    spec: &str,
+    usage: &str,
+) -> Result<(Flags, Vec<String>), CommandOutput> {
+    let mut flags = Vec::new();
+    let mut operands = Vec::new();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        if arg == "--" {
+            operands.extend(args);
+            break;
+        }
+        if arg.starts_with("--") {
+            return Err(unexpected_option(&arg, usage));
+        }
+        // a lone `-` stands for what's piped in
+        let Some(letters) = arg.strip_prefix('-').filter(|l| !l.is_empty()) else {
+            operands.push(arg);
+            continue;
+        };
+
+        for (i, c) in letters.char_indices() {
+            let Some(at) = spec.find(c).filter(|_| c != ':') else {
+                return Err(unexpected_option(&format!("-{c}"), usage));
+            };
+            if !spec[at + 1..].starts_with(':') {
+                flags.push((c, None));
+                continue;
+            }
+
+            let rest = &letters[i + 1..];
+            let value = match rest.is_empty() {
+                true => args.next(),
+                false => Some(rest.to_owned()),
+            };
+            let Some(value) = value else {
+                return Err(usage_error(format!("flag needs a value: -{c}"), usage));
+            };
+            flags.push((c, Some(value)));
+            break;
+        }
+    }
+    Ok((flags, operands))
+}
+
+/// The text a command works on: the files named, one after another,
+/// or what's piped into it if there are none.
+/// Returns the text along with an error for each file that can't be read.
+fn input(name: &str, files: &[String], process: Process) -> (String, Vec<String>) {
+    if files.is_empty() {
+        return (process.stdin().unwrap_or_default(), Vec::new());
+    }
+
+    let (fs, _set_fs) = use_fs().expect("not yet created");
+    fs.with_untracked(|fs| read(fs, name, files, process.stdin()))
+}
+
+/// Concatenates the files at `paths`, where `-` stands for `stdin`
+fn read(fs: &Fs, name: &str, paths: &[String], stdin: Option<String>) -> (String, Vec<String>) {
+    let mut text = String::new();
+    let mut errors = Vec::new();
+    for arg in paths {
+        if arg == "-" {
+            text.push_str(stdin.as_deref().unwrap_or_default());
+            continue;
+        }
+        match fs::normalize(arg).map(|path| fs.read(&path)) {
+            Ok(Some(content)) => text.push_str(content),
+            Ok(None) => errors.push(format!("{name}: {arg}: No such file or directory")),
+            Err(e) => errors.push(format!("{name}: {e}")),
+        }
+    }
+    (text, errors)
+}
+
+/// Output of a command that prints `text`, followed by its errors, failing if there are any
+fn printed(text: &str, errors: Vec<String>) -> CommandOutput {
+    let mut output = CommandOutput::printed(text);
+    let status = u8::from(!errors.is_empty());
+    output.blocks.extend(errors.into_iter().map(Block::Error));
+    output.with_status(status)
+}
+
+/// Joins lines back into text, each ending in a newline
+fn unlines<S: AsRef<str>>(lines: impl IntoIterator<Item = S>) -> String {
+    lines
+        .into_iter()
+        .map(|l| format!("{}\n", l.as_ref()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(ToString::to_string).collect()
+    }
+
+    #[rstest]
+    #[case::flags(&["-r", "-n", "a"], &[('r', None), ('n', None)], &["a"])]
+    #[case::grouped(&["-rn", "a"], &[('r', None), ('n', None)], &["a"])]
+    #[case::value(&["-k", "2", "a"], &[('k', Some("2"))], &["a"])]
+    #[case::attached_value(&["-rk2"], &[('r', None), ('k', Some("2"))], &[])]
+    #[case::after_operands(&["a", "-r"], &[('r', None)], &["a"])]
+    #[case::dash(&["-"], &[], &["-"])]
+    #[case::end(&["--", "-r"], &[], &["-r"])]
+    fn test_getopt(
+        #[case] given: &[&str],
+        #[case] flags: &[(char, Option<&str>)],
+        #[case] operands: &[&str],
+    ) {
+        let flags = flags
+            .iter()
+            .map(|&(c, v)| (c, v.map(ToOwned::to_owned)))
+            .collect::<Vec<_>>();
+        assert_eq!(getopt(args(given), "rnk:", ""), Ok((flags, args(operands))));
+    }
+
+    #[rstest]
+    #[case::unknown(&["-x"], "unexpected flag: -x")]
+    #[case::long(&["--reverse"], "unexpected flag: --reverse")]
+    #[case::missing_value(&["-k"], "flag needs a value: -k")]
+    fn test_getopt_error(#[case] given: &[&str], #[case] expected: &str) {
+        let output = getopt(args(given), "rk:", "").expect_err("should fail");
+        assert_eq!(output.blocks[0], Block::Error(expected.to_owned()));
+        assert_eq!(output.status, 2);
+    }
+
+    #[test]
+    fn test_read() {
+        let mut fs = Fs::default();
+        fs.write("a.txt", "a\n");
+
+        assert_eq!(
+            read(
+                &fs,
+                "cat",
+                &args(&["a.txt", "-", "b.txt"]),
+                Some("piped\n".to_owned())
+            ),
+            (
+                "a\npiped\n".to_owned(),
+                vec!["cat: b.txt: No such file or directory".to_owned()]
+            )
+        );
     }
 }
@@ -0,0 +1,426 @@
+use std::iter::Peekable;
+use std::ops::ControlFlow;
+use std::str::Chars;
+
+use super::echo::{escape, number, unescape};
+use super::{Command, printed};
+use crate::shell::output::CommandOutput;
+use crate::shell::process::Process;
+use crate::shell::width::width;
+
+/// Most digits read for a width or a precision
+const MAX_DIGITS: usize = 4;
+
+/// Widest a width or a precision from an argument can be, as with `%*d`
+const MAX_WIDTH: u64 = 9999;
+
+pub struct Printf;
+
+impl Command for Printf {
+    const NAME: &'static str = "printf";
+    const DESCRIPTION: &'static str = "format and print data";
+    const USAGE: &'static str = "\t\
+    printf format [argument]...  print the arguments as the format says,
+                                 reusing it until they run out
+
+    %s   a string         %d, %i  an integer       %f  a decimal, 6 digits after the point
+    %b   a string with    %u      an unsigned one  %c  the first character
+         escapes, as in   %x, %X  in hexadecimal   %%  a percent sign
+         echo -e          %o      in octal
+
+    Flags, a width and a precision go between, as in %-10s, %05d or %.2f";
+
+    fn run(args: Vec<String>, _: Process) -> CommandOutput {
+        let args = match args.split_first() {
+            Some((first, rest)) if first == "--" => rest,
+            _ => args.as_slice(),
+        };
+        let Some((format, args)) = args.split_first() else {
+            return CommandOutput::error("printf: usage: printf format [argument]...", 2);
+        };
+
+        let (text, errors) = printf(format, args);
+        printed(&text, errors)
+    }
+}
+
+/// Formats `args` as bash's `printf` does, returning the text with the errors on the way.
+/// The format is applied again as long as some arguments are left.
+fn printf(format: &str, args: &[String]) -> (String, Vec<String>) {
+    let mut printer = Printer {
+        args,
+        next: 0,
+        out: String::new(),
+        errors: Vec::new(),
+    };
+
+    loop {
+        let start = printer.next;
+        if printer.print(format).is_break() {
+            break;
+        }
+        // the format is only applied again if it took some arguments
+        if printer.next == start || printer.next >= args.len() {
+            break;
+        }
+    }
+    (printer.out, printer.errors)
+}
+
+struct Printer<'a> {
+    args: &'a [String],
+    /// Index of the next argument to format
+    next: usize,
+    out: String,
+    errors: Vec<String>,
+}
+
+/// A conversion like `%-10s`, without its conversion character
+#[derive(Debug, Default)]
+struct Spec {
+    /// Aligns to the left, with `-`
+    left: bool,
+    /// Shows a plus sign before positive numbers, with `+`
+    plus: bool,
+    /// Shows a space before positive numbers, with ` `
+    space: bool,
+    /// Pads numbers with zeros, with `0`
+    zero: bool,
+    /// Adds `0x` to hexadecimal and `0` to octal numbers, with `#`
+    alternate: bool,
+    width: usize,
+    precision: Option<usize>,
+}
+
+impl<'a> Printer<'a> {
+    /// Applies the format once, breaking when it's stopped by `\c` in `%b` or an invalid conversion
+    fn print(&mut self, format: &str) -> ControlFlow<()> {
+        let mut chars = format.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => self.escape(&mut chars),
+                '%' => self.conversion(&mut chars)?,
+                c => self.out.push(c),
+            }
+        }
+        ControlFlow::Continue(())
+    }
+
+    /// Escapes in the format are those of `echo -e`,
+    /// except octal ones don't need a leading zero and `\c` is kept as is
+    fn escape(&mut self, chars: &mut Peekable<Chars>) {
+        match chars.peek() {
+            Some('0'..='7') => {
+                let n = number(chars, 8, 3).unwrap_or_default();
+                self.out.push(char::from(n as u8));
+            },
+            Some('c') => self.out.push('\\'),
+            _ => {
+                let _ = escape(chars, &mut self.out);
+            },
+        }
+    }
+
+    fn conversion(&mut self, chars: &mut Peekable<Chars>) -> ControlFlow<()> {
+        if chars.next_if_eq(&'%').is_some() {
+            self.out.push('%');
+            return ControlFlow::Continue(());
+        }
+
+        let mut spec = Spec::default();
+        while let Some(flag) = chars.next_if(|c| "-+ 0#".contains(*c)) {
+            match flag {
+                '-' => spec.left = true,
+                '+' => spec.plus = true,
+                ' ' => spec.space = true,
+                '0' => spec.zero = true,
+                _ => spec.alternate = true,
+            }
+        }
+        if chars.next_if_eq(&'*').is_some() {
+            let width = self.integer();
+            spec.left |= width < 0;
+            spec.width = width.unsigned_abs().min(MAX_WIDTH) as usize;
+        } else {
+            spec.width = number(chars, 10, MAX_DIGITS).unwrap_or_default() as usize;
+        }
+        if chars.next_if_eq(&'.').is_some() {
+            spec.precision = Some(if chars.next_if_eq(&'*').is_some() {
+                self.integer().clamp(0, MAX_WIDTH as i64) as usize
+            } else {
+                number(chars, 10, MAX_DIGITS).unwrap_or_default() as usize
+            });
+        }
+
+        let Some(conversion) = chars.next() else {
+            self.errors
+                .push("printf: `%': missing format character".to_owned());
+            return ControlFlow::Break(());
+        };
+        match conversion {
+            's' => {
+                let arg = self.arg().unwrap_or_default();
+                let arg = match spec.precision {
+                    Some(p) => arg.chars().take(p).collect(),
+                    None => arg.to_owned(),
+                };
+                self.pad(&arg, &spec);
+            },
+            'b' => {
+                let (arg, stop) = match unescape(self.arg().unwrap_or_default()) {
+                    ControlFlow::Continue(arg) => (arg, false),
+                    ControlFlow::Break(arg) => (arg, true),
+                };
+                self.pad(&arg, &spec);
+                if stop {
+                    return ControlFlow::Break(());
+                }
+            },
+            'c' => {
+                let arg = self.arg().unwrap_or_default();
+                let c = arg.chars().next().map(String::from).unwrap_or_default();
+                self.pad(&c, &spec);
+            },
+            'd' | 'i' => {
+                let n = self.integer();
+                let digits = digits(n.unsigned_abs().to_string(), spec.precision);
+                let sign = sign(n < 0, &spec);
+                self.number(sign, &digits, &spec);
+            },
+            'u' | 'o' | 'x' | 'X' => {
+                // negative numbers wrap around, as in C
+                let n = self.integer() as u64;
+                let (text, prefix) = match conversion {
+                    'u' => (n.to_string(), ""),
+                    'o' => (format!("{n:o}"), "0"),
+                    'x' => (format!("{n:x}"), "0x"),
+                    _ => (format!("{n:X}"), "0X"),
+                };
+                let prefix = if spec.alternate && n != 0 { prefix } else { "" };
+                self.number(prefix, &digits(text, spec.precision), &spec);
+            },
+            'f' | 'F' => {
+                let n = self.float();
+                let digits = format!("{:.*}", spec.precision.unwrap_or(6), n.abs());
+                let sign = sign(n.is_sign_negative(), &spec);
+                self.number(sign, &digits, &spec);
+            },
+            c => {
+                self.errors
+                    .push(format!("printf: `{c}': invalid format character"));
+                return ControlFlow::Break(());
+            },
+        }
+        ControlFlow::Continue(())
+    }
+
+    fn arg(&mut self) -> Option<&'a str> {
+        let arg = self.args.get(self.next)?;
+        self.next += 1;
+        Some(arg)
+    }
+
+    /// The next argument as an integer, or 0 if it's missing.
+    /// Reports one that isn't, using the number it starts with.
+    fn integer(&mut self) -> i64 {
+        let Some(arg) = self.arg() else {
+            return 0;
+        };
+        let (n, valid) = integer(arg);
+        if !valid {
+            self.errors.push(format!("printf: {arg}: invalid number"));
+        }
+        n
+    }
+
+    /// The next argument as a decimal, or 0 if it's missing.
+    /// Reports one that isn't, using the number it starts with.
+    fn float(&mut self) -> f64 {
+        let Some(arg) = self.arg() else {
+            return 0.0;
+        };
+        if let Some(n) = quoted(arg) {
+            return n as f64;
+        }
+
+        let trimmed = arg.trim_start();
+        if trimmed.is_empty() {
+            return 0.0;
+        }
+        if let Ok(n) = trimmed.parse() {
+            return n;
+        }
+        self.errors.push(format!("printf: {arg}: invalid number"));
+        (0..trimmed.len())
+            .rev()
+            .filter(|&i| trimmed.is_char_boundary(i))
+            .find_map(|i| trimmed[..i].parse().ok())
+            .unwrap_or_default()
+    }
+
+    /// Pads a number to the width, with zeros after its sign if asked to
+    fn number(&mut self, sign: &str, digits: &str, spec: &Spec) {
+        // zeros would change an integer whose digits are set by the precision
+        let integer = !digits.contains('.');
+        if spec.zero && !spec.left && !(integer && spec.precision.is_some()) {
+            let fill = spec.width.saturating_sub(sign.len() + digits.len());
+            self.out.push_str(sign);
+            self.out.push_str(&"0".repeat(fill));
+            self.out.push_str(digits);
+        } else {
+            self.pad(&format!("{sign}{digits}"), spec);
+        }
+    }
+
+    fn pad(&mut self, s: &str, spec: &Spec) {
+        let fill = " ".repeat(spec.width.saturating_sub(width(s)));
+        if spec.left {
+            self.out.push_str(s);
+            self.out.push_str(&fill);
+        } else {
+            self.out.push_str(&fill);
+            self.out.push_str(s);
+        }
+    }
+}
+
+/// Pads `digits` with zeros to the number of digits set by the precision
+fn digits(digits: String, precision: Option<usize>) -> String {
+    match precision {
+        Some(p) if digits.len() < p => format!("{}{digits}", "0".repeat(p - digits.len())),
+        _ => digits,
+    }
+}
+
+fn sign(negative: bool, spec: &Spec) -> &'static str {
+    if negative {
+        "-"
+    } else if spec.plus {
+        "+"
+    } else if spec.space {
+        " "
+    } else {
+        ""
+    }
+}
+
+/// Parses an integer as bash does, in decimal, hexadecimal with `0x` or octal with a leading `0`,
+/// or the code of the character after a quote, as in `'a`.
+/// Returns the number it starts with and whether that was all of it.
+fn integer(s: &str) -> (i64, bool) {
+    if let Some(n) = quoted(s) {
+        return (n, true);
+    }
+
+    let s = s.trim_start();
+    let (negative, unsigned) = match s.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, s.strip_prefix('+').unwrap_or(s)),
+    };
+    let (radix, digits) = if let Some(hex) = unsigned
+        .strip_prefix("0x")
+        .or_else(|| unsigned.strip_prefix("0X"))
+    {
+        (16, hex)
+    } else if unsigned.len() > 1
+        && let Some(octal) = unsigned.strip_prefix('0')
+    {
+        (8, octal)
+    } else {
+        (10, unsigned)
+    };
+
+    let end = digits
+        .find(|c: char| !c.is_digit(radix))
+        .unwrap_or(digits.len());
+    let (n, fits) = match i64::from_str_radix(&digits[..end], radix) {
+        Ok(n) => (n, true),
+        Err(_) if end == 0 => (0, true),
+        // too large, so it's as large as can be
+        Err(_) => (i64::MAX, false),
+    };
+    let n = if negative { -n } else { n };
+    // an empty argument is 0, but a sign on its own isn't a number
+    (n, fits && end == digits.len() && (end > 0 || s.is_empty()))
+}
+
+/// The code of the character after a leading quote, as in `'a` or `"a`
+fn quoted(s: &str) -> Option<i64> {
+    let c = s.strip_prefix(['\'', '"'])?.chars().next()?;
+    Some(i64::from(u32::from(c)))
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+    use crate::shell::output::Block;
+
+    fn printf(format: &str, args: &[&str]) -> (String, Vec<String>) {
+        let args = args.iter().map(ToString::to_string).collect::<Vec<_>>();
+        super::printf(format, &args)
+    }
+
+    // the expected output is that of bash 5.2
+    #[rstest]
+    #[case::string("%s!", &["hi"], "hi!")]
+    #[case::no_newline("a", &[], "a")]
+    #[case::repeats("%s-%s\\n", &["a", "b", "c"], "a-b\nc-\n")]
+    #[case::no_conversions("x\\n", &["a", "b"], "x\n")]
+    #[case::missing("%s|%d|%s\\n", &[], "|0|\n")]
+    #[case::left("%-6s|", &["ab"], "ab    |")]
+    #[case::right("%5s|", &["ab"], "   ab|")]
+    #[case::truncate("%.2s", &["abc"], "ab")]
+    #[case::char("%c", &["hello"], "h")]
+    #[case::percent("100%%", &[], "100%")]
+    #[case::decimal("%d|", &["0x1f", "010", "-5", "'a", ""], "31|8|-5|97|0|")]
+    #[case::zeros("%06d", &["-42"], "-00042")]
+    #[case::plus("%+d|% d", &["5", "5"], "+5| 5")]
+    #[case::precision("%.3d", &["7"], "007")]
+    #[case::star("%*d|%-*d|", &["4", "1", "3", "2"], "   1|2  |")]
+    #[case::hex("%x %X %#x", &["255", "255", "255"], "ff FF 0xff")]
+    #[case::negative_hex("%x", &["-1"], "ffffffffffffffff")]
+    #[case::octal("%o %#o", &["8", "8"], "10 010")]
+    #[case::float("%f", &["1.5"], "1.500000")]
+    #[case::float_precision("%5.2f|", &["3.14159"], " 3.14|")]
+    #[case::float_zeros("%08.3f", &["-3.14159"], "-003.142")]
+    #[case::escapes("a\\tb\\101\\0101", &[], "a\tbA\u{8}1")]
+    #[case::literal_stop("a\\cb", &[], "a\\cb")]
+    #[case::b("%b|", &["a\\tb"], "a\tb|")]
+    #[case::b_stops("%b|", &["x\\cy", "z"], "x")]
+    fn test_printf(#[case] format: &str, #[case] args: &[&str], #[case] expected: &str) {
+        assert_eq!(printf(format, args), (expected.to_owned(), vec![]));
+    }
+
+    #[rstest]
+    #[case::letters("%d|", &["abc"], "0|", "printf: abc: invalid number")]
+    #[case::decimal("%d|", &["3.5"], "3|", "printf: 3.5: invalid number")]
+    #[case::float("%f|", &["1.5x"], "1.500000|", "printf: 1.5x: invalid number")]
+    #[case::missing_character("a%", &[], "a", "printf: `%': missing format character")]
+    #[case::invalid_character("a%qb", &["x"], "a", "printf: `q': invalid format character")]
+    fn test_printf_errors(
+        #[case] format: &str,
+        #[case] args: &[&str],
+        #[case] expected: &str,
+        #[case] error: &str,
+    ) {
+        assert_eq!(
+            printf(format, args),
+            (expected.to_owned(), vec![error.to_owned()])
+        );
+    }
+
+    #[test]
+    fn test_run() {
+        let args = ["--", "%s\\n", "a", "b"].map(ToString::to_string).to_vec();
+        let output = Printf::run(args, Process::new());
+        assert_eq!(
+            output.blocks,
+            vec![Block::Line("a".into()), Block::Line("b".into())]
+        );
+
+        let output = Printf::run(vec![], Process::new());
+        assert_eq!(output.status, 2);
+    }
+}
@@ -0,0 +1,880 @@
+use std::cmp::Ordering;
+use std::str::FromStr;
+
+use futures::future::try_join_all;
+use icondata as i;
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use strum::{EnumIter, EnumString, IntoEnumIterator, IntoStaticStr, VariantNames};
+
+use super::{Command, unexpected_option, usage_error};
+use crate::config::{CONFIG, InProgress, SNAPSHOT, SnapshotMode};
+use crate::shell::net::{FetchError, Stale};
+use crate::shell::output::{Block, CommandOutput, Glyph, Line, Row, Span, Style, Table};
+use crate::shell::process::{CancelToken, Process};
+
+mod snapshot;
+mod source;
+
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, EnumString, EnumIter, IntoStaticStr, VariantNames,
+)]
+#[strum(serialize_all = "lowercase")]
+enum Format {
+    #[default]
+    Table,
+    Json,
+    Yaml,
+    Csv,
+    Tsv,
+    #[strum(to_string = "md", serialize = "markdown")]
+    Markdown,
+}
+
+/// What `--sort` orders projects by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, EnumIter, IntoStaticStr, VariantNames)]
+#[strum(serialize_all = "lowercase")]
+enum Sort {
+    Stars,
+    Forks,
+    Name,
+    Updated,
+}
+
+impl Sort {
+    /// Orders by name alphabetically, and by the rest with the most, or the latest, first.
+    /// Projects in progress have no stars, forks or updates, so they go last.
+    fn compare(self, a: &Repository, b: &Repository) -> Ordering {
+        match self {
+            Self::Stars => b.stars().cmp(&a.stars()),
+            Self::Forks => b.forks().cmp(&a.forks()),
+            Self::Name => a.name().to_lowercase().cmp(&b.name().to_lowercase()),
+            Self::Updated => b.updated_at().cmp(&a.updated_at()),
+        }
+    }
+}
+
+/// What `--status` keeps
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, VariantNames)]
+#[strum(serialize_all = "kebab-case")]
+enum Status {
+    Released,
+    InProgress,
+}
+
+/// Fields of a repository in the columns of CSV, TSV and Markdown,
+/// named as they are in JSON and YAML
+const COLUMNS: [&str; 7] = [
+    "name",
+    "status",
+    "description",
+    "language",
+    "stargazers_count",
+    "forks",
+    "html_url",
+];
+
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Options {
+    format: Format,
+    sort: Option<Sort>,
+    /// Language to keep, matched regardless of case
+    lang: Option<String>,
+    status: Option<Status>,
+    limit: Option<usize>,
+    reverse: bool,
+    /// Fetches everything again instead of using the cache
+    refresh: bool,
+    /// Shows the request that failed along with the error
+    verbose: bool,
+}
+
+impl Options {
+    fn parse(args: &[String]) -> Result<Self, CommandOutput> {
+        let mut options = Self::default();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            // values can be attached, as in `--sort=stars` or `-ojson`
+            let (flag, value) = match arg.split_once('=') {
+                Some((flag, value)) if flag.starts_with("--") => (flag, Some(value)),
+                _ => match arg.strip_prefix("-o") {
+                    Some(value) if !value.is_empty() && !value.starts_with('-') => {
+                        ("-o", Some(value))
+                    },
+                    _ => (arg.as_str(), None),
+                },
+            };
+
+            match flag {
+                "-j" | "--json" => options.format = Format::Json,
+                "--reverse" => options.reverse = true,
+                "--refresh" => options.refresh = true,
+                "-v" | "--verbose" => options.verbose = true,
+                "-o" | "--output" | "--sort" | "--lang" | "--status" | "--limit" => {
+                    let Some(value) = value.or_else(|| args.next().map(String::as_str)) else {
+                        return Err(usage_error(
+                            format!("flag needs a value: {flag}"),
+                            Projects::USAGE,
+                        ));
+                    };
+                    options.set(flag, value)?;
+                },
+                flag if flag.starts_with('-') => {
+                    return Err(unexpected_option(arg, Projects::USAGE));
+                },
+                _ => {
+                    return Err(usage_error(
+                        format!("unexpected argument: {arg}"),
+                        Projects::USAGE,
+                    ));
+                },
+            }
+        }
+        Ok(options)
+    }
+
+    /// Sets the option of a flag that takes a value
+    fn set(&mut self, flag: &str, value: &str) -> Result<(), CommandOutput> {
+        match flag {
+            "--sort" => self.sort = Some(choice("sort key", value)?),
+            "--lang" => self.lang = Some(value.to_owned()),
+            "--status" => self.status = Some(choice("status", value)?),
+            "--limit" => match value.parse() {
+                Ok(n) => self.limit = Some(n),
+                Err(_) => {
+                    return Err(usage_error(
+                        format!("invalid limit: '{value}'"),
+                        Projects::USAGE,
+                    ));
+                },
+            },
+            _ => self.format = choice("format", value)?,
+        }
+        Ok(())
+    }
+
+    /// Filters, sorts and limits the projects, in that order
+    fn apply(&self, mut repos: Vec<Repository>) -> Vec<Repository> {
+        repos.retain(|repo| {
+            let lang = self.lang.as_ref().is_none_or(|lang| {
+                repo.language()
+                    .is_some_and(|l| l.eq_ignore_ascii_case(lang))
+            });
+            let status = self.status.is_none_or(|status| repo.status() == status);
+            lang && status
+        });
+        if let Some(sort) = self.sort {
+            repos.sort_by(|a, b| sort.compare(a, b));
+        }
+        if self.reverse {
+            repos.reverse();
+        }
+        if let Some(limit) = self.limit {
+            repos.truncate(limit);
+        }
+        repos
+    }
+}
+
+/// Parses one of the values of `T`, like a format or a sort key
+fn choice<T: FromStr + VariantNames>(what: &str, value: &str) -> Result<T, CommandOutput> {
+    T::from_str(value).map_err(|_| {
+        usage_error(
+            format!(
+                "unknown {what}: {value}, expected one of {}",
+                T::VARIANTS.join(", ")
+            ),
+            Projects::USAGE,
+        )
+    })
+}
+
+pub struct Projects;
+
+impl Command for Projects {
+    const NAME: &'static str = "projects";
+    const DESCRIPTION: &'static str = "explore my projects";
+    const USAGE: &'static str = "\t\
+    projects                    use table format
+    projects -o, --output FMT   use FMT: table, json, yaml, csv, tsv or md for Markdown
+    projects -j, --json         use JSON format, like -o json
+    projects --plain            use a plain-text table, wrapped to $COLUMNS
+
+    projects --sort KEY         sort by stars, forks, name or updated
+    projects --lang LANG        keep those written in LANG, e.g. rust
+    projects --status STATUS    keep those released or in-progress
+    projects --limit N          keep the first N
+    projects --reverse          reverse the order
+    projects --refresh          fetch them again, instead of using those fetched lately
+    projects -v, --verbose      show the request that failed, if one does";
+
+    fn run(args: Vec<String>, process: Process) -> CommandOutput {
+        let options = match Options::parse(&args) {
+            Ok(options) => options,
+            Err(output) => return output,
+        };
+
+        let cancel = process.cancel;
+        CommandOutput::deferred("One moment...", async move {
+            let (released, notice) = match load(cancel, options.refresh).await {
+                Ok(loaded) => loaded,
+                Err(e) => return failed(&e, options.verbose),
+            };
+            let mut output = output(&options.apply(repositories(released)), options.format);
+            if let Some(notice) = notice {
+                output.blocks.push(match options.format {
+                    Format::Table => Block::Line(Span::styled(notice, Style::Muted).into()),
+                    // kept out of what's piped, so it can still be parsed
+                    _ => Block::Error(notice),
+                });
+            }
+            output
+        })
+    }
+
+    fn suggest() -> Vec<String> {
+        let mut opts = vec![
+            "projects -j".to_owned(),
+            "projects --json".to_owned(),
+            "projects --plain".to_owned(),
+            "projects --refresh".to_owned(),
+        ];
+        opts.extend(Format::iter().map(|f| format!("projects -o {}", <&str>::from(f))));
+        opts.extend(Sort::iter().map(|s| format!("projects --sort {}", <&str>::from(s))));
+        opts
+    }
+}
+
+fn output(repos: &[Repository], format: Format) -> CommandOutput {
+    let (id, text) = match format {
+        Format::Table => {
+            return CommandOutput::new(vec![Block::Table(table(repos))]);
+        },
+        Format::Json => ("projects-json", serde_json::to_string_pretty(repos).ok()),
+        Format::Yaml => ("projects-yaml", serde_yaml::to_string(repos).ok()),
+        Format::Csv => (
+            "projects-csv",
+            records(repos).map(|r| separated(&r, ',', csv_field)),
+        ),
+        Format::Tsv => (
+            "projects-tsv",
+            records(repos).map(|r| separated(&r, '\t', tsv_field)),
+        ),
+        Format::Markdown => ("projects-markdown", records(repos).map(|r| markdown(&r))),
+    };
+    let Some(text) = text else {
+        return CommandOutput::error("error: failed to format project data", 1);
+    };
+    let text = text.strip_suffix('\n').unwrap_or(&text).to_owned();
+    CommandOutput::new(vec![Block::group(id, vec![Block::Pre(text)])])
+}
+
+/// The cells of each repository under `COLUMNS`, taken from its JSON so they match it.
+/// Fields that a repository doesn't have, like the stars of one in progress, are empty.
+fn records(repos: &[Repository]) -> Option<Vec<Vec<String>>> {
+    let Value::Array(repos) = serde_json::to_value(repos).ok()? else {
+        return None;
+    };
+    let cell = |value: Option<&Value>| match value {
+        None | Some(Value::Null) => String::new(),
+        Some(Value::String(s)) => s.clone(),
+        Some(value) => value.to_string(),
+    };
+    let records = repos
+        .iter()
+        .map(|repo| COLUMNS.iter().map(|c| cell(repo.get(c))).collect())
+        .collect();
+    Some(records)
+}
+
+/// Rows of fields under a header, each field escaped by `field`, as in CSV and TSV
+fn separated(records: &[Vec<String>], sep: char, field: fn(&str) -> String) -> String {
+    let header = COLUMNS.iter().map(|c| field(c));
+    let rows = records.iter().map(|r| r.iter().map(|c| field(c)));
+    std::iter::once(header.collect::<Vec<_>>())
+        .chain(rows.map(Iterator::collect))
+        .map(|fields| fields.join(&sep.to_string()) + "\n")
+        .collect()
+}
+
+/// Quotes a CSV field if it has to be, doubling its quotes, as RFC 4180 says
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_owned()
+    }
+}
+
+/// TSV fields can't be quoted, so tabs and line breaks become spaces
+fn tsv_field(s: &str) -> String {
+    s.replace(['\t', '\n', '\r'], " ")
+}
+
+/// A Markdown table, ready to paste in a README
+fn markdown(records: &[Vec<String>]) -> String {
+    let row = |cells: Vec<String>| format!("| {} |\n", cells.join(" | "));
+    let cell = |s: &String| s.replace('|', "\\|").replace(['\n', '\r'], " ");
+
+    let mut text = row(COLUMNS.map(str::to_owned).to_vec());
+    text.push_str(&row(COLUMNS.map(|_| "---".to_owned()).to_vec()));
+    for record in records {
+        text.push_str(&row(record.iter().map(cell).collect()));
+    }
+    text
+}
+
+fn failed(error: &FetchError, verbose: bool) -> CommandOutput {
+    let mut blocks = vec![Block::Error(format!(
+        "error: failed to load project data: {error}"
+    ))];
+    if verbose {
+        blocks.push(Block::Error(error.details()));
+    }
+    blocks.push(Block::Error(error.hint().to_owned()));
+    CommandOutput::new(blocks).with_status(1)
+}
+
+fn table(repos: &[Repository]) -> Table {
+    let headers = [
+        "NAME",
+        "DESCRIPTION",
+        "LANGUAGE",
+        "STARS",
+        "FORKS",
+        "STATUS",
+    ];
+    Table {
+        id: Some("projects"),
+        headers: headers.map(str::to_owned).to_vec(),
+        rows: repos.iter().map(row).collect(),
+    }
+}
+
+fn row(repo: &Repository) -> Row {
+    match repo.clone() {
+        Repository::Public {
+            released:
+                Released {
+                    name,
+                    description,
+                    html_url,
+                    stargazers_count,
+                    language,
+                    forks,
+                    ..
+                },
+        } => Row {
+            cells: vec![
+                name.into(),
+                description.unwrap_or_default().into(),
+                self::language(language),
+                Span::new(stargazers_count.to_string())
+                    .with_glyph(Glyph::Icon(i::FaStarRegular))
+                    .into(),
+                Span::new(forks.to_string())
+                    .with_glyph(Glyph::Icon(i::FaCodeForkSolid))
+                    .into(),
+                "Released".into(),
+            ],
+            link: Some(html_url),
+        },
+        Repository::Private {
+            in_progress:
+                InProgress {
+                    name,
+                    description,
+                    language,
+                },
+        } => Row {
+            cells: vec![
+                name.into(),
+                description.unwrap_or_default().into(),
+                self::language(language),
+                // no stargazers_count
+                Line::default(),
+                // no forks
+                Line::default(),
+                Span::styled("Coming Soon", Style::Muted).into(),
+            ],
+            link: None,
+        },
+    }
+}
+
+/// The language with a dot in its color on GitHub, or the one in the config
+fn language(lang: Option<String>) -> Line {
+    let Some(lang) = lang else {
+        return Line::default();
+    };
+    let color = CONFIG
+        .language_color(&lang)
+        .unwrap_or("var(--color-white)")
+        .to_owned();
+
+    Span::new(lang).with_glyph(Glyph::Dot(color)).into()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(from = "Released", tag = "status", rename_all = "lowercase")]
+enum Repository {
+    Public {
+        #[serde(flatten)]
+        released: Released,
+    },
+    Private {
+        #[serde(flatten)]
+        in_progress: InProgress,
+    },
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+struct Released {
+    name: String,
+    description: Option<String>,
+    html_url: String,
+    stargazers_count: usize,
+    language: Option<String>,
+    forks: usize,
+    /// When the repository last changed, in ISO 8601, which sorts by time.
+    /// It's only for sorting, so it's kept out of the output.
+    #[serde(skip_serializing)]
+    updated_at: Option<String>,
+}
+
+impl Repository {
+    fn name(&self) -> &str {
+        match self {
+            Self::Public { released } => &released.name,
+            Self::Private { in_progress } => &in_progress.name,
+        }
+    }
+
+    fn language(&self) -> Option<&str> {
+        match self {
+            Self::Public { released } => released.language.as_deref(),
+            Self::Private { in_progress } => in_progress.language.as_deref(),
+        }
+    }
+
+    fn status(&self) -> Status {
+        match self {
+            Self::Public { .. } => Status::Released,
+            Self::Private { .. } => Status::InProgress,
+        }
+    }
+
+    fn stars(&self) -> Option<usize> {
+        match self {
+            Self::Public { released } => Some(released.stargazers_count),
+            Self::Private { .. } => None,
+        }
+    }
+
+    fn forks(&self) -> Option<usize> {
+        match self {
+            Self::Public { released } => Some(released.forks),
+            Self::Private { .. } => None,
+        }
+    }
+
+    fn updated_at(&self) -> Option<&str> {
+        match self {
+            Self::Public { released } => released.updated_at.as_deref(),
+            Self::Private { .. } => None,
+        }
+    }
+}
+
+impl From<Released> for Repository {
+    fn from(released: Released) -> Self {
+        Repository::Public { released }
+    }
+}
+
+/// Fetches the released projects, or takes them from the snapshot if there is one
+/// and it's to be shown instead, or fetching fails, along with a notice saying so.
+/// There's a notice too if they're cached ones that couldn't be fetched again.
+async fn load(
+    cancel: CancelToken,
+    refresh: bool,
+) -> Result<(Vec<Released>, Option<String>), FetchError> {
+    let now = web_sys::js_sys::Date::now();
+    let Some((config, json)) = CONFIG.snapshot.as_ref().zip(SNAPSHOT) else {
+        let (released, stale) = fetch_released(cancel, refresh).await?;
+        return Ok((released, stale.map(|s| stale_notice(&s, now))));
+    };
+    let failure = match config.mode {
+        SnapshotMode::Only => None,
+        SnapshotMode::Fallback => match fetch_released(cancel, refresh).await {
+            Ok((released, stale)) => return Ok((released, stale.map(|s| stale_notice(&s, now)))),
+            Err(e) => Some(e),
+        },
+    };
+
+    let released = snapshot::parse(&config.path, json)?;
+    let taken_at = snapshot::taken_at(config);
+    Ok((
+        released,
+        Some(snapshot::notice(taken_at, now, failure.as_ref())),
+    ))
+}
+
+/// Fetches the repositories of every source at once, merged in their order
+async fn fetch_released(cancel: CancelToken, refresh: bool) -> Result<source::Fetched, FetchError> {
+    let config = &CONFIG.github;
+    let sources = CONFIG.sources();
+    let fetches = sources
+        .iter()
+        .map(|s| source::fetch(s, &config.repos, config.cache_ttl, cancel, refresh));
+    let (released, stale): (Vec<_>, Vec<_>) = try_join_all(fetches).await?.into_iter().unzip();
+    Ok((source::merge(released), stale.into_iter().flatten().next()))
+}
+
+/// Tells that the projects are cached ones, how old they are, and why they couldn't be
+/// fetched again. `now` is in milliseconds since the epoch.
+fn stale_notice(stale: &Stale, now: f64) -> String {
+    format!(
+        "couldn't refresh project data ({}), showing what was fetched {}",
+        stale.error,
+        snapshot::ago(now - stale.fetched_at)
+    )
+}
+
+/// The released projects listed in the config, or all of them, and those in progress
+fn repositories(mut released: Vec<Released>) -> Vec<Repository> {
+    let config = &CONFIG.github;
+
+    if !config.repos.is_empty() {
+        // only include repositories listed in the config
+        released.retain(|r| config.repos.contains(&r.name));
+    }
+    let mut repos = released
+        .into_iter()
+        .map(Repository::from)
+        .collect::<Vec<_>>();
+
+    // add in progress projects manually
+    for item in config.in_progress.clone() {
+        repos.push(Repository::Private { in_progress: item });
+    }
+
+    repos
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    fn repos() -> Vec<Repository> {
+        vec![
+            Repository::Public {
+                released: Released {
+                    name: "seaq".to_owned(),
+                    description: Some("About seaq".to_owned()),
+                    html_url: "https://github.com/nt54hamnghi/seaq".to_owned(),
+                    stargazers_count: 5,
+                    language: Some("Rust".to_owned()),
+                    forks: 1,
+                    updated_at: Some("2025-06-01T12:00:00Z".to_owned()),
+                },
+            },
+            Repository::Private {
+                in_progress: InProgress {
+                    name: "wip".to_owned(),
+                    ..InProgress::default()
+                },
+            },
+        ]
+    }
+
+    #[test]
+    fn test_table() {
+        let table = table(&repos());
+        let text = table
+            .rows
+            .iter()
+            .map(|r| r.cells.iter().map(Line::text).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+
+        assert_eq!(table.headers.len(), 6);
+        assert_eq!(
+            text,
+            [
+                ["seaq", "About seaq", "Rust", "5", "1", "Released"],
+                ["wip", "", "", "", "", "Coming Soon"],
+            ]
+        );
+        assert_eq!(
+            table.rows[0].link.as_deref(),
+            Some("https://github.com/nt54hamnghi/seaq")
+        );
+        assert_eq!(table.rows[1].link, None);
+    }
+
+    #[test]
+    fn test_language_color() {
+        let dot = |lang: &str| language(Some(lang.to_owned())).0[0].glyph.clone();
+
+        assert_eq!(dot("Rust"), Some(Glyph::Dot("#dea584".to_owned())));
+        assert_eq!(
+            dot("Nope"),
+            Some(Glyph::Dot("var(--color-white)".to_owned()))
+        );
+        assert_eq!(language(None), Line::default());
+    }
+
+    #[test]
+    fn test_json() {
+        let output = output(&repos(), Format::Json);
+        let [Block::Group { blocks, .. }] = output.blocks.as_slice() else {
+            panic!("should be a single group");
+        };
+        let [Block::Pre(json)] = blocks.as_slice() else {
+            panic!("should be preformatted");
+        };
+
+        let value: serde_json::Value = serde_json::from_str(json).unwrap();
+        assert_eq!(value[0]["name"], "seaq");
+        assert_eq!(value[0].get("updated_at"), None);
+        assert_eq!(value[1]["status"], "private");
+        assert_eq!(output.status, 0);
+    }
+
+    /// The text of a format other than a table
+    fn text(format: Format) -> String {
+        let output = output(&repos(), format);
+        let [Block::Group { blocks, .. }] = output.blocks.as_slice() else {
+            panic!("should be a single group");
+        };
+        let [Block::Pre(text)] = blocks.as_slice() else {
+            panic!("should be preformatted");
+        };
+        text.clone()
+    }
+
+    #[test]
+    fn test_yaml() {
+        let value: serde_json::Value = serde_yaml::from_str(&text(Format::Yaml)).unwrap();
+        assert_eq!(value[0]["name"], "seaq");
+        assert_eq!(value[0]["stargazers_count"], 5);
+        assert_eq!(value[1]["status"], "private");
+    }
+
+    #[rstest]
+    #[case::csv(
+        Format::Csv,
+        "name,status,description,language,stargazers_count,forks,html_url\n\
+         seaq,public,About seaq,Rust,5,1,https://github.com/nt54hamnghi/seaq\n\
+         wip,private,,,,,"
+    )]
+    #[case::tsv(
+        Format::Tsv,
+        "name\tstatus\tdescription\tlanguage\tstargazers_count\tforks\thtml_url\n\
+         seaq\tpublic\tAbout seaq\tRust\t5\t1\thttps://github.com/nt54hamnghi/seaq\n\
+         wip\tprivate\t\t\t\t\t"
+    )]
+    #[case::markdown(
+        Format::Markdown,
+        "| name | status | description | language | stargazers_count | forks | html_url |\n\
+         | --- | --- | --- | --- | --- | --- | --- |\n\
+         | seaq | public | About seaq | Rust | 5 | 1 | https://github.com/nt54hamnghi/seaq |\n\
+         | wip | private |  |  |  |  |  |"
+    )]
+    fn test_records(#[case] format: Format, #[case] expected: &str) {
+        assert_eq!(text(format), expected);
+    }
+
+    #[rstest]
+    #[case::plain("a b", "a b")]
+    #[case::comma("a, b", "\"a, b\"")]
+    #[case::quote("say \"hi\"", "\"say \"\"hi\"\"\"")]
+    #[case::newline("a\nb", "\"a\nb\"")]
+    fn test_csv_field(#[case] s: &str, #[case] expected: &str) {
+        assert_eq!(csv_field(s), expected);
+    }
+
+    #[test]
+    fn test_markdown_escapes() {
+        let records = vec![vec!["a|b".to_owned(), "c\nd".to_owned()]];
+        assert!(markdown(&records).ends_with("| a\\|b | c d |\n"));
+    }
+
+    #[rstest]
+    #[case::none(&[], Format::Table)]
+    #[case::json(&["-j"], Format::Json)]
+    #[case::long_json(&["--json"], Format::Json)]
+    #[case::output(&["-o", "yaml"], Format::Yaml)]
+    #[case::attached(&["-ocsv"], Format::Csv)]
+    #[case::long(&["--output", "tsv"], Format::Tsv)]
+    #[case::equals(&["--output=md"], Format::Markdown)]
+    #[case::markdown(&["-o", "markdown"], Format::Markdown)]
+    #[case::last(&["-j", "-o", "table"], Format::Table)]
+    fn test_options(#[case] args: &[&str], #[case] format: Format) {
+        let args = args.iter().map(ToString::to_string).collect::<Vec<_>>();
+        let options = Options {
+            format,
+            ..Options::default()
+        };
+        assert_eq!(Options::parse(&args), Ok(options));
+    }
+
+    #[test]
+    fn test_options_filters() {
+        let args = [
+            "--sort",
+            "stars",
+            "--lang=rust",
+            "--status",
+            "released",
+            "--limit",
+            "2",
+            "--reverse",
+            "-o",
+            "csv",
+        ];
+        let args = args.map(str::to_owned);
+        assert_eq!(
+            Options::parse(&args),
+            Ok(Options {
+                format: Format::Csv,
+                sort: Some(Sort::Stars),
+                lang: Some("rust".to_owned()),
+                status: Some(Status::Released),
+                limit: Some(2),
+                reverse: true,
+                refresh: false,
+                verbose: false,
+            })
+        );
+    }
+
+    /// Released projects named after their stars, forks and day of update, and one in progress
+    fn unsorted() -> Vec<Repository> {
+        let released = |name: &str, stars, forks, day: u8, language: &str| Repository::Public {
+            released: Released {
+                name: name.to_owned(),
+                stargazers_count: stars,
+                forks,
+                language: Some(language.to_owned()),
+                updated_at: Some(format!("2025-06-{day:02}T00:00:00Z")),
+                ..Released::default()
+            },
+        };
+        vec![
+            released("b", 5, 0, 2, "Rust"),
+            Repository::Private {
+                in_progress: InProgress {
+                    name: "a".to_owned(),
+                    language: Some("Rust".to_owned()),
+                    ..InProgress::default()
+                },
+            },
+            released("C", 9, 1, 1, "Go"),
+            released("d", 1, 3, 3, "rust"),
+        ]
+    }
+
+    #[rstest]
+    #[case::none(&[], &["b", "a", "C", "d"])]
+    #[case::stars(&["--sort", "stars"], &["C", "b", "d", "a"])]
+    #[case::forks(&["--sort", "forks"], &["d", "C", "b", "a"])]
+    #[case::name(&["--sort", "name"], &["a", "b", "C", "d"])]
+    #[case::updated(&["--sort", "updated"], &["d", "b", "C", "a"])]
+    #[case::reverse(&["--sort", "stars", "--reverse"], &["a", "d", "b", "C"])]
+    #[case::lang(&["--lang", "RUST"], &["b", "a", "d"])]
+    #[case::released(&["--status", "released"], &["b", "C", "d"])]
+    #[case::in_progress(&["--status", "in-progress"], &["a"])]
+    #[case::limit(&["--sort", "name", "--limit", "2"], &["a", "b"])]
+    #[case::all(
+        &["--lang", "rust", "--status", "released", "--sort", "stars", "--limit", "1"],
+        &["b"]
+    )]
+    fn test_apply(#[case] args: &[&str], #[case] expected: &[&str]) {
+        let args = args.iter().map(ToString::to_string).collect::<Vec<_>>();
+        let repos = Options::parse(&args).unwrap().apply(unsorted());
+        assert_eq!(
+            repos.iter().map(Repository::name).collect::<Vec<_>>(),
+            expected
+        );
+    }
+
+    #[rstest]
+    #[case::unknown(&["-o", "xml"], "unknown format: xml, expected one of table, json, yaml, csv, tsv, md")]
+    #[case::missing(&["-o"], "flag needs a value: -o")]
+    #[case::operand(&["seaq"], "unexpected argument: seaq")]
+    #[case::sort(&["--sort", "size"], "unknown sort key: size, expected one of stars, forks, name, updated")]
+    #[case::status(&["--status=done"], "unknown status: done, expected one of released, in-progress")]
+    #[case::limit(&["--limit", "-1"], "invalid limit: '-1'")]
+    #[case::sort_value(&["--sort"], "flag needs a value: --sort")]
+    fn test_options_error(#[case] args: &[&str], #[case] expected: &str) {
+        let args = args.iter().map(ToString::to_string).collect::<Vec<_>>();
+        let output = Options::parse(&args).unwrap_err();
+        assert_eq!(output.blocks[0], Block::Error(expected.to_owned()));
+        assert_eq!(output.status, 2);
+    }
+
+    #[test]
+    fn test_stale_notice() {
+        let stale = Stale {
+            fetched_at: 0.0,
+            error: FetchError::RateLimited {
+                url: "https://api.github.com/users/a/repos".to_owned(),
+                status: 403,
+                resets_in: None,
+            },
+        };
+        assert_eq!(
+            stale_notice(&stale, 3.0 * 60.0 * 60_000.0),
+            "couldn't refresh project data (the rate limit was hit), \
+             showing what was fetched 3 hours ago"
+        );
+    }
+
+    #[test]
+    fn test_failed() {
+        let error = FetchError::NotFound {
+            url: "https://api.github.com/users/nobody/repos".to_owned(),
+        };
+        let errors = |verbose| {
+            failed(&error, verbose)
+                .blocks
+                .into_iter()
+                .map(|b| match b {
+                    Block::Error(e) => e,
+                    b => panic!("should be an error, not {b:?}"),
+                })
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            errors(false),
+            [
+                "error: failed to load project data: not found",
+                "check the username in the config"
+            ]
+        );
+        assert_eq!(
+            errors(true),
+            [
+                "error: failed to load project data: not found",
+                "GET https://api.github.com/users/nobody/repos: HTTP 404",
+                "check the username in the config"
+            ]
+        );
+        assert_eq!(failed(&error, false).status, 1);
+    }
+
+    #[test]
+    fn test_unexpected_option() {
+        let output = Projects::run(vec!["-x".to_owned()], Process::new());
+        assert_eq!(
+            output.blocks[0],
+            Block::Error("unexpected flag: -x".to_owned())
+        );
+        assert_eq!(output.status, 2);
+    }
+}
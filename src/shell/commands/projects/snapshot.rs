@@ -0,0 +1,153 @@
+use web_sys::js_sys::Date;
+
+use super::Released;
+use crate::config::Snapshot;
+use crate::shell::net::FetchError;
+
+/// Reads the repositories of the snapshot at `path`
+pub fn parse(path: &str, json: &str) -> Result<Vec<Released>, FetchError> {
+    serde_json::from_str(json).map_err(|e| FetchError::Parse {
+        url: path.to_owned(),
+        reason: e.to_string(),
+    })
+}
+
+/// Tells that the projects are from the snapshot, how old it is, and why, if fetching them
+/// failed. `taken_at` and `now` are in milliseconds since the epoch.
+pub fn notice(taken_at: Option<f64>, now: f64, failure: Option<&FetchError>) -> String {
+    let age = match taken_at {
+        Some(taken_at) => format!("showing a snapshot from {}", ago(now - taken_at)),
+        None => "showing a snapshot".to_owned(),
+    };
+    match failure {
+        Some(e) => format!("couldn't load project data ({e}), {age}"),
+        None => age,
+    }
+}
+
+/// How long ago something was, `ms` milliseconds back, in its largest unit
+pub fn ago(ms: f64) -> String {
+    const UNITS: [(&str, &str, u64); 5] = [
+        ("a year", "years", 365 * 24 * 60 * 60),
+        ("a month", "months", 30 * 24 * 60 * 60),
+        ("a day", "days", 24 * 60 * 60),
+        ("an hour", "hours", 60 * 60),
+        ("a minute", "minutes", 60),
+    ];
+
+    // one from the future, after the clock was turned back, is from now
+    let secs = (ms / 1000.0).max(0.0) as u64;
+    let Some((one, many, n)) = UNITS
+        .iter()
+        .map(|(one, many, len)| (one, many, secs / len))
+        .find(|(_, _, n)| *n > 0)
+    else {
+        return "just now".to_owned();
+    };
+    match n {
+        1 => format!("{one} ago"),
+        n => format!("{n} {many} ago"),
+    }
+}
+
+/// When the snapshot was taken, in milliseconds since the epoch, if it's known
+pub fn taken_at(snapshot: &Snapshot) -> Option<f64> {
+    let taken_at = snapshot.taken_at.as_deref()?;
+    Some(Date::parse(taken_at)).filter(|ms| !ms.is_nan())
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+    use crate::config::SNAPSHOT;
+
+    const MINUTE: f64 = 60_000.0;
+    const DAY: f64 = 24.0 * 60.0 * MINUTE;
+
+    #[rstest]
+    #[case::now(30_000.0, "just now")]
+    #[case::minute(MINUTE, "a minute ago")]
+    #[case::minutes(59.0 * MINUTE, "59 minutes ago")]
+    #[case::hour(60.0 * MINUTE, "an hour ago")]
+    #[case::hours(23.0 * 60.0 * MINUTE, "23 hours ago")]
+    #[case::day(DAY, "a day ago")]
+    #[case::days(3.0 * DAY, "3 days ago")]
+    #[case::months(65.0 * DAY, "2 months ago")]
+    #[case::years(800.0 * DAY, "2 years ago")]
+    #[case::from_the_future(-DAY, "just now")]
+    fn test_ago(#[case] ms: f64, #[case] expected: &str) {
+        assert_eq!(ago(ms), expected);
+    }
+
+    #[test]
+    fn test_notice() {
+        let now = 10.0 * DAY;
+        let failure = FetchError::Timeout {
+            url: "https://api.github.com/users/a/repos".to_owned(),
+        };
+
+        assert_eq!(
+            notice(Some(7.0 * DAY), now, None),
+            "showing a snapshot from 3 days ago"
+        );
+        assert_eq!(notice(None, now, None), "showing a snapshot");
+        assert_eq!(
+            notice(Some(now), now, Some(&failure)),
+            "couldn't load project data (the request timed out), showing a snapshot from just now"
+        );
+    }
+
+    #[test]
+    fn test_parse() {
+        let json = r#"[{
+            "name": "wcli",
+            "description": null,
+            "html_url": "https://github.com/ada/wcli",
+            "stargazers_count": 12,
+            "language": "Rust",
+            "forks": 2
+        }]"#;
+        let repos = parse("snapshot.json", json).unwrap();
+        assert_eq!(repos[0].name, "wcli");
+        assert_eq!(repos[0].stargazers_count, 12);
+
+        assert!(matches!(
+            parse("snapshot.json", "{}"),
+            Err(FetchError::Parse { url, .. }) if url == "snapshot.json"
+        ));
+    }
+
+    /// A snapshot as `just snapshot` takes one, cut down to two repositories
+    const FIXTURE: &str = include_str!("fixtures/snapshot.json");
+
+    #[test]
+    fn test_parse_fixture() {
+        let repos = parse("snapshot.json", FIXTURE).unwrap();
+        let summary = repos
+            .iter()
+            .map(|r| (r.name.as_str(), r.language.as_deref(), r.stargazers_count))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            summary,
+            [("seaq", Some("Go"), 5), ("wcli", Some("Rust"), 2)]
+        );
+        assert_eq!(
+            repos[0].description.as_deref(),
+            Some("Extract insights from web content with LLMs")
+        );
+        assert_eq!(repos[1].description, None);
+        assert_eq!(repos[1].html_url, "https://github.com/nt54hamnghi/wcli");
+        assert_eq!(repos[1].updated_at.as_deref(), Some("2025-06-01T12:00:00Z"));
+    }
+
+    /// The snapshot embedded by the config, if there is one
+    #[test]
+    fn test_embedded_snapshot_is_valid() {
+        if let Some(json) = SNAPSHOT {
+            assert!(!parse("snapshot", json).unwrap().is_empty());
+        }
+    }
+}
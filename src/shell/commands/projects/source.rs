@@ -0,0 +1,312 @@
+use std::collections::HashMap;
+
+use futures::stream::{self, StreamExt};
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+
+use super::Released;
+use crate::config::Source;
+use crate::shell::net::{self, FetchError, Stale, next_page};
+use crate::shell::process::CancelToken;
+use crate::stores::cache::Kind;
+
+/// Most pages of repositories fetched from a source
+const MAX_PAGES: usize = 10;
+/// Most requests for the languages of GitLab projects made at once
+const MAX_LANGUAGE_REQUESTS: usize = 6;
+
+/// A project as GitLab lists it
+#[derive(Debug, Deserialize)]
+struct GitlabProject {
+    id: u64,
+    name: String,
+    description: Option<String>,
+    web_url: String,
+    star_count: usize,
+    forks_count: usize,
+    last_activity_at: Option<String>,
+}
+
+impl From<GitlabProject> for Released {
+    /// GitLab lists no languages, so the language is filled in by `language` after
+    fn from(project: GitlabProject) -> Self {
+        Released {
+            name: project.name,
+            description: project.description.filter(|d| !d.is_empty()),
+            html_url: project.web_url,
+            stargazers_count: project.star_count,
+            language: None,
+            forks: project.forks_count,
+            updated_at: project.last_activity_at,
+        }
+    }
+}
+
+/// A repository as Gitea and Forgejo list it, with empty strings for what it hasn't got
+#[derive(Debug, Deserialize)]
+struct GiteaRepo {
+    name: String,
+    #[serde(default)]
+    description: String,
+    html_url: String,
+    stars_count: usize,
+    forks_count: usize,
+    #[serde(default)]
+    language: String,
+    updated_at: Option<String>,
+}
+
+impl From<GiteaRepo> for Released {
+    fn from(repo: GiteaRepo) -> Self {
+        let non_empty = |s: String| Some(s).filter(|s| !s.is_empty());
+        Released {
+            name: repo.name,
+            description: non_empty(repo.description),
+            html_url: repo.html_url,
+            stargazers_count: repo.stars_count,
+            language: non_empty(repo.language),
+            forks: repo.forks_count,
+            updated_at: repo.updated_at,
+        }
+    }
+}
+
+/// Repositories of a source, some of which may be cached ones
+/// that are used after fetching them again failed
+pub type Fetched = (Vec<Released>, Option<Stale>);
+
+/// Fetches the repositories of a source, in the shape GitHub lists them.
+/// `only` lists the names of those to show, if not all of them, so nothing more
+/// is fetched for the others.
+pub async fn fetch(
+    source: &Source,
+    only: &[String],
+    ttl: u64,
+    cancel: CancelToken,
+    refresh: bool,
+) -> Result<Fetched, FetchError> {
+    let url = source.api_url();
+    match source {
+        Source::Github { .. } | Source::File { .. } => {
+            pages::<Released>(url, ttl, cancel, refresh).await
+        },
+        Source::Gitlab { .. } => {
+            let (projects, stale) = pages::<GitlabProject>(url, ttl, cancel, refresh).await?;
+            let projects = listed(projects, only);
+            let languages = projects
+                .iter()
+                .map(|p| language(source, p.id, ttl, cancel, refresh));
+            let languages = stream::iter(languages)
+                .buffered(MAX_LANGUAGE_REQUESTS)
+                .collect::<Vec<_>>()
+                .await;
+            let repos = projects
+                .into_iter()
+                .zip(languages)
+                .map(|(project, language)| Released {
+                    language,
+                    ..project.into()
+                })
+                .collect();
+            Ok((repos, stale))
+        },
+        Source::Gitea { .. } => {
+            let (repos, stale) = pages::<GiteaRepo>(url, ttl, cancel, refresh).await?;
+            Ok((repos.into_iter().map(Into::into).collect(), stale))
+        },
+    }
+}
+
+/// The projects named in `only`, or all of them if it's empty
+fn listed(mut projects: Vec<GitlabProject>, only: &[String]) -> Vec<GitlabProject> {
+    if !only.is_empty() {
+        projects.retain(|p| only.contains(&p.name));
+    }
+    projects
+}
+
+/// Fetches the main language of the GitLab project `id`, if it can be told
+async fn language(
+    source: &Source,
+    id: u64,
+    ttl: u64,
+    cancel: CancelToken,
+    refresh: bool,
+) -> Option<String> {
+    let url = source.languages_url(id)?;
+    let entry = net::fetch(&url, Kind::Detail, ttl, cancel, refresh)
+        .await
+        .ok()?;
+    top_language(&entry.body)
+}
+
+/// The language most of a project is in, from GitLab's share of each, as in
+/// `{"Rust": 80.5, "HTML": 19.5}`
+fn top_language(json: &str) -> Option<String> {
+    let shares = serde_json::from_str::<HashMap<String, f64>>(json).ok()?;
+    shares
+        .into_iter()
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(name, _)| name)
+}
+
+/// Fetches a list from `url` and the pages after it, which GitHub, GitLab and Gitea
+/// all link to in a `Link` header, along with the first page that's stale, if any
+async fn pages<T: DeserializeOwned>(
+    url: String,
+    ttl: u64,
+    cancel: CancelToken,
+    refresh: bool,
+) -> Result<(Vec<T>, Option<Stale>), FetchError> {
+    let mut repos = Vec::new();
+    let mut stale = None;
+    let mut url = Some(url);
+    for _ in 0..MAX_PAGES {
+        let Some(page_url) = url.take() else {
+            break;
+        };
+        let page = net::fetch(&page_url, Kind::List, ttl, cancel, refresh).await?;
+        stale = stale.or_else(|| Stale::of(&page));
+        url = page.link.as_deref().and_then(next_page).map(str::to_owned);
+        let page = serde_json::from_str::<Vec<T>>(&page.body).map_err(|e| FetchError::Parse {
+            url: page_url,
+            reason: e.to_string(),
+        })?;
+        repos.extend(page);
+    }
+    Ok((repos, stale))
+}
+
+/// The repositories of several sources in one list, in the order of the sources.
+/// One mirrored to several of them is listed once, from the first, by its name.
+pub fn merge(sources: Vec<Vec<Released>>) -> Vec<Released> {
+    let mut repos: Vec<Released> = Vec::new();
+    for repo in sources.into_iter().flatten() {
+        if !repos
+            .iter()
+            .any(|r| r.name.eq_ignore_ascii_case(&repo.name))
+        {
+            repos.push(repo);
+        }
+    }
+    repos
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[test]
+    fn test_gitlab() {
+        let json = r#"[{
+            "id": 1,
+            "name": "wcli",
+            "path_with_namespace": "ada/wcli",
+            "description": "",
+            "web_url": "https://gitlab.com/ada/wcli",
+            "star_count": 3,
+            "forks_count": 1,
+            "last_activity_at": "2025-06-01T12:00:00.000Z"
+        }]"#;
+        let repos = serde_json::from_str::<Vec<GitlabProject>>(json).unwrap();
+        let released = Released::from(repos.into_iter().next().unwrap());
+
+        assert_eq!(released.name, "wcli");
+        assert_eq!(released.description, None);
+        assert_eq!(released.html_url, "https://gitlab.com/ada/wcli");
+        assert_eq!(released.stargazers_count, 3);
+        assert_eq!(released.language, None);
+        assert_eq!(released.forks, 1);
+        assert_eq!(
+            released.updated_at.as_deref(),
+            Some("2025-06-01T12:00:00.000Z")
+        );
+    }
+
+    #[rstest]
+    #[case::all(&[], &["wcli", "seaq"])]
+    #[case::some(&["seaq", "nope"], &["seaq"])]
+    fn test_listed(#[case] only: &[&str], #[case] expected: &[&str]) {
+        let projects = ["wcli", "seaq"]
+            .into_iter()
+            .enumerate()
+            .map(|(id, name)| GitlabProject {
+                id: id as u64,
+                name: name.to_owned(),
+                description: None,
+                web_url: format!("https://gitlab.com/ada/{name}"),
+                star_count: 0,
+                forks_count: 0,
+                last_activity_at: None,
+            })
+            .collect();
+        let only = only.iter().map(ToString::to_string).collect::<Vec<_>>();
+        let names = listed(projects, &only)
+            .into_iter()
+            .map(|p| p.name)
+            .collect::<Vec<_>>();
+        assert_eq!(names, expected);
+    }
+
+    #[rstest]
+    #[case::most(r#"{"HTML": 19.5, "Rust": 80.5}"#, Some("Rust"))]
+    #[case::none("{}", None)]
+    #[case::invalid("[]", None)]
+    fn test_top_language(#[case] json: &str, #[case] expected: Option<&str>) {
+        assert_eq!(top_language(json).as_deref(), expected);
+    }
+
+    #[test]
+    fn test_gitea() {
+        let json = r#"[{
+            "id": 1,
+            "name": "wcli",
+            "full_name": "ada/wcli",
+            "description": "A terminal",
+            "html_url": "https://codeberg.org/ada/wcli",
+            "stars_count": 2,
+            "forks_count": 0,
+            "language": "",
+            "updated_at": "2025-06-01T12:00:00Z"
+        }]"#;
+        let repos = serde_json::from_str::<Vec<GiteaRepo>>(json).unwrap();
+        let released = Released::from(repos.into_iter().next().unwrap());
+
+        assert_eq!(released.description.as_deref(), Some("A terminal"));
+        assert_eq!(released.html_url, "https://codeberg.org/ada/wcli");
+        assert_eq!(released.stargazers_count, 2);
+        assert_eq!(released.language, None);
+    }
+
+    #[test]
+    fn test_merge() {
+        let repo = |name: &str, html_url: &str| Released {
+            name: name.to_owned(),
+            html_url: html_url.to_owned(),
+            ..Released::default()
+        };
+        let github = vec![
+            repo("wcli", "https://github.com/ada/wcli"),
+            repo("seaq", "https://github.com/ada/seaq"),
+        ];
+        let gitea = vec![
+            repo("WCLI", "https://git.example.com/ada/wcli"),
+            repo("dots", "https://git.example.com/ada/dots"),
+        ];
+
+        let urls = merge(vec![github, gitea])
+            .into_iter()
+            .map(|r| r.html_url)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            urls,
+            [
+                "https://github.com/ada/wcli",
+                "https://github.com/ada/seaq",
+                "https://git.example.com/ada/dots",
+            ]
+        );
+    }
+}
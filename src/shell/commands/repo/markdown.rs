@@ -0,0 +1,363 @@
+use pulldown_cmark::{Event, Options, Parser, Tag, TagEnd};
+
+use crate::shell::output::ansi::Sgr;
+use crate::shell::output::{Block, Line, Row, Span, Style, Table};
+
+/// Renders Markdown, like a README, as blocks. Links relative to the document are
+/// resolved against `base`, and HTML and links that could run code, which can't be
+/// shown safely, are left out.
+pub fn render(markdown: &str, base: &str) -> Vec<Block> {
+    let options =
+        Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
+    let mut renderer = Renderer {
+        base,
+        ..Renderer::default()
+    };
+    for event in Parser::new_ext(markdown, options) {
+        renderer.event(event);
+    }
+    renderer.blocks
+}
+
+#[derive(Default)]
+struct Renderer<'a> {
+    base: &'a str,
+    blocks: Vec<Block>,
+    /// Lines of the paragraph being written, before the one in `spans`
+    lines: Vec<Line>,
+    spans: Vec<Span>,
+    /// Styles of the spans, the innermost last
+    styles: Vec<Style>,
+    /// Where the spans link to, the innermost last, `None` for a link that goes nowhere
+    links: Vec<Option<String>>,
+    /// Lists being written, the innermost last
+    lists: Vec<List>,
+    /// Code of the code block being written
+    code: Option<String>,
+    table: Option<Table>,
+    /// Cells of the table row being written
+    cells: Vec<Line>,
+    /// How many quotes deep the text is
+    quotes: usize,
+}
+
+#[derive(Default)]
+struct List {
+    /// Number of the next item, in an ordered list
+    next: Option<u64>,
+    items: Vec<Line>,
+}
+
+impl Renderer<'_> {
+    fn event(&mut self, event: Event) {
+        match event {
+            Event::Start(tag) => self.start(tag),
+            Event::End(tag) => self.end(tag),
+            Event::Text(text) => match &mut self.code {
+                Some(code) => code.push_str(&text),
+                None => self.text(&text),
+            },
+            Event::Code(code) | Event::InlineMath(code) | Event::DisplayMath(code) => {
+                self.styles.push(Style::Command);
+                self.text(&code);
+                self.styles.pop();
+            },
+            Event::SoftBreak => self.text(" "),
+            Event::HardBreak => self.break_line(),
+            Event::TaskListMarker(done) => self.text(if done { "[x] " } else { "[ ] " }),
+            Event::Rule | Event::Html(_) | Event::InlineHtml(_) | Event::FootnoteReference(_) => {},
+        }
+    }
+
+    fn start(&mut self, tag: Tag) {
+        match tag {
+            Tag::Heading { .. } => self.styles.push(Style::Heading),
+            Tag::BlockQuote(_) => self.quotes += 1,
+            Tag::CodeBlock(_) => self.code = Some(String::new()),
+            Tag::List(first) => {
+                // a list in an item ends the text of the item
+                self.end_item();
+                self.lists.push(List {
+                    next: first,
+                    items: Vec::new(),
+                });
+            },
+            Tag::Item => {
+                if let Some(n) = self.lists.last_mut().and_then(|l| l.next.as_mut()) {
+                    self.spans.push(Span::new(format!("{n}. ")));
+                    *n += 1;
+                }
+            },
+            Tag::Table(_) => self.table = Some(Table::default()),
+            Tag::Emphasis => self.styles.push(Style::Ansi(Sgr {
+                italic: true,
+                ..Sgr::default()
+            })),
+            Tag::Strong => self.styles.push(Style::Strong),
+            Tag::Link { dest_url, .. } => {
+                self.links.push(self.resolve(&dest_url));
+                self.styles.push(Style::Accent);
+            },
+            // the alt text stands in for an image, linked to it unless it's in a link
+            Tag::Image { dest_url, .. } => {
+                let link = match self.links.last() {
+                    Some(link) => link.clone(),
+                    None => self.resolve(&dest_url),
+                };
+                self.links.push(link);
+                self.styles.push(Style::Muted);
+            },
+            _ => {},
+        }
+    }
+
+    fn end(&mut self, tag: TagEnd) {
+        match tag {
+            TagEnd::Paragraph if self.lists.is_empty() => self.end_paragraph(),
+            // paragraphs of an item run on, as in a tight list
+            TagEnd::Paragraph => self.text(" "),
+            TagEnd::Heading(_) => {
+                self.end_paragraph();
+                self.styles.pop();
+            },
+            TagEnd::BlockQuote(_) => self.quotes -= 1,
+            TagEnd::CodeBlock => {
+                let code = self.code.take().unwrap_or_default();
+                let code = code.trim_end_matches('\n');
+                match self.lists.last_mut() {
+                    Some(list) => list
+                        .items
+                        .extend(code.lines().map(|l| Span::styled(l, Style::Command).into())),
+                    None => self.push(vec![Block::Pre(code.to_owned())]),
+                }
+            },
+            TagEnd::List(_) => {
+                let Some(list) = self.lists.pop() else {
+                    return;
+                };
+                match (self.lists.last_mut(), list.next) {
+                    // a nested list is indented under the item it's in
+                    (Some(parent), _) => {
+                        parent.items.extend(list.items.into_iter().map(|mut item| {
+                            item.0.insert(0, Span::new("  "));
+                            item
+                        }));
+                    },
+                    (None, Some(_)) => self.push(list.items.into_iter().map(Block::Line).collect()),
+                    (None, None) => self.push(vec![Block::List(list.items)]),
+                }
+            },
+            TagEnd::Item => self.end_item(),
+            TagEnd::TableCell => {
+                let cell = Line(std::mem::take(&mut self.spans));
+                self.cells.push(cell);
+            },
+            TagEnd::TableHead => {
+                let headers = self.cells.drain(..).map(|c| c.text()).collect();
+                if let Some(table) = &mut self.table {
+                    table.headers = headers;
+                }
+            },
+            TagEnd::TableRow => {
+                let cells = std::mem::take(&mut self.cells);
+                if let Some(table) = &mut self.table {
+                    table.rows.push(Row { cells, link: None });
+                }
+            },
+            TagEnd::Table => {
+                if let Some(table) = self.table.take() {
+                    self.push(vec![Block::Table(table)]);
+                }
+            },
+            TagEnd::Emphasis | TagEnd::Strong => {
+                self.styles.pop();
+            },
+            TagEnd::Link | TagEnd::Image => {
+                self.links.pop();
+                self.styles.pop();
+            },
+            _ => {},
+        }
+    }
+
+    fn text(&mut self, text: &str) {
+        let mut span = Span::new(text);
+        if let Some(style) = self.styles.last() {
+            span = span.with_style(style.clone());
+        }
+        if let Some(Some(link)) = self.links.last() {
+            span = span.with_link(link.clone());
+        }
+        self.spans.push(span);
+    }
+
+    /// Starts another line of the paragraph
+    fn break_line(&mut self) {
+        let mut spans = std::mem::take(&mut self.spans);
+        if self.quotes > 0 {
+            spans.insert(0, Span::styled("│ ".repeat(self.quotes), Style::Muted));
+        }
+        self.lines.push(Line(spans));
+    }
+
+    fn end_paragraph(&mut self) {
+        self.break_line();
+        let lines = std::mem::take(&mut self.lines);
+        self.push(lines.into_iter().map(Block::Line).collect());
+    }
+
+    /// Adds the text written since the item started to the list, if there is any
+    fn end_item(&mut self) {
+        let mut spans = std::mem::take(&mut self.spans);
+        while spans.last().is_some_and(|s| s.text.trim().is_empty()) {
+            spans.pop();
+        }
+        if spans.is_empty() {
+            return;
+        }
+        if let Some(list) = self.lists.last_mut() {
+            list.items.push(Line(spans));
+        }
+    }
+
+    /// Adds the blocks of a paragraph, list or the like, apart from those before it
+    fn push(&mut self, blocks: Vec<Block>) {
+        if !self.blocks.is_empty() {
+            self.blocks.push(Block::Blank);
+        }
+        self.blocks.extend(blocks);
+    }
+
+    /// Where a link goes, with one relative to the document resolved against the base.
+    /// Links to a heading go nowhere, as they'd leave the terminal, and neither do those
+    /// with a scheme other than `SCHEMES`, like `javascript:`, which could run code.
+    fn resolve(&self, url: &str) -> Option<String> {
+        if url.starts_with('#') {
+            return None;
+        }
+        match scheme(url) {
+            Some(_) => is_safe(url).then(|| url.to_owned()),
+            None => {
+                let path = url.trim_start_matches("./").trim_start_matches('/');
+                Some(format!("{}{path}", self.base))
+            },
+        }
+    }
+}
+
+/// Schemes a link may have
+const SCHEMES: [&str; 3] = ["http", "https", "mailto"];
+
+/// Whether `url` is absolute with one of `SCHEMES`, so it's safe to link to
+pub(super) fn is_safe(url: &str) -> bool {
+    scheme(url).is_some_and(|scheme| SCHEMES.iter().any(|s| s.eq_ignore_ascii_case(scheme)))
+}
+
+/// The scheme of an absolute URL, as `https` in `https://leptos.dev`
+fn scheme(url: &str) -> Option<&str> {
+    let (scheme, _) = url.split_once(':')?;
+    let mut chars = scheme.chars();
+    let valid = chars.next()?.is_ascii_alphabetic()
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'));
+    valid.then_some(scheme)
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    const BASE: &str = "https://github.com/ada/wcli/blob/main/";
+
+    fn text(blocks: &[Block]) -> Vec<String> {
+        blocks
+            .iter()
+            .map(|b| match b {
+                Block::Line(l) => l.text(),
+                Block::Blank => String::new(),
+                Block::Pre(text) => format!("pre: {text}"),
+                Block::List(items) => {
+                    let items = items.iter().map(Line::text).collect::<Vec<_>>();
+                    format!("list: {}", items.join(" / "))
+                },
+                Block::Table(t) => format!("table: {} by {}", t.headers.join(" "), t.rows.len()),
+                b => panic!("unexpected block {b:?}"),
+            })
+            .collect()
+    }
+
+    #[rstest]
+    #[case::paragraphs("one\ntwo\n\nthree", &["one two", "", "three"])]
+    #[case::hard_break("one  \ntwo", &["one", "two"])]
+    #[case::heading("# wcli\n\nA terminal", &["wcli", "", "A terminal"])]
+    #[case::code("```rust\nfn main() {}\n```", &["pre: fn main() {}"])]
+    #[case::list("- a\n- b\n  - c", &["list: a / b /   c"])]
+    #[case::ordered("1. a\n2. b", &["1. a", "2. b"])]
+    #[case::tasks("- [x] done\n- [ ] not yet", &["list: [x] done / [ ] not yet"])]
+    #[case::quote("> said", &["│ said"])]
+    #[case::table("| a | b |\n| - | - |\n| 1 | 2 |", &["table: a b by 1"])]
+    #[case::html("<p align=\"center\">logo</p>\n\ntext", &["text"])]
+    fn test_render(#[case] markdown: &str, #[case] expected: &[&str]) {
+        assert_eq!(text(&render(markdown, BASE)), expected);
+    }
+
+    #[test]
+    fn test_render_styles() {
+        let blocks = render("**wcli** is `cargo`-built *fast*", BASE);
+        let [Block::Line(Line(spans))] = blocks.as_slice() else {
+            panic!("should be a line");
+        };
+
+        let styles = spans.iter().map(|s| &s.style).collect::<Vec<_>>();
+        assert_eq!(
+            styles,
+            [
+                &Style::Strong,
+                &Style::Plain,
+                &Style::Command,
+                &Style::Plain,
+                &Style::Ansi(Sgr {
+                    italic: true,
+                    ..Sgr::default()
+                }),
+            ]
+        );
+    }
+
+    #[rstest]
+    #[case::absolute("[x](https://leptos.dev)", Some("https://leptos.dev"))]
+    #[case::relative(
+        "[x](docs/setup.md)",
+        Some("https://github.com/ada/wcli/blob/main/docs/setup.md")
+    )]
+    #[case::dot(
+        "[x](./LICENSE)",
+        Some("https://github.com/ada/wcli/blob/main/LICENSE")
+    )]
+    #[case::root("[x](/LICENSE)", Some("https://github.com/ada/wcli/blob/main/LICENSE"))]
+    #[case::anchor("[x](#usage)", None)]
+    #[case::mailto("[x](mailto:ada@example.com)", Some("mailto:ada@example.com"))]
+    #[case::upper_case("[x](HTTPS://leptos.dev)", Some("HTTPS://leptos.dev"))]
+    #[case::colon_in_path(
+        "[x](docs/a:b.md)",
+        Some("https://github.com/ada/wcli/blob/main/docs/a:b.md")
+    )]
+    #[case::javascript("[x](javascript:alert(1))", None)]
+    #[case::javascript_slashes("[x](javascript://%0Aalert(1))", None)]
+    #[case::javascript_upper_case("[x](JavaScript:alert(1))", None)]
+    #[case::data("[x](data:text/html;base64,PHNjcmlwdD4=)", None)]
+    #[case::data_image("![x](data:image/svg+xml;base64,PHN2Zz4=)", None)]
+    #[case::image(
+        "![logo](logo.png)",
+        Some("https://github.com/ada/wcli/blob/main/logo.png")
+    )]
+    #[case::badge("[![CI](badge.svg)](https://ci.test)", Some("https://ci.test"))]
+    fn test_render_links(#[case] markdown: &str, #[case] expected: Option<&str>) {
+        let blocks = render(markdown, BASE);
+        let [Block::Line(Line(spans))] = blocks.as_slice() else {
+            panic!("should be a line");
+        };
+        assert_eq!(spans[0].link.as_deref(), expected);
+    }
+}
@@ -0,0 +1,447 @@
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+
+use super::{Command, getopt, usage_error};
+use crate::config::CONFIG;
+use crate::shell::net::{self, FetchError};
+use crate::shell::output::{Block, CommandOutput, Glyph, Line, Span, Style};
+use crate::shell::process::{CancelToken, Process};
+use crate::stores::cache::Kind;
+
+mod markdown;
+
+/// Width of the bar of languages, in characters
+const BAR_WIDTH: usize = 48;
+
+/// A repository as GitHub shows it on its own
+#[derive(Debug, Clone, Default, Deserialize)]
+struct Details {
+    full_name: String,
+    description: Option<String>,
+    html_url: String,
+    homepage: Option<String>,
+    #[serde(default)]
+    topics: Vec<String>,
+    license: Option<License>,
+    /// When something was last pushed to it, in ISO 8601
+    pushed_at: Option<String>,
+    open_issues_count: usize,
+    languages_url: String,
+    default_branch: String,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+struct License {
+    name: String,
+}
+
+/// A README as GitHub gives it, in base64
+#[derive(Debug, Deserialize)]
+struct Readme {
+    content: String,
+}
+
+pub struct Repo;
+
+impl Command for Repo {
+    const NAME: &'static str = "repo";
+    const DESCRIPTION: &'static str = "look into one of my projects";
+    const USAGE: &'static str = "\t\
+    repo NAME           show the project NAME, with its languages and README
+    repo OWNER/NAME     show a project of someone else on GitHub";
+
+    fn run(args: Vec<String>, process: Process) -> CommandOutput {
+        let (_, operands) = match getopt(args, "", Self::USAGE) {
+            Ok(parsed) => parsed,
+            Err(output) => return output,
+        };
+        let [name] = operands.as_slice() else {
+            return usage_error(
+                "repo: expected the name of a project".to_owned(),
+                Self::USAGE,
+            );
+        };
+        if !is_valid_name(name) {
+            return usage_error(format!("repo: {name}: not a project name"), Self::USAGE);
+        }
+        let Some(url) = CONFIG.sources().iter().find_map(|s| s.repo_url(name)) else {
+            return CommandOutput::error("repo: no GitHub source in the config", 1);
+        };
+
+        let name = name.clone();
+        let cancel = process.cancel;
+        CommandOutput::deferred("One moment...", async move {
+            match get::<Details>(&url, cancel).await {
+                Ok(details) => show(details, &url, cancel),
+                Err(e) => failed(&name, &e),
+            }
+        })
+    }
+
+    fn suggest() -> Vec<String> {
+        CONFIG
+            .github
+            .repos
+            .iter()
+            .map(|r| format!("repo {r}"))
+            .collect()
+    }
+}
+
+/// Whether `name` is that of a project, maybe after its owner, as `wcli` or `ada/wcli`,
+/// so it's only ever a path to a repository once put in a URL
+fn is_valid_name(name: &str) -> bool {
+    let parts = name.split('/').collect::<Vec<_>>();
+    (1..=2).contains(&parts.len())
+        && parts.iter().all(|part| {
+            !matches!(*part, "" | "." | "..")
+                && part
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-'))
+        })
+}
+
+/// GETs JSON from `url` through the cache
+async fn get<T: DeserializeOwned>(url: &str, cancel: CancelToken) -> Result<T, FetchError> {
+    let entry = net::fetch(url, Kind::Detail, CONFIG.github.cache_ttl, cancel, false).await?;
+    serde_json::from_str(&entry.body).map_err(|e| FetchError::Parse {
+        url: url.to_owned(),
+        reason: e.to_string(),
+    })
+}
+
+fn failed(name: &str, error: &FetchError) -> CommandOutput {
+    let blocks = match error {
+        FetchError::NotFound { .. } => vec![Block::Error(format!("repo: {name}: no such project"))],
+        e => vec![
+            Block::Error(format!("repo: failed to load {name}: {e}")),
+            Block::Error(e.hint().to_owned()),
+        ],
+    };
+    CommandOutput::new(blocks).with_status(1)
+}
+
+/// The details of a repository, with its languages and README loading after them
+fn show(details: Details, url: &str, cancel: CancelToken) -> CommandOutput {
+    let mut blocks = overview(&details);
+
+    let languages_url = details.languages_url.clone();
+    let languages = CommandOutput::deferred("Loading languages...", async move {
+        match get::<Map<String, Value>>(&languages_url, cancel).await {
+            Ok(bytes) => {
+                CommandOutput::new(vec![Block::group("repo-languages", languages(&bytes))])
+            },
+            Err(e) => CommandOutput::error(format!("repo: failed to load languages: {e}"), 1),
+        }
+    });
+    blocks.push(Block::Blank);
+    blocks.extend(languages.blocks);
+
+    let readme_url = format!("{url}/readme");
+    let base = format!("{}/blob/{}/", details.html_url, details.default_branch);
+    let readme = CommandOutput::deferred("Loading README...", async move {
+        match get::<Readme>(&readme_url, cancel).await {
+            Ok(readme) => match decode(&readme.content) {
+                Some(text) => CommandOutput::new(vec![Block::group(
+                    "repo-readme",
+                    markdown::render(&text, &base),
+                )]),
+                None => CommandOutput::error("repo: failed to read the README", 1),
+            },
+            Err(FetchError::NotFound { .. }) => {
+                CommandOutput::line(Span::styled("No README yet", Style::Muted))
+            },
+            Err(e) => CommandOutput::error(format!("repo: failed to load the README: {e}"), 1),
+        }
+    });
+    blocks.push(Block::Blank);
+    blocks.extend(readme.blocks);
+
+    CommandOutput::new(blocks)
+}
+
+/// The name linked to the repository on GitHub, its description and what's known about it
+fn overview(details: &Details) -> Vec<Block> {
+    let Details {
+        full_name,
+        description,
+        html_url,
+        homepage,
+        topics,
+        license,
+        pushed_at,
+        open_issues_count,
+        ..
+    } = details.clone();
+
+    let mut blocks = vec![Block::Line(
+        Span::styled(full_name, Style::Strong)
+            .with_link(html_url.clone())
+            .into(),
+    )];
+    if let Some(description) = description {
+        blocks.push(Block::Line(description.into()));
+    }
+
+    let mut items = Vec::new();
+    let mut item = |label: &str, value: Line| {
+        items.push((Span::styled(label, Style::Label).into(), value));
+    };
+    if !topics.is_empty() {
+        item("topics", topics.join(", ").into());
+    }
+    if let Some(license) = license {
+        item("license", license.name.into());
+    }
+    if let Some(pushed_at) = pushed_at {
+        // the date is enough, as in 2025-06-01T12:00:00Z
+        let date = pushed_at.split('T').next().unwrap_or_default().to_owned();
+        item("pushed", date.into());
+    }
+    item(
+        "issues",
+        Span::new(format!("{open_issues_count} open"))
+            .with_link(format!("{html_url}/issues"))
+            .into(),
+    );
+    if let Some(homepage) = homepage.filter(|h| !h.is_empty()) {
+        // it's whatever the owner typed in, so only a web or mail address is linked to
+        let span = Span::new(homepage.clone()).with_style(Style::Accent);
+        let span = if markdown::is_safe(&homepage) { span.with_link(homepage) } else { span };
+        item("homepage", span.into());
+    }
+
+    blocks.push(Block::Blank);
+    blocks.push(Block::Terms {
+        id: Some("repo"),
+        items,
+    });
+    blocks
+}
+
+/// A bar split between the languages by how much of the code is in each,
+/// over each with its share, in the color GitHub shows it in
+fn languages(bytes: &Map<String, Value>) -> Vec<Block> {
+    let bytes = bytes
+        .iter()
+        .filter_map(|(lang, n)| Some((lang.as_str(), n.as_u64()?)))
+        .collect::<Vec<_>>();
+    let total = bytes.iter().map(|(_, n)| n).sum::<u64>();
+    if total == 0 {
+        return vec![Block::Line(
+            Span::styled("No code yet", Style::Muted).into(),
+        )];
+    }
+
+    let mut bar = Vec::new();
+    let mut legend = Vec::new();
+    // each ends where its share of the bar so far does, so they add up to all of it
+    let (mut so_far, mut end) = (0, 0);
+    for (lang, n) in bytes {
+        let color = CONFIG
+            .language_color(lang)
+            .unwrap_or("var(--color-white)")
+            .to_owned();
+        so_far += n;
+        let start = end;
+        end = (so_far as f64 / total as f64 * BAR_WIDTH as f64).round() as usize;
+        if end > start {
+            bar.push(Span::styled(
+                "█".repeat(end - start),
+                Style::Color(color.clone()),
+            ));
+        }
+
+        if !legend.is_empty() {
+            legend.push(Span::new("  "));
+        }
+        let share = n as f64 / total as f64 * 100.0;
+        legend.push(Span::new(format!("{lang} {share:.1}%")).with_glyph(Glyph::Dot(color)));
+    }
+    vec![Block::Line(Line(bar)), Block::Line(Line(legend))]
+}
+
+/// The text of base64 content, which GitHub breaks into lines
+fn decode(content: &str) -> Option<String> {
+    let content = content.split_whitespace().collect::<String>();
+    let bytes = STANDARD.decode(content).ok()?;
+    String::from_utf8(bytes).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    fn details() -> Details {
+        Details {
+            full_name: "ada/wcli".to_owned(),
+            description: Some("A terminal".to_owned()),
+            html_url: "https://github.com/ada/wcli".to_owned(),
+            homepage: Some("https://wcli.dev".to_owned()),
+            topics: vec!["cli".to_owned(), "wasm".to_owned()],
+            license: Some(License {
+                name: "MIT License".to_owned(),
+            }),
+            pushed_at: Some("2025-06-01T12:00:00Z".to_owned()),
+            open_issues_count: 3,
+            ..Details::default()
+        }
+    }
+
+    #[test]
+    fn test_overview() {
+        let blocks = overview(&details());
+        let Block::Line(title) = &blocks[0] else {
+            panic!("should start with the name");
+        };
+        assert_eq!(title.text(), "ada/wcli");
+        assert_eq!(
+            title.0[0].link.as_deref(),
+            Some("https://github.com/ada/wcli")
+        );
+
+        let Some(Block::Terms { items, .. }) = blocks.last() else {
+            panic!("should end with the details");
+        };
+        let items = items
+            .iter()
+            .map(|(term, desc)| (term.text(), desc.text()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            items,
+            [
+                ("topics".to_owned(), "cli, wasm".to_owned()),
+                ("license".to_owned(), "MIT License".to_owned()),
+                ("pushed".to_owned(), "2025-06-01".to_owned()),
+                ("issues".to_owned(), "3 open".to_owned()),
+                ("homepage".to_owned(), "https://wcli.dev".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_overview_leaves_out_what_is_missing() {
+        let details = Details {
+            full_name: "ada/wcli".to_owned(),
+            homepage: Some(String::new()),
+            ..Details::default()
+        };
+        let Some(Block::Terms { items, .. }) = overview(&details).pop() else {
+            panic!("should end with the details");
+        };
+        let terms = items.iter().map(|(t, _)| t.text()).collect::<Vec<_>>();
+        assert_eq!(terms, ["issues"]);
+    }
+
+    #[rstest]
+    #[case::https("https://wcli.dev", Some("https://wcli.dev"))]
+    #[case::javascript("javascript:alert(1)", None)]
+    #[case::data("data:text/html;base64,PHNjcmlwdD4=", None)]
+    #[case::no_scheme("wcli.dev", None)]
+    fn test_overview_homepage(#[case] homepage: &str, #[case] link: Option<&str>) {
+        let details = Details {
+            homepage: Some(homepage.to_owned()),
+            ..details()
+        };
+        let Some(Block::Terms { items, .. }) = overview(&details).pop() else {
+            panic!("should end with the details");
+        };
+        let (_, desc) = items.last().unwrap();
+        assert_eq!(desc.text(), homepage);
+        assert_eq!(desc.0[0].link.as_deref(), link);
+    }
+
+    #[test]
+    fn test_languages() {
+        let bytes = serde_json::from_str(r#"{"Rust": 7500, "HTML": 2000, "CSS": 500}"#).unwrap();
+        let blocks = languages(&bytes);
+        let [Block::Line(bar), Block::Line(legend)] = blocks.as_slice() else {
+            panic!("should be a bar and a legend");
+        };
+
+        let widths = bar
+            .0
+            .iter()
+            .map(|s| s.text.chars().count())
+            .collect::<Vec<_>>();
+        assert_eq!(widths, [36, 10, 2]);
+        assert_eq!(bar.0[0].style, Style::Color("#dea584".to_owned()));
+        assert_eq!(legend.text(), "Rust 75.0%  HTML 20.0%  CSS 5.0%");
+    }
+
+    #[test]
+    fn test_languages_without_code() {
+        let blocks = languages(&Map::new());
+        assert_eq!(
+            blocks,
+            [Block::Line(
+                Span::styled("No code yet", Style::Muted).into()
+            )]
+        );
+    }
+
+    #[rstest]
+    #[case::lines("IyB3\nY2xp\n", Some("# wcli"))]
+    #[case::invalid("not base64!", None)]
+    fn test_decode(#[case] content: &str, #[case] expected: Option<&str>) {
+        assert_eq!(decode(content).as_deref(), expected);
+    }
+
+    #[rstest]
+    #[case::missing(&[], "repo: expected the name of a project")]
+    #[case::several(&["wcli", "seaq"], "repo: expected the name of a project")]
+    #[case::flag(&["-x"], "unexpected flag: -x")]
+    #[case::query(&["a/b?x"], "repo: a/b?x: not a project name")]
+    #[case::parent(&["../x"], "repo: ../x: not a project name")]
+    #[case::nested(&["a/b/c"], "repo: a/b/c: not a project name")]
+    #[case::empty_owner(&["/wcli"], "repo: /wcli: not a project name")]
+    fn test_repo_usage(#[case] args: &[&str], #[case] expected: &str) {
+        let args = args.iter().map(ToString::to_string).collect();
+        let output = Repo::run(args, Process::new());
+        assert_eq!(output.blocks[0], Block::Error(expected.to_owned()));
+        assert_eq!(output.status, 2);
+    }
+
+    #[rstest]
+    #[case::name("wcli", true)]
+    #[case::owner("nt54hamnghi/wcli", true)]
+    #[case::punctuation("my_repo-2.0", true)]
+    #[case::dot_name("ada/.github", true)]
+    #[case::query("a/b?x", false)]
+    #[case::parent("../x", false)]
+    #[case::dot(".", false)]
+    #[case::space("a b", false)]
+    #[case::empty("", false)]
+    fn test_is_valid_name(#[case] name: &str, #[case] expected: bool) {
+        assert_eq!(is_valid_name(name), expected);
+    }
+
+    #[test]
+    fn test_failed() {
+        let not_found = FetchError::NotFound {
+            url: "https://api.github.com/repos/ada/nope".to_owned(),
+        };
+        assert_eq!(
+            failed("nope", &not_found).blocks,
+            [Block::Error("repo: nope: no such project".to_owned())]
+        );
+
+        let timeout = FetchError::Timeout {
+            url: "https://api.github.com/repos/ada/wcli".to_owned(),
+        };
+        let output = failed("wcli", &timeout);
+        assert_eq!(
+            output.blocks,
+            [
+                Block::Error("repo: failed to load wcli: the request timed out".to_owned()),
+                Block::Error("the server may be busy, try again in a moment".to_owned()),
+            ]
+        );
+        assert_eq!(output.status, 1);
+    }
+}
@@ -0,0 +1,206 @@
+use std::time::Duration;
+
+use leptos::prelude::*;
+
+use super::Command;
+use crate::components::screen::Animate;
+use crate::shell::output::CommandOutput;
+use crate::shell::process::Process;
+use crate::shell::raw::{Animation, Cell, Color, Flow, Grid};
+
+const ROWS: usize = 16;
+const COLS: usize = 86;
+const FRAME_RATE: Duration = Duration::from_millis(40);
+
+const LOCOMOTIVE: [&str; 7] = [
+    r"      ====        ________                ___________ ",
+    r"  _D _|  |_______/        \__I_I_____===__|_________| ",
+    r"   |(_)---  |   H\________/ |   |        =|___ ___|   ",
+    r"   /     |  |   H  |  |     |   |         ||_| |_||   ",
+    r"  |      |  |   H  |__--------------------| [___] |   ",
+    r"  | ________|___H__/__|_____/[][]~\_______|       |   ",
+    r"  |/ |   |-----------I_____I [][] []  D   |=======|__ ",
+];
+
+/// The driving wheels in each position of a turn
+const WHEELS: [[&str; 3]; 6] = [
+    [
+        r"__/ =| o |=-~~\  /~~\  /~~\  /~~\ ____Y___________|__ ",
+        r" |/-=|___|=    ||    ||    ||    |_____/~\___/        ",
+        r"  \_/      \O=====O=====O=====O_/      \_/            ",
+    ],
+    [
+        r"__/ =| o |=-~~\  /~~\  /~~\  /~~\ ____Y___________|__ ",
+        r" |/-=|___|=O=====O=====O=====O   |_____/~\___/        ",
+        r"  \_/      \__/  \__/  \__/  \__/      \_/            ",
+    ],
+    [
+        r"__/ =| o |=-O=====O=====O=====O \ ____Y___________|__ ",
+        r" |/-=|___|=    ||    ||    ||    |_____/~\___/        ",
+        r"  \_/      \__/  \__/  \__/  \__/      \_/            ",
+    ],
+    [
+        r"__/ =| o |=-~O=====O=====O=====O\ ____Y___________|__ ",
+        r" |/-=|___|=    ||    ||    ||    |_____/~\___/        ",
+        r"  \_/      \__/  \__/  \__/  \__/      \_/            ",
+    ],
+    [
+        r"__/ =| o |=-~~\  /~~\  /~~\  /~~\ ____Y___________|__ ",
+        r" |/-=|___|=   O=====O=====O=====O|_____/~\___/        ",
+        r"  \_/      \__/  \__/  \__/  \__/      \_/            ",
+    ],
+    [
+        r"__/ =| o |=-~~\  /~~\  /~~\  /~~\ ____Y___________|__ ",
+        r" |/-=|___|=    ||    ||    ||    |_____/~\___/        ",
+        r"  \_/      \_O=====O=====O=====O/      \_/            ",
+    ],
+];
+
+const COAL_CAR: [&str; 10] = [
+    r"                              ",
+    r"                              ",
+    r"    _________________         ",
+    r"   _|                \_____A  ",
+    r" =|                        |  ",
+    r" -|                        |  ",
+    r"__|________________________|_ ",
+    r"|__________________________|_ ",
+    r"   |_D__D__D_|  |_D__D__D_|   ",
+    r"    \_/   \_/    \_/   \_/    ",
+];
+
+/// Puffs of smoke, growing as they drift away from the funnel
+const SMOKE: [&str; 4] = ["(@@)", "(  )", "(@@@@)", "(    )"];
+
+/// Width of the locomotive, where the coal car is hitched
+const LOCOMOTIVE_WIDTH: isize = 54;
+/// Width of the whole train
+const WIDTH: usize = LOCOMOTIVE_WIDTH as usize + 30;
+/// Rows above the train, for the smoke
+const SKY: usize = ROWS - COAL_CAR.len();
+
+pub struct Sl;
+
+impl Command for Sl {
+    const NAME: &'static str = "sl";
+    const DESCRIPTION: &'static str = "steam locomotive";
+    const USAGE: &'static str = "\t\
+    sl  watch a train go by for mistyping ls
+        press Ctrl+C to stop it";
+
+    fn run(_: Vec<String>, _: Process) -> CommandOutput {
+        CommandOutput::view(|| view! { <Animate animation=Train::new() /> })
+    }
+}
+
+/// A train crossing the screen from right to left
+#[derive(Debug, Clone)]
+struct Train {
+    /// Column of the front of the train, negative once it starts leaving the screen
+    x: isize,
+}
+
+impl Train {
+    fn new() -> Self {
+        Self { x: COLS as isize }
+    }
+
+    /// Writes `s` at (row, x), clipping whatever falls off either side of the grid
+    fn print(grid: &mut Grid, row: usize, x: isize, s: &str, fg: Color) {
+        for (i, ch) in s.chars().enumerate() {
+            if let Ok(col) = usize::try_from(x + i as isize) {
+                grid.set(row, col, Cell::new(ch, fg));
+            }
+        }
+    }
+}
+
+impl Animation for Train {
+    fn size(&self) -> (usize, usize) {
+        (ROWS, COLS)
+    }
+
+    fn frame_rate(&self) -> Duration {
+        FRAME_RATE
+    }
+
+    fn advance(&mut self) -> Flow {
+        self.x -= 1;
+        match self.x + WIDTH as isize <= 0 {
+            true => Flow::Exit,
+            false => Flow::Continue,
+        }
+    }
+
+    fn draw(&self, grid: &mut Grid) {
+        let x = self.x;
+
+        let wheels = WHEELS[x.rem_euclid(WHEELS.len() as isize) as usize];
+        for (i, line) in LOCOMOTIVE.iter().chain(wheels.iter()).enumerate() {
+            Self::print(grid, SKY + i, x, line, Color::Foreground);
+        }
+        for (i, line) in COAL_CAR.iter().enumerate() {
+            Self::print(grid, SKY + i, x + LOCOMOTIVE_WIDTH, line, Color::Foreground);
+        }
+
+        // smoke drifts up and back from the funnel, changing shape as the train moves
+        let phase = (x.rem_euclid(16) / 4) as usize;
+        for i in 0..SMOKE.len() {
+            let puff = SMOKE[(i + phase) % SMOKE.len()];
+            Self::print(
+                grid,
+                SKY - 1 - i,
+                x + 6 + 3 * i as isize,
+                puff,
+                Color::Primary,
+            );
+        }
+    }
+
+    fn still(&mut self) {
+        self.x = ((COLS - WIDTH) / 2) as isize;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_art_widths() {
+        let widths = LOCOMOTIVE
+            .iter()
+            .chain(WHEELS.iter().flatten())
+            .map(|line| line.chars().count());
+        assert!(widths.into_iter().all(|w| w == 54));
+        assert!(COAL_CAR.iter().all(|line| line.chars().count() == 30));
+    }
+
+    #[test]
+    fn test_train_leaves_the_screen() {
+        let mut train = Train::new();
+        let frames = std::iter::repeat_with(|| train.advance())
+            .take_while(|flow| *flow == Flow::Continue)
+            .count();
+
+        assert_eq!(frames, COLS + WIDTH - 1);
+    }
+
+    #[test]
+    fn test_still_shows_the_whole_train() {
+        let mut train = Train::new();
+        train.still();
+        let mut grid = Grid::new(ROWS, COLS);
+        train.draw(&mut grid);
+
+        let text = grid
+            .lines()
+            .map(|line| line.iter().map(|c| c.ch).collect::<String>())
+            .collect::<Vec<_>>();
+        assert!(text.iter().any(|line| line.contains("_D _|  |___")));
+        assert!(
+            text.iter()
+                .any(|line| line.contains(r"\_/   \_/    \_/   \_/"))
+        );
+    }
+}
@@ -0,0 +1,188 @@
+use std::time::Duration;
+
+use leptos::prelude::*;
+
+use super::Command;
+use crate::components::screen::Screen;
+use crate::games::snake::{Direction, Game, HighScores, State};
+use crate::shell::output::CommandOutput;
+use crate::shell::process::Process;
+use crate::shell::raw::{Cell, Color, Flow, Grid, Key, Program};
+use crate::stores::storage;
+
+const STORAGE_KEY: &str = "wcli:snake";
+/// Size of the board, in snake cells
+const ROWS: usize = 16;
+const COLS: usize = 24;
+
+pub struct Snake;
+
+impl Command for Snake {
+    const NAME: &'static str = "snake";
+    const DESCRIPTION: &'static str = "play a game of snake";
+    const USAGE: &'static str = "\t\
+    snake  move with the arrow keys or WASD
+           press p to pause, r to restart, q to quit";
+
+    fn run(_: Vec<String>, _: Process) -> CommandOutput {
+        let program = SnakeProgram {
+            game: Game::new(ROWS, COLS, fastrand::u64(..)),
+            scores: storage::load(STORAGE_KEY).unwrap_or_default(),
+            rank: None,
+            recorded: false,
+        };
+
+        CommandOutput::view(|| view! { <Screen program=program /> })
+    }
+}
+
+struct SnakeProgram {
+    game: Game,
+    scores: HighScores,
+    /// Rank of the last finished game in the high-score table, if it made it in
+    rank: Option<usize>,
+    /// Whether the current game's score has been recorded
+    recorded: bool,
+}
+
+impl SnakeProgram {
+    /// Records the score of the current game once, and persists the high-score table
+    fn finish(&mut self) {
+        if self.recorded {
+            return;
+        }
+        self.recorded = true;
+        self.rank = self.scores.record(self.game.score());
+        if self.rank.is_some() {
+            storage::save(STORAGE_KEY, &self.scores);
+        }
+    }
+
+    fn restart(&mut self) {
+        self.game = Game::new(ROWS, COLS, fastrand::u64(..));
+        self.rank = None;
+        self.recorded = false;
+    }
+
+    /// Writes `s` centered on the given row of the board
+    fn banner(grid: &mut Grid, row: usize, s: &str, fg: Color) {
+        let col = (grid.cols().saturating_sub(s.chars().count())) / 2;
+        grid.print(row, col, s, fg);
+    }
+}
+
+impl Program for SnakeProgram {
+    fn size(&self) -> (usize, usize) {
+        // a title line, a bordered board, and a help line
+        // each snake cell is two characters wide so it looks square
+        (ROWS + 4, COLS * 2 + 2)
+    }
+
+    fn tick_rate(&self) -> Option<Duration> {
+        (self.game.state() == State::Running).then(|| self.game.interval())
+    }
+
+    fn on_key(&mut self, key: &Key) -> Flow {
+        if key.is_interrupt() {
+            self.finish();
+            return Flow::Exit;
+        }
+
+        match key.name.as_str() {
+            "q" | "Q" | "Escape" => {
+                self.finish();
+                return Flow::Exit;
+            },
+            "p" | "P" | " " => self.game.toggle_pause(),
+            "r" | "R" if self.game.state() == State::Over => self.restart(),
+            name => {
+                if let Some(direction) = Direction::from_key(name) {
+                    self.game.turn(direction);
+                }
+            },
+        }
+
+        Flow::Continue
+    }
+
+    fn on_tick(&mut self) -> Flow {
+        self.game.step();
+        if self.game.state() == State::Over {
+            self.finish();
+        }
+        Flow::Continue
+    }
+
+    fn draw(&self, grid: &mut Grid) {
+        let width = COLS * 2;
+        let best = self
+            .scores
+            .best()
+            .unwrap_or_default()
+            .max(self.game.score());
+        grid.print(
+            0,
+            0,
+            &format!(" SNAKE  score: {}  best: {best}", self.game.score()),
+            Color::Primary,
+        );
+
+        // border
+        grid.print(1, 0, &format!("┌{}┐", "─".repeat(width)), Color::Primary);
+        for row in 0..ROWS {
+            grid.print(row + 2, 0, "│", Color::Primary);
+            grid.print(row + 2, width + 1, "│", Color::Primary);
+        }
+        grid.print(
+            ROWS + 2,
+            0,
+            &format!("└{}┘", "─".repeat(width)),
+            Color::Primary,
+        );
+
+        let mut fill = |(row, col): (usize, usize), color: Color| {
+            let cell = Cell::new(' ', color).on(color);
+            grid.set(row + 2, col * 2 + 1, cell);
+            grid.set(row + 2, col * 2 + 2, cell);
+        };
+        if let Some(food) = self.game.food() {
+            fill(food, Color::Red);
+        }
+        for (i, point) in self.game.snake().iter().enumerate() {
+            fill(*point, if i == 0 { Color::Yellow } else { Color::Green });
+        }
+
+        let help = match self.game.state() {
+            State::Running => "arrows/wasd move · p pause · q quit",
+            State::Paused => "p resume · q quit",
+            State::Over => "r restart · q quit",
+        };
+        Self::banner(grid, ROWS + 3, help, Color::Foreground);
+
+        match self.game.state() {
+            State::Running => {},
+            State::Paused => Self::banner(grid, ROWS / 2 + 2, " PAUSED ", Color::Yellow),
+            State::Over => {
+                let top = ROWS / 2 - 2;
+                Self::banner(grid, top, " GAME OVER ", Color::Red);
+                Self::banner(grid, top + 2, " HIGH SCORES ", Color::Primary);
+                for (i, score) in self.scores.scores().iter().enumerate() {
+                    let line = format!(" {}. {score:>4} ", i + 1);
+                    let color =
+                        if self.rank == Some(i) { Color::Yellow } else { Color::Foreground };
+                    Self::banner(grid, top + 3 + i, &line, color);
+                }
+            },
+        }
+    }
+
+    fn summary(&self) -> Option<String> {
+        let score = self.game.score();
+        let summary = match (self.rank, self.scores.best()) {
+            (Some(0), _) => format!("snake: scored {score}, a new high score!"),
+            (_, Some(best)) => format!("snake: scored {score} (best: {best})"),
+            (_, None) => format!("snake: scored {score}"),
+        };
+        Some(summary)
+    }
+}
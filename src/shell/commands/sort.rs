@@ -0,0 +1,116 @@
+use std::cmp::Ordering;
+
+use super::{Command, getopt, input, printed, unlines};
+use crate::shell::output::CommandOutput;
+use crate::shell::process::Process;
+
+pub struct Sort;
+
+impl Command for Sort {
+    const NAME: &'static str = "sort";
+    const DESCRIPTION: &'static str = "sort lines";
+    const USAGE: &'static str = "\t\
+    sort [file]...     sort the lines of the files, or of what's piped in
+    sort -r [file]...  sort in reverse
+    sort -n [file]...  sort by the number each line starts with
+    sort -u [file]...  leave out lines equal to one before them
+
+    Flags combine, as in -rn";
+
+    fn run(args: Vec<String>, process: Process) -> CommandOutput {
+        let (flags, files) = match getopt(args, "rnu", Self::USAGE) {
+            Ok(parsed) => parsed,
+            Err(output) => return output,
+        };
+        let has = |flag| flags.iter().any(|(c, _)| *c == flag);
+        let order = Order {
+            reverse: has('r'),
+            numeric: has('n'),
+            unique: has('u'),
+        };
+
+        let (text, errors) = input(Self::NAME, &files, process);
+        let lines = text.lines().collect::<Vec<_>>();
+        printed(&unlines(sort(&lines, order)), errors)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Order {
+    reverse: bool,
+    /// Compares the numbers the lines start with, where a line without one counts as 0
+    numeric: bool,
+    /// Keeps only the first of the lines that compare equal
+    unique: bool,
+}
+
+fn sort<'a>(lines: &[&'a str], order: Order) -> Vec<&'a str> {
+    let key = |a: &str, b: &str| match order.numeric {
+        true => number(a).total_cmp(&number(b)),
+        false => a.cmp(b),
+    };
+    // lines with equal keys are ordered by their text, unless only one of them is kept
+    let compare = |a: &&str, b: &&str| {
+        let ordering = match order.unique {
+            true => key(a, b),
+            false => key(a, b).then_with(|| a.cmp(b)),
+        };
+        if order.reverse { ordering.reverse() } else { ordering }
+    };
+
+    let mut lines = lines.to_vec();
+    lines.sort_by(compare);
+    if order.unique {
+        lines.dedup_by(|a, b| key(a, b) == Ordering::Equal);
+    }
+    lines
+}
+
+/// The number a line starts with, after any blanks, as in `sort -n`
+fn number(line: &str) -> f64 {
+    let line = line.trim_start();
+    let digits = |s: &str| s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+
+    let mut end = usize::from(line.starts_with('-'));
+    end += digits(&line[end..]);
+    if line[end..].starts_with('.') {
+        end += 1 + digits(&line[end + 1..]);
+    }
+    line[..end].parse().unwrap_or(0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    const LINES: [&str; 6] = ["b", "10 x", "a", "9", "-1", "b"];
+
+    #[rstest]
+    #[case::text(Order::default(), &["-1", "10 x", "9", "a", "b", "b"])]
+    #[case::reverse(Order { reverse: true, ..Order::default() }, &["b", "b", "a", "9", "10 x", "-1"])]
+    #[case::numeric(Order { numeric: true, ..Order::default() }, &["-1", "a", "b", "b", "9", "10 x"])]
+    #[case::unique(Order { unique: true, ..Order::default() }, &["-1", "10 x", "9", "a", "b"])]
+    #[case::numeric_unique(
+        Order { numeric: true, unique: true, ..Order::default() },
+        &["-1", "b", "9", "10 x"]
+    )]
+    #[case::reverse_numeric(
+        Order { reverse: true, numeric: true, ..Order::default() },
+        &["10 x", "9", "b", "b", "a", "-1"]
+    )]
+    fn test_sort(#[case] order: Order, #[case] expected: &[&str]) {
+        assert_eq!(sort(&LINES, order), expected);
+    }
+
+    #[rstest]
+    #[case::integer("42", 42.0)]
+    #[case::decimal(" 1.5kb", 1.5)]
+    #[case::negative("-3 items", -3.0)]
+    #[case::none("abc", 0.0)]
+    #[case::dash("-", 0.0)]
+    fn test_number(#[case] line: &str, #[case] expected: f64) {
+        assert_eq!(number(line), expected);
+    }
+}
@@ -0,0 +1,43 @@
+use leptos::prelude::*;
+
+use super::Command;
+use crate::shell::output::CommandOutput;
+use crate::shell::process::Process;
+use crate::shell::script::use_script;
+use crate::stores::fs::{self, use_fs};
+
+pub struct Source;
+
+impl Command for Source {
+    const NAME: &'static str = "source";
+    const DESCRIPTION: &'static str = "run commands from a file";
+    const USAGE: &'static str = "\t\
+    source [file]  run the commands in a file, one line at a time
+                   blank lines and lines starting with # are skipped
+                   ~/.wclirc is sourced on boot, unless the page is opened with ?nostartup";
+
+    fn run(args: Vec<String>, _: Process) -> CommandOutput {
+        let error = match args.first() {
+            None => "source: missing file operand".to_owned(),
+            Some(arg) => match fs::normalize(arg) {
+                Ok(path) => {
+                    let (fs, _set_fs) = use_fs().expect("not yet created");
+                    let queue = use_script().expect("not yet created");
+                    let found = fs.with_untracked(|fs| {
+                        let script = fs.read(&path)?;
+                        queue.update(|queue| queue.source(script));
+                        Some(())
+                    });
+                    // the commands are run one by one once this one is done, as if typed
+                    if found.is_some() {
+                        return CommandOutput::default();
+                    }
+                    format!("source: {arg}: no such file")
+                },
+                Err(e) => format!("source: {e}"),
+            },
+        };
+
+        CommandOutput::error(error, 1)
+    }
+}
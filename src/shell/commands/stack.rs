@@ -1,9 +1,7 @@
-use leptos::prelude::*;
-use leptos::reactive::wrappers::write::SignalSetter;
-use leptos_icons::Icon;
-
 use super::Command;
 use crate::config::{CONFIG, StackItem};
+use crate::shell::output::{Block, CommandOutput, Glyph, Line, Span, Style};
+use crate::shell::process::Process;
 
 pub struct Stack;
 
@@ -13,45 +11,71 @@ impl Command for Stack {
     const USAGE: &'static str = "\t\
     stack";
 
-    fn run(_: Vec<String>, _: SignalSetter<bool>) -> Option<impl IntoView> {
+    fn run(_: Vec<String>, _: Process) -> CommandOutput {
         let stack = CONFIG
             .stack
             .iter()
-            .map(
-                |(title, items)| view! { <StackSection title=title.clone() items=items.clone() /> },
-            )
-            .collect_view();
+            .map(|(title, items)| section(title, items))
+            .collect();
 
-        Some(view! { <div class="flex flex-col gap-6">{stack}</div> })
+        CommandOutput::new(stack)
     }
 }
 
-#[component]
-fn StackSection(title: String, items: Vec<StackItem>) -> impl IntoView {
-    view! {
-        <div>
-            <h3 class="flex gap-2 items-center pb-1">
-                <span class="font-bold text-primary">">"</span>
-                <span>{title.to_uppercase()}</span>
-            </h3>
-            <div class="flex flex-wrap gap-4">
-                {items.into_iter().map(|i| view! { <StackBadge item=i /> }).collect_view()}
-            </div>
-        </div>
+fn section(title: &str, items: &[StackItem]) -> Block {
+    Block::Group {
+        id: None,
+        title: Some(Line(vec![
+            Span::styled(">", Style::Heading),
+            Span::new(format!(" {}", title.to_uppercase())),
+        ])),
+        blocks: vec![Block::Badges(items.iter().map(badge).collect())],
     }
 }
 
-#[component]
-fn StackBadge(item: StackItem) -> impl IntoView {
-    let StackItem { name, icon, color } = item;
-    let color = color.unwrap_or("var(--color-white)".to_owned());
-    view! {
-        <div
-            class="flex gap-2 items-center py-2 px-3 rounded-md border text-foreground"
-            style=format!("border-color: {color}")
-        >
-            <Icon icon=icon height="1.5rem" width="1.5rem" {..} style=format!("color: {color}") />
-            <span class="flex-1">{name}</span>
-        </div>
+fn badge(item: &StackItem) -> Span {
+    let StackItem { name, icon, color } = item.clone();
+    let style = color.map_or(Style::Plain, Style::Color);
+    Span::styled(name, style).with_glyph(Glyph::Icon(icon))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_section() {
+        let items = [
+            StackItem {
+                name: "rust".to_owned(),
+                icon: icondata::FaRustBrands,
+                color: Some("#c45508".to_owned()),
+            },
+            StackItem {
+                name: "github".to_owned(),
+                icon: icondata::FaGithubBrands,
+                color: None,
+            },
+        ];
+        let Block::Group { title, blocks, .. } = section("backend", &items) else {
+            panic!("should be a group");
+        };
+
+        assert_eq!(title.map(|t| t.text()), Some("> BACKEND".to_owned()));
+        assert_eq!(
+            blocks,
+            [Block::Badges(vec![
+                Span::styled("rust", Style::Color("#c45508".to_owned()))
+                    .with_glyph(Glyph::Icon(icondata::FaRustBrands)),
+                Span::new("github").with_glyph(Glyph::Icon(icondata::FaGithubBrands)),
+            ])]
+        );
+    }
+
+    #[test]
+    fn test_run() {
+        let output = Stack::run(vec![], Process::new());
+        assert_eq!(output.blocks.len(), CONFIG.stack.len());
+        assert_eq!(output.status, 0);
     }
 }
@@ -0,0 +1,87 @@
+use super::head::{LINES, shorthand};
+use super::{Command, getopt, input, printed, unlines};
+use crate::shell::output::CommandOutput;
+use crate::shell::process::Process;
+
+pub struct Tail;
+
+impl Command for Tail {
+    const NAME: &'static str = "tail";
+    const DESCRIPTION: &'static str = "print the last lines";
+    const USAGE: &'static str = "\t\
+    tail [file]...        print the last 10 lines of the files, or of what's piped in
+    tail -n N [file]...   print the last N lines, also written -N
+    tail -n +N [file]...  print the lines from the Nth on";
+
+    fn run(args: Vec<String>, process: Process) -> CommandOutput {
+        let (flags, files) = match getopt(shorthand(args), "n:", Self::USAGE) {
+            Ok(parsed) => parsed,
+            Err(output) => return output,
+        };
+        let count = match flags.last() {
+            Some((_, Some(n))) => match count(n) {
+                Some(count) => count,
+                None => {
+                    return CommandOutput::error(
+                        format!("tail: invalid number of lines: '{n}'"),
+                        1,
+                    );
+                },
+            },
+            _ => Count::Last(LINES),
+        };
+
+        let (text, errors) = input(Self::NAME, &files, process);
+        let lines = text.lines().collect::<Vec<_>>();
+        printed(&unlines(tail(&lines, count)), errors)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Count {
+    /// The last lines, as in `-n 5`
+    Last(usize),
+    /// The lines from the one numbered from 1, as in `-n +5`
+    From(usize),
+}
+
+fn count(s: &str) -> Option<Count> {
+    match s.strip_prefix('+') {
+        Some(n) => n.parse().ok().map(Count::From),
+        None => s.parse().ok().map(Count::Last),
+    }
+}
+
+fn tail<'a>(lines: &[&'a str], count: Count) -> Vec<&'a str> {
+    let skip = match count {
+        Count::Last(n) => lines.len().saturating_sub(n),
+        Count::From(n) => n.saturating_sub(1),
+    };
+    lines.iter().skip(skip).copied().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case::last(Count::Last(2), &["b", "c"])]
+    #[case::more(Count::Last(5), &["a", "b", "c"])]
+    #[case::none(Count::Last(0), &[])]
+    #[case::from(Count::From(2), &["b", "c"])]
+    #[case::from_zero(Count::From(0), &["a", "b", "c"])]
+    #[case::from_past_end(Count::From(9), &[])]
+    fn test_tail(#[case] count: Count, #[case] expected: &[&str]) {
+        assert_eq!(tail(&["a", "b", "c"], count), expected);
+    }
+
+    #[rstest]
+    #[case::last("3", Some(Count::Last(3)))]
+    #[case::from("+3", Some(Count::From(3)))]
+    #[case::invalid("x", None)]
+    fn test_count(#[case] s: &str, #[case] expected: Option<Count>) {
+        assert_eq!(count(s), expected);
+    }
+}
@@ -1,10 +1,11 @@
 use std::str::FromStr;
 
 use leptos::prelude::*;
-use leptos::reactive::wrappers::write::SignalSetter;
 use strum::{IntoEnumIterator, VariantNames};
 
-use super::{Command, UnexpectedOption};
+use super::{Command, unexpected_option};
+use crate::shell::output::{Block, CommandOutput};
+use crate::shell::process::Process;
 use crate::stores::theme::{Theme as ThemeChoice, use_theme};
 
 #[derive(Debug, Clone, Copy)]
@@ -18,48 +19,28 @@ impl Command for Theme {
     theme [name]      use the specified theme
     theme -l, --list  list available themes";
 
-    fn run(args: Vec<String>, _: SignalSetter<bool>) -> Option<impl IntoView> {
-        let (theme, set_theme) = use_theme().unwrap();
-
-        let selected = if args.is_empty() {
-            let current = theme.get();
-            ThemeChoice::random_except(current)
-        } else {
-            let opt = args.first().expect("has at least 1 item");
-
-            if opt.starts_with('-') {
-                match opt.as_str() {
-                    "-l" | "--list" => return Some(view! { <ThemeList /> }.into_any()),
-                    _ => {
-                        return Some(
-                            view! { <UnexpectedOption opt=opt usage=Self::USAGE /> }.into_any(),
-                        );
-                    },
-                }
-            };
-
-            match ThemeChoice::from_str(opt.as_str()) {
-                Ok(t) => t,
+    fn run(args: Vec<String>, _: Process) -> CommandOutput {
+        let chosen = match args.first().map(String::as_str) {
+            None => None,
+            Some("-l" | "--list") => return CommandOutput::new(vec![theme_list()]),
+            Some(opt) if opt.starts_with('-') => return unexpected_option(opt, Self::USAGE),
+            Some(name) => match ThemeChoice::from_str(name) {
+                Ok(t) => Some(t),
                 Err(_) => {
-                    return Some(
-                        view! {
-                            <div class="text-fail">
-                                <p>{format!("theme '{opt}' is not supported")}</p>
-                                <ThemeList />
-                            </div>
-                        }
-                        .into_any(),
-                    );
+                    return CommandOutput::new(vec![
+                        Block::Error(format!("theme '{name}' is not supported")),
+                        theme_list(),
+                    ])
+                    .with_status(1);
                 },
-            }
+            },
         };
 
+        let (theme, set_theme) = use_theme().expect("not yet created");
+        let selected = chosen.unwrap_or_else(|| ThemeChoice::random_except(theme.get_untracked()));
         set_theme.set(selected);
 
-        Some(
-            view! { <p class="text-foreground">{format!("theme '{selected}' selected")}</p> }
-                .into_any(),
-        )
+        CommandOutput::line(format!("theme '{selected}' selected"))
     }
 
     fn suggest() -> Vec<String> {
@@ -69,7 +50,63 @@ impl Command for Theme {
     }
 }
 
-#[component]
-fn ThemeList() -> impl IntoView {
-    view! { <p>"available themes: " {ThemeChoice::VARIANTS.join(", ")}</p> }
+fn theme_list() -> Block {
+    Block::Line(format!("available themes: {}", ThemeChoice::VARIANTS.join(", ")).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stores::theme::create_theme;
+
+    fn run(args: &[&str]) -> CommandOutput {
+        Theme::run(
+            args.iter().map(ToString::to_string).collect(),
+            Process::new(),
+        )
+    }
+
+    #[test]
+    fn test_list() {
+        let output = run(&["--list"]);
+        assert_eq!(output.blocks, [theme_list()]);
+        assert_eq!(output.status, 0);
+    }
+
+    #[test]
+    fn test_not_supported() {
+        let output = run(&["nope"]);
+        assert_eq!(
+            output.blocks,
+            [
+                Block::Error("theme 'nope' is not supported".to_owned()),
+                theme_list()
+            ]
+        );
+        assert_eq!(output.status, 1);
+    }
+
+    #[test]
+    fn test_unexpected_option() {
+        let output = run(&["-x"]);
+        assert_eq!(
+            output.blocks[0],
+            Block::Error("unexpected flag: -x".to_owned())
+        );
+        assert_eq!(output.status, 2);
+    }
+
+    #[test]
+    fn test_select() {
+        Owner::new().with(|| {
+            let (theme, _) = create_theme();
+
+            let output = run(&["nord"]);
+            assert_eq!(output.blocks, [Block::Line("theme 'nord' selected".into())]);
+            assert_eq!(theme.get_untracked(), ThemeChoice::Nord);
+
+            run(&[]);
+            assert_ne!(theme.get_untracked(), ThemeChoice::Nord);
+        });
+    }
 }
@@ -0,0 +1,185 @@
+use std::collections::HashMap;
+
+use super::{Command, getopt, printed};
+use crate::shell::output::CommandOutput;
+use crate::shell::process::Process;
+
+/// Classes of characters a set can name, as in `[:upper:]`, with the ranges in them in order
+const CLASSES: [(&str, &[(char, char)]); 6] = [
+    ("alnum", &[('0', '9'), ('A', 'Z'), ('a', 'z')]),
+    ("alpha", &[('A', 'Z'), ('a', 'z')]),
+    ("digit", &[('0', '9')]),
+    ("lower", &[('a', 'z')]),
+    ("space", &[('\t', '\r'), (' ', ' ')]),
+    ("upper", &[('A', 'Z')]),
+];
+
+pub struct Tr;
+
+impl Command for Tr {
+    const NAME: &'static str = "tr";
+    const DESCRIPTION: &'static str = "translate or delete characters";
+    const USAGE: &'static str = "\t\
+    tr SET1 SET2  replace the characters piped in that are in SET1 with those in SET2
+    tr -d SET1    delete the characters piped in that are in SET1
+
+    Sets are made of characters, ranges like a-z, classes like [:upper:],
+    and escapes like \\n or \\t. SET2 is extended with its last character to the length of SET1";
+
+    fn run(args: Vec<String>, process: Process) -> CommandOutput {
+        let (flags, sets) = match getopt(args, "d", Self::USAGE) {
+            Ok(parsed) => parsed,
+            Err(output) => return output,
+        };
+        let delete = !flags.is_empty();
+
+        let text = process.stdin().unwrap_or_default();
+        let translated = match (delete, sets.as_slice()) {
+            (_, []) => Err("missing operand".to_owned()),
+            (true, [from]) => Ok(tr(&text, &set(from), None)),
+            (false, [from]) => Err(format!("missing operand after '{from}'")),
+            (false, [from, to]) => {
+                let to = set(to);
+                match to.is_empty() {
+                    true => Err("when not truncating set1, string2 must be non-empty".to_owned()),
+                    false => Ok(tr(&text, &set(from), Some(&to))),
+                }
+            },
+            (_, [.., extra]) => Err(format!("extra operand '{extra}'")),
+        };
+
+        match translated {
+            Ok(text) => printed(&text, Vec::new()),
+            Err(e) => CommandOutput::error(format!("tr: {e}"), 1),
+        }
+    }
+}
+
+/// Expands the ranges, classes and escapes of a set into its characters
+fn set(s: &str) -> Vec<char> {
+    let mut chars = Vec::new();
+    let mut rest = s;
+    while !rest.is_empty() {
+        if let Some((name, ranges)) = CLASSES
+            .iter()
+            .find(|(name, _)| rest.starts_with(&format!("[:{name}:]")))
+        {
+            chars.extend(ranges.iter().flat_map(|&(start, end)| start..=end));
+            rest = &rest[name.len() + 4..];
+            continue;
+        }
+
+        let (c, after) = character(rest);
+        // a range needs a character after the `-`, otherwise the `-` is taken as is
+        match after.strip_prefix('-').filter(|r| !r.is_empty()) {
+            Some(range) => {
+                let (end, after) = character(range);
+                chars.extend(c..=end);
+                rest = after;
+            },
+            None => {
+                chars.push(c);
+                rest = after;
+            },
+        }
+    }
+    chars
+}
+
+/// The first character of a non-empty set, which may be escaped, with what follows it
+fn character(s: &str) -> (char, &str) {
+    let mut chars = s.chars();
+    let c = chars.next().expect("should not be empty");
+    if c != '\\' {
+        return (c, chars.as_str());
+    }
+
+    let rest = chars.as_str();
+    let octal = rest.chars().take(3).take_while(|c| c.is_digit(8)).count();
+    if octal > 0 {
+        let n = u32::from_str_radix(&rest[..octal], 8).unwrap_or_default();
+        return (char::from(n as u8), &rest[octal..]);
+    }
+
+    let Some(e) = chars.next() else {
+        return ('\\', "");
+    };
+    let c = match e {
+        'a' => '\x07',
+        'b' => '\x08',
+        'f' => '\x0c',
+        'n' => '\n',
+        'r' => '\r',
+        't' => '\t',
+        'v' => '\x0b',
+        e => e,
+    };
+    (c, chars.as_str())
+}
+
+/// Replaces the characters of `text` that are in `from` with those at the same place in `to`,
+/// or deletes them without `to`
+fn tr(text: &str, from: &[char], to: Option<&[char]>) -> String {
+    let Some(to) = to else {
+        return text.chars().filter(|c| !from.contains(c)).collect();
+    };
+
+    // the last character of `to` stands in for any missing ones,
+    // and a character repeated in `from` is replaced with the last one it lines up with
+    let map = from
+        .iter()
+        .enumerate()
+        .map(|(i, &c)| (c, to[i.min(to.len() - 1)]))
+        .collect::<HashMap<_, _>>();
+    text.chars().map(|c| *map.get(&c).unwrap_or(&c)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case::chars("abc", "abc")]
+    #[case::range("a-e", "abcde")]
+    #[case::class("[:digit:]", "0123456789")]
+    #[case::escapes(r"\n\t\\", "\n\t\\")]
+    #[case::octal(r"\101", "A")]
+    #[case::escaped_dash(r"a\-z", "a-z")]
+    #[case::trailing_dash("a-", "a-")]
+    #[case::leading_dash("-a", "-a")]
+    fn test_set(#[case] s: &str, #[case] expected: &str) {
+        assert_eq!(set(s).into_iter().collect::<String>(), expected);
+    }
+
+    // the expected output is that of GNU tr
+    #[rstest]
+    #[case::upper("Hello\n", "a-z", Some("A-Z"), "HELLO\n")]
+    #[case::classes("hello\n", "[:lower:]", Some("[:upper:]"), "HELLO\n")]
+    #[case::extends_last("hello\n", "lo", Some("x"), "hexxx\n")]
+    #[case::newlines("a\nb\n", r"\n", Some(" "), "a b ")]
+    #[case::delete("hello\n", "l", None, "heo\n")]
+    fn test_tr(
+        #[case] text: &str,
+        #[case] from: &str,
+        #[case] to: Option<&str>,
+        #[case] expected: &str,
+    ) {
+        let to = to.map(set);
+        assert_eq!(tr(text, &set(from), to.as_deref()), expected);
+    }
+
+    #[rstest]
+    #[case::missing(&[], "tr: missing operand")]
+    #[case::missing_set2(&["h"], "tr: missing operand after 'h'")]
+    #[case::empty_set2(&["a", ""], "tr: when not truncating set1, string2 must be non-empty")]
+    #[case::extra(&["a", "b", "c"], "tr: extra operand 'c'")]
+    fn test_run_error(#[case] args: &[&str], #[case] expected: &str) {
+        let args = args.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            Tr::run(args, Process::new()),
+            CommandOutput::error(expected, 1)
+        );
+    }
+}
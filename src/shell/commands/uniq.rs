@@ -0,0 +1,66 @@
+use super::{Command, getopt, input, printed, unlines};
+use crate::shell::output::CommandOutput;
+use crate::shell::process::Process;
+
+pub struct Uniq;
+
+impl Command for Uniq {
+    const NAME: &'static str = "uniq";
+    const DESCRIPTION: &'static str = "leave out repeated lines";
+    const USAGE: &'static str = "\t\
+    uniq [file]...     print the lines of the files, or of what's piped in,
+                       leaving out those equal to the line before them
+    uniq -c [file]...  print how many times each line is repeated before it
+
+    Only adjacent lines are compared, so the lines are often sorted first, as in `sort | uniq -c`";
+
+    fn run(args: Vec<String>, process: Process) -> CommandOutput {
+        let (flags, files) = match getopt(args, "c", Self::USAGE) {
+            Ok(parsed) => parsed,
+            Err(output) => return output,
+        };
+        let count = !flags.is_empty();
+
+        let (text, errors) = input(Self::NAME, &files, process);
+        let lines = text.lines().collect::<Vec<_>>();
+        let lines = uniq(&lines).into_iter().map(|(n, line)| match count {
+            true => format!("{n:>7} {line}"),
+            false => line.to_owned(),
+        });
+        printed(&unlines(lines), errors)
+    }
+}
+
+/// Each run of equal lines as one, with the number of lines in it
+fn uniq<'a>(lines: &[&'a str]) -> Vec<(usize, &'a str)> {
+    let mut runs: Vec<(usize, &str)> = Vec::new();
+    for &line in lines {
+        match runs.last_mut() {
+            Some((n, last)) if *last == line => *n += 1,
+            _ => runs.push((1, line)),
+        }
+    }
+    runs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_uniq() {
+        assert_eq!(uniq(&["a", "a", "b", "a"]), [(2, "a"), (1, "b"), (1, "a")]);
+        assert_eq!(uniq(&[]), []);
+    }
+
+    #[test]
+    fn test_run_count() {
+        let process = Process::new();
+        process.pipe(Some("a\na\nb\n".to_owned()));
+
+        assert_eq!(
+            Uniq::run(vec!["-c".to_owned()], process),
+            CommandOutput::text("      2 a\n      1 b")
+        );
+    }
+}
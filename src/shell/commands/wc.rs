@@ -0,0 +1,178 @@
+use std::slice;
+
+use super::{Command, getopt, input, printed, unlines};
+use crate::shell::output::CommandOutput;
+use crate::shell::process::Process;
+
+pub struct Wc;
+
+impl Command for Wc {
+    const NAME: &'static str = "wc";
+    const DESCRIPTION: &'static str = "count lines, words and bytes";
+    const USAGE: &'static str = "\t\
+    wc [file]...     count the lines, words and bytes of the files, or of what's piped in
+    wc -l [file]...  count the lines
+    wc -w [file]...  count the words
+    wc -c [file]...  count the bytes
+
+    Flags combine, as in -lw, and the counts are always in that order";
+
+    fn run(args: Vec<String>, process: Process) -> CommandOutput {
+        let (flags, files) = match getopt(args, "lwc", Self::USAGE) {
+            Ok(parsed) => parsed,
+            Err(output) => return output,
+        };
+        let mut shown = Shown {
+            lines: flags.iter().any(|(c, _)| *c == 'l'),
+            words: flags.iter().any(|(c, _)| *c == 'w'),
+            bytes: flags.iter().any(|(c, _)| *c == 'c'),
+        };
+        if flags.is_empty() {
+            shown = Shown {
+                lines: true,
+                words: true,
+                bytes: true,
+            };
+        }
+
+        if files.is_empty() {
+            let (text, errors) = input(Self::NAME, &files, process);
+            return printed(&unlines(table(&[(Counts::of(&text), None)], shown)), errors);
+        }
+
+        let mut rows = Vec::new();
+        let mut errors = Vec::new();
+        for file in &files {
+            let (text, failed) = input(Self::NAME, slice::from_ref(file), process);
+            if failed.is_empty() {
+                rows.push((Counts::of(&text), Some(file.as_str())));
+            }
+            errors.extend(failed);
+        }
+        if rows.len() > 1 {
+            let total = rows
+                .iter()
+                .fold(Counts::default(), |total, (c, _)| total + *c);
+            rows.push((total, Some("total")));
+        }
+        printed(&unlines(table(&rows, shown)), errors)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct Counts {
+    lines: usize,
+    words: usize,
+    bytes: usize,
+}
+
+impl Counts {
+    fn of(text: &str) -> Self {
+        Self {
+            lines: text.matches('\n').count(),
+            words: text.split_whitespace().count(),
+            bytes: text.len(),
+        }
+    }
+}
+
+impl std::ops::Add for Counts {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            lines: self.lines + other.lines,
+            words: self.words + other.words,
+            bytes: self.bytes + other.bytes,
+        }
+    }
+}
+
+/// Which counts are shown
+#[derive(Debug, Clone, Copy)]
+struct Shown {
+    lines: bool,
+    words: bool,
+    bytes: bool,
+}
+
+/// Lines of counts, each followed by the name of its file if there is one.
+/// The counts are aligned to the right, unless there's only one of them.
+fn table(rows: &[(Counts, Option<&str>)], shown: Shown) -> Vec<String> {
+    let values = |c: &Counts| {
+        [
+            (shown.lines, c.lines),
+            (shown.words, c.words),
+            (shown.bytes, c.bytes),
+        ]
+        .into_iter()
+        .filter_map(|(shown, n)| shown.then_some(n))
+        .collect::<Vec<_>>()
+    };
+    let width = rows
+        .iter()
+        .flat_map(|(c, _)| values(c))
+        .map(|n| n.to_string().len())
+        .max()
+        .unwrap_or_default();
+
+    rows.iter()
+        .map(|(counts, name)| {
+            let mut fields = values(counts)
+                .into_iter()
+                .map(|n| format!("{n:>width$}"))
+                .collect::<Vec<_>>();
+            fields.extend(name.map(ToOwned::to_owned));
+            fields.join(" ")
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    const ALL: Shown = Shown {
+        lines: true,
+        words: true,
+        bytes: true,
+    };
+
+    #[rstest]
+    #[case::empty("", Counts { lines: 0, words: 0, bytes: 0 })]
+    #[case::lines("a b\nc\n", Counts { lines: 2, words: 3, bytes: 6 })]
+    #[case::unterminated("a", Counts { lines: 0, words: 1, bytes: 1 })]
+    #[case::spaces("  a \t b  \n", Counts { lines: 1, words: 2, bytes: 10 })]
+    #[case::multibyte("日本\n", Counts { lines: 1, words: 1, bytes: 7 })]
+    fn test_counts(#[case] text: &str, #[case] expected: Counts) {
+        assert_eq!(Counts::of(text), expected);
+    }
+
+    #[test]
+    fn test_table() {
+        let a = Counts::of("one two\nthree\n");
+        let b = Counts::of("four\n");
+        let rows = [
+            (a, Some("a.txt")),
+            (b, Some("b.txt")),
+            (a + b, Some("total")),
+        ];
+
+        assert_eq!(
+            table(&rows, ALL),
+            [" 2  3 14 a.txt", " 1  1  5 b.txt", " 3  4 19 total"]
+        );
+    }
+
+    #[test]
+    fn test_table_single() {
+        let shown = Shown {
+            lines: true,
+            words: false,
+            bytes: false,
+        };
+        assert_eq!(table(&[(Counts::of("a\nb\n"), None)], shown), ["2"]);
+    }
+}
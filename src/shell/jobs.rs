@@ -0,0 +1,297 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use leptos::prelude::*;
+use leptos::tachys::view::any_view::AnyViewState;
+use leptos::tachys::view::{Mountable, Render};
+use web_sys::Element;
+
+use super::dispatch;
+use super::process::Process;
+use crate::stores::history::{History, use_history};
+
+/// A command started in the background with `&`
+#[derive(Debug, Clone)]
+pub struct Job {
+    pub number: usize,
+    /// The command line, without the trailing `&`
+    pub input: String,
+    pub status: Status,
+    pub process: Process,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Running,
+    Done,
+    /// Done, but failed with the given status
+    Exit(u8),
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Running => write!(f, "Running"),
+            Self::Done => write!(f, "Done"),
+            Self::Exit(status) => write!(f, "Exit {status}"),
+        }
+    }
+}
+
+/// The output of a job, rendered off-screen until it's brought to the foreground
+pub struct Output {
+    /// Owns the reactive state of the command, so it outlives the entry that started it
+    owner: Owner,
+    pub container: Element,
+    state: AnyViewState,
+}
+
+impl Output {
+    /// Takes the output down and stops the command's effects
+    pub fn dispose(mut self) {
+        self.state.unmount();
+        self.owner.cleanup();
+    }
+}
+
+/// How a job is referred to in `fg` and `kill`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JobSpec {
+    /// `%%`, `%+` or no argument at all
+    Current,
+    /// `%-`
+    Previous,
+    /// `%n`
+    Number(usize),
+}
+
+impl JobSpec {
+    pub fn parse(arg: Option<&str>) -> Option<Self> {
+        match arg {
+            None | Some("%%" | "%+") => Some(Self::Current),
+            Some("%-") => Some(Self::Previous),
+            Some(arg) => arg.strip_prefix('%')?.parse().ok().map(Self::Number),
+        }
+    }
+}
+
+/// Table of background jobs, shared by the shell and the job control commands
+#[derive(Clone, Copy)]
+pub struct Jobs {
+    /// Jobs ordered by number
+    table: RwSignal<Vec<Job>>,
+    outputs: StoredValue<HashMap<usize, Output>, LocalStorage>,
+    /// Owner of the shell, which jobs are children of
+    owner: StoredValue<Owner>,
+    set_history: WriteSignal<History>,
+}
+
+impl Jobs {
+    /// Runs `input` in the background and returns its job number
+    pub fn spawn(&self, input: String) -> usize {
+        let number = self.table.with_untracked(|table| next_number(table));
+        let owner = self.owner.with_value(|owner| owner.with(Owner::new));
+
+        let jobs = *self;
+        let (process, state) = owner.with(|| {
+            let process = Process::new();
+            let state = dispatch(input.clone(), process).into_any().build();
+            // building the output runs the command, so it's pending by now if it's still loading
+            Effect::new(move |_| {
+                if !process.pending.get() {
+                    jobs.finish(number);
+                }
+            });
+            (process, state)
+        });
+        let container = document()
+            .create_element("div")
+            .expect("should create element");
+        let mut output = Output {
+            owner,
+            container,
+            state,
+        };
+        output.state.mount(&output.container, None);
+        self.outputs.update_value(|outputs| {
+            outputs.insert(number, output);
+        });
+
+        self.table.update(|table| {
+            table.push(Job {
+                number,
+                input,
+                status: Status::Running,
+                process,
+            })
+        });
+
+        number
+    }
+
+    /// Marks a running job as done, with the status it exited with, and announces it
+    fn finish(&self, number: usize) {
+        let notice = self.table.try_update(|table| {
+            let marker = marker(table, number);
+            let job = table
+                .iter_mut()
+                .find(|j| j.number == number && j.status == Status::Running)?;
+            job.status = match job.process.status() {
+                0 => Status::Done,
+                status => Status::Exit(status),
+            };
+            Some(format!("[{number}]{marker} {} {}", job.status, job.input))
+        });
+        if let Some(notice) = notice.flatten() {
+            self.set_history.update(|history| history.notify(notice));
+        }
+    }
+
+    /// Every job with its marker
+    pub fn list(&self) -> Vec<(Job, char)> {
+        self.table.with(|table| {
+            table
+                .iter()
+                .map(|job| (job.clone(), marker(table, job.number)))
+                .collect()
+        })
+    }
+
+    /// Finds the job referred to by `spec`
+    pub fn find(&self, spec: JobSpec) -> Option<(Job, char)> {
+        self.table.with_untracked(|table| {
+            let job = match spec {
+                JobSpec::Current => table.last(),
+                JobSpec::Previous => table.iter().rev().nth(1),
+                JobSpec::Number(n) => table.iter().find(|j| j.number == n),
+            }?;
+            Some((job.clone(), marker(table, job.number)))
+        })
+    }
+
+    /// Removes a job from the table, handing over its output
+    pub fn take(&self, number: usize) -> Option<(Job, Output)> {
+        let job = self.table.try_update(|table| {
+            let i = table.iter().position(|j| j.number == number)?;
+            Some(table.remove(i))
+        })??;
+        let output = self
+            .outputs
+            .try_update_value(|outputs| outputs.remove(&number))??;
+        Some((job, output))
+    }
+
+    /// Forgets the jobs that are done, once they've been reported, as bash does
+    pub fn reap(&self) {
+        let done = self.table.with_untracked(|table| {
+            table
+                .iter()
+                .filter(|j| j.status != Status::Running)
+                .map(|j| j.number)
+                .collect::<Vec<_>>()
+        });
+        for number in done {
+            if let Some((_, output)) = self.take(number) {
+                output.dispose();
+            }
+        }
+    }
+
+    /// Cancels a job and throws its output away
+    pub fn kill(&self, number: usize) -> Option<Job> {
+        let (job, output) = self.take(number)?;
+        job.process.interrupt();
+        output.dispose();
+        Some(job)
+    }
+}
+
+/// Numbers start at 1 and count up from the highest one in use,
+/// so the newest job always has the highest number
+fn next_number(table: &[Job]) -> usize {
+    table.iter().map(|j| j.number).max().unwrap_or(0) + 1
+}
+
+/// `+` marks the current job, i.e. the newest one, and `-` the one before it
+fn marker(table: &[Job], number: usize) -> char {
+    let mut numbers = table.iter().rev().map(|j| j.number);
+    match (numbers.next(), numbers.next()) {
+        (Some(n), _) if n == number => '+',
+        (_, Some(n)) if n == number => '-',
+        _ => ' ',
+    }
+}
+
+/// Creates the job table and provides it as context to the component tree.
+/// It should be called only once in the root component, after the history store.
+pub fn create_jobs() -> Jobs {
+    let (_history, set_history) = use_history().expect("not yet created");
+    let jobs = Jobs {
+        table: RwSignal::new(Vec::new()),
+        outputs: StoredValue::new_local(HashMap::new()),
+        owner: StoredValue::new(Owner::current().expect("should be in a component")),
+        set_history,
+    };
+    provide_context(jobs);
+    jobs
+}
+
+/// Retrieves the job table from the component tree context.
+/// Returns `None` if no job table has been created.
+pub fn use_jobs() -> Option<Jobs> {
+    use_context::<Jobs>()
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    fn table(numbers: &[usize]) -> Vec<Job> {
+        numbers
+            .iter()
+            .map(|&number| Job {
+                number,
+                input: "projects".to_owned(),
+                status: Status::Running,
+                process: Process::new(),
+            })
+            .collect()
+    }
+
+    #[rstest]
+    #[case::none(None, Some(JobSpec::Current))]
+    #[case::percent(Some("%%"), Some(JobSpec::Current))]
+    #[case::plus(Some("%+"), Some(JobSpec::Current))]
+    #[case::minus(Some("%-"), Some(JobSpec::Previous))]
+    #[case::number(Some("%2"), Some(JobSpec::Number(2)))]
+    #[case::missing_percent(Some("2"), None)]
+    #[case::not_a_number(Some("%a"), None)]
+    fn test_job_spec(#[case] arg: Option<&str>, #[case] expected: Option<JobSpec>) {
+        assert_eq!(JobSpec::parse(arg), expected);
+    }
+
+    #[rstest]
+    #[case::running(Status::Running, "Running")]
+    #[case::done(Status::Done, "Done")]
+    #[case::exit(Status::Exit(127), "Exit 127")]
+    fn test_status(#[case] status: Status, #[case] expected: &str) {
+        assert_eq!(status.to_string(), expected);
+    }
+
+    #[rstest]
+    #[case::empty(&[], 1)]
+    #[case::after_highest(&[1, 3], 4)]
+    fn test_next_number(#[case] numbers: &[usize], #[case] expected: usize) {
+        assert_eq!(next_number(&table(numbers)), expected);
+    }
+
+    #[rstest]
+    #[case::current(3, '+')]
+    #[case::previous(2, '-')]
+    #[case::other(1, ' ')]
+    fn test_marker(#[case] number: usize, #[case] expected: char) {
+        assert_eq!(marker(&table(&[1, 2, 3]), number), expected);
+    }
+}
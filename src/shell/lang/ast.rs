@@ -0,0 +1,81 @@
+/// A sequence of commands, separated by `;` or newlines
+pub type List = Vec<AndOr>;
+
+/// Commands chained with `&&` and `||`, which run left to right,
+/// each depending on the status of the one before it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AndOr {
+    pub first: Command,
+    pub rest: Vec<(Connector, Command)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Connector {
+    /// `&&`, runs the next command if the previous one succeeded
+    And,
+    /// `||`, runs the next command if the previous one failed
+    Or,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    /// `a=1 name arg...`
+    Simple(Simple),
+    /// `if list; then list; [elif list; then list;]... [else list;] fi`
+    If {
+        /// Each condition with the commands run if it succeeds
+        branches: Vec<(List, List)>,
+        otherwise: Option<List>,
+    },
+    /// `for name [in word...]; do list; done`, which loops over the arguments without `in`
+    For {
+        var: String,
+        words: Option<Vec<Word>>,
+        body: List,
+    },
+    /// `while list; do list; done`, or `until` when `until` is set
+    While { cond: List, body: List, until: bool },
+    /// `{ list; }`
+    Group(List),
+    /// `! command`, which inverts the status of the command
+    Not(Box<Command>),
+    /// `command | command...`, where each command reads the output of the one before it
+    Pipeline(Vec<Command>),
+    /// `name() command` or `function name command`
+    Function { name: String, body: Box<Command> },
+}
+
+/// A command with its arguments, and variables assigned before it
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Simple {
+    pub assignments: Vec<(String, Word)>,
+    pub words: Vec<Word>,
+}
+
+/// A word, made of parts that are expanded and joined together
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Word(pub Vec<Part>);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Part {
+    /// Text outside of quotes
+    Literal(String),
+    /// Text inside single or double quotes
+    Quoted(String),
+    /// `$name` or `${name}`, which is split into fields outside of double quotes
+    Var { name: String, quoted: bool },
+    /// `$(list)`, which is replaced by the output of `list`,
+    /// split into fields outside of double quotes
+    Subst { list: List, quoted: bool },
+}
+
+impl Word {
+    /// The text of the word if it's plain, i.e. without quotes or expansions.
+    /// Only plain words can be reserved words, like `if` or `done`.
+    pub fn as_plain(&self) -> Option<&str> {
+        match self.0.as_slice() {
+            [Part::Literal(s)] => Some(s),
+            _ => None,
+        }
+    }
+}
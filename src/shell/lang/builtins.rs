@@ -0,0 +1,72 @@
+/// Commands run by the interpreter itself rather than looked up in the palette
+pub const BUILTINS: [&str; 4] = ["true", "false", "test", "["];
+
+/// Evaluates the arguments of `test`, or of `[` without the closing `]`.
+/// Supports string and integer comparisons, `-n`, `-z` and `!`.
+pub fn test(args: &[&str]) -> Result<bool, String> {
+    match args {
+        [] => Ok(false),
+        [s] => Ok(!s.is_empty()),
+        [a, "=" | "==", b] => Ok(a == b),
+        [a, "!=", b] => Ok(a != b),
+        [a, op @ ("-eq" | "-ne" | "-lt" | "-le" | "-gt" | "-ge"), b] => {
+            let (a, b) = (integer(a)?, integer(b)?);
+            Ok(match *op {
+                "-eq" => a == b,
+                "-ne" => a != b,
+                "-lt" => a < b,
+                "-le" => a <= b,
+                "-gt" => a > b,
+                _ => a >= b,
+            })
+        },
+        // a binary operator takes precedence, so `! = y` compares `!` with `y`
+        ["!", rest @ ..] => test(rest).map(|b| !b),
+        ["-n", s] => Ok(!s.is_empty()),
+        ["-z", s] => Ok(s.is_empty()),
+        [op, _] => Err(format!("{op}: unary operator expected")),
+        [_, op, _] => Err(format!("{op}: binary operator expected")),
+        _ => Err("too many arguments".to_owned()),
+    }
+}
+
+fn integer(s: &str) -> Result<i64, String> {
+    s.trim()
+        .parse()
+        .map_err(|_| format!("{s}: integer expression expected"))
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case::empty(&[], false)]
+    #[case::string(&["a"], true)]
+    #[case::empty_string(&[""], false)]
+    #[case::not(&["!", "a"], false)]
+    #[case::not_unary(&["!", "-z", "a"], true)]
+    #[case::bang_compared(&["!", "=", "y"], false)]
+    #[case::bang_not_equal(&["!", "!=", "y"], true)]
+    #[case::non_zero(&["-n", "a"], true)]
+    #[case::zero(&["-z", "a"], false)]
+    #[case::equal(&["a", "=", "a"], true)]
+    #[case::not_equal(&["a", "!=", "a"], false)]
+    #[case::eq(&["10", "-eq", "10"], true)]
+    #[case::lt(&["9", "-lt", "10"], true)]
+    #[case::ge(&["9", "-ge", "10"], false)]
+    fn test_test(#[case] args: &[&str], #[case] expected: bool) {
+        assert_eq!(test(args), Ok(expected));
+    }
+
+    #[rstest]
+    #[case::not_integer(&["a", "-lt", "1"], "a: integer expression expected")]
+    #[case::unary(&["-x", "a"], "-x: unary operator expected")]
+    #[case::binary(&["a", "-x", "b"], "-x: binary operator expected")]
+    #[case::too_many(&["a", "b", "c", "d"], "too many arguments")]
+    fn test_test_error(#[case] args: &[&str], #[case] expected: &str) {
+        assert_eq!(test(args), Err(expected.to_owned()));
+    }
+}
@@ -0,0 +1,792 @@
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+
+use super::ast::{AndOr, Command, Connector, List, Part, Simple, Word};
+use super::builtins;
+
+/// Most loop iterations a single line may run, so a visitor can't hang the tab
+pub const MAX_ITERATIONS: usize = 10_000;
+/// Most functions and command substitutions that may be run within one another
+pub const MAX_DEPTH: usize = 100;
+/// Characters unquoted expansions are split on, bash's default `IFS`
+const IFS: [char; 3] = [' ', '\t', '\n'];
+
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + 'a>>;
+
+/// Exit status of a command, which isn't known until its output has loaded
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Exited(u8),
+    /// The command run last is still loading, see [`Runner::wait`]
+    Loading,
+}
+
+/// Runs the commands the interpreter doesn't handle itself
+pub trait Runner {
+    /// Runs the command `name` and returns its exit status, if it's known already.
+    /// `stdin` is the text piped into it, if any.
+    fn run(&mut self, name: &str, args: Vec<String>, stdin: Option<String>) -> Status;
+
+    /// Waits for the command run last to load and returns its exit status
+    fn wait(&mut self) -> BoxFuture<'_, u8>;
+
+    /// Reports an error of the interpreter, e.g. a bad `test` expression
+    fn error(&mut self, msg: String);
+
+    /// Runs a subshell to completion and captures what it prints,
+    /// for a command substitution or a command piped into another
+    fn capture(&mut self, subshell: Subshell) -> BoxFuture<'static, Captured>;
+}
+
+/// What a subshell printed, along with its exit status
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Captured {
+    /// Text output, which is substituted or piped
+    pub stdout: String,
+    /// Error messages, which are shown rather than captured
+    pub stderr: Vec<String>,
+    pub status: u8,
+}
+
+/// Shell state that outlives a single line: variables, functions and the last status
+#[derive(Debug, Clone, Default)]
+pub struct Env {
+    vars: HashMap<String, String>,
+    functions: HashMap<String, Command>,
+    status: u8,
+}
+
+impl Env {
+    pub fn var(&self, name: &str) -> Option<&str> {
+        self.vars.get(name).map(String::as_str)
+    }
+
+    pub fn is_function(&self, name: &str) -> bool {
+        self.functions.contains_key(name)
+    }
+
+    /// Sets `$?`, for a line that ended before its last command had loaded
+    pub fn set_status(&mut self, status: u8) {
+        self.status = status;
+    }
+}
+
+/// Stops the whole line, as opposed to a failing command which only sets the status
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Abort {
+    TooManyIterations,
+    TooDeep,
+}
+
+impl fmt::Display for Abort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooManyIterations => {
+                write!(f, "loop stopped after {MAX_ITERATIONS} iterations")
+            },
+            Self::TooDeep => write!(f, "maximum function nesting level ({MAX_DEPTH}) exceeded"),
+        }
+    }
+}
+
+/// A command substitution, or a command piped into another, which runs in a copy of the shell
+/// so its assignments don't leak into the line around it
+pub struct Subshell {
+    list: List,
+    env: Env,
+    args: Vec<String>,
+    stdin: Option<String>,
+    /// Loop iterations of the whole line, which the subshell counts towards
+    iterations: Rc<Cell<usize>>,
+    depth: usize,
+}
+
+impl Subshell {
+    /// Runs the substitution and returns its exit status once it has loaded
+    pub async fn run<R: Runner>(mut self, runner: &mut R) -> u8 {
+        let mut interpreter = Interpreter {
+            status: Status::Exited(self.env.status),
+            env: &mut self.env,
+            runner,
+            args: self.args,
+            stdin: self.stdin,
+            iterations: self.iterations,
+            depth: self.depth,
+            substituted: None,
+        };
+        let status = interpreter.run(&self.list).await;
+        interpreter.resolve(status).await
+    }
+}
+
+pub struct Interpreter<'a, R> {
+    env: &'a mut Env,
+    runner: &'a mut R,
+    /// Positional parameters, i.e. the arguments of the function being run
+    args: Vec<String>,
+    /// Text piped into the commands being run
+    stdin: Option<String>,
+    /// Loop iterations of the line so far, shared with the subshells it runs
+    iterations: Rc<Cell<usize>>,
+    depth: usize,
+    /// Status of the last command substitution, which is the status of
+    /// a command made only of assignments
+    substituted: Option<u8>,
+    /// Status of the last command, i.e. `$?`
+    status: Status,
+}
+
+impl<'a, R: Runner> Interpreter<'a, R> {
+    pub fn new(env: &'a mut Env, runner: &'a mut R) -> Self {
+        Self {
+            status: Status::Exited(env.status),
+            env,
+            runner,
+            args: Vec::new(),
+            stdin: None,
+            iterations: Rc::default(),
+            depth: 0,
+            substituted: None,
+        }
+    }
+
+    /// Runs `list` and returns its exit status.
+    /// If it has to be stopped, the error is reported and the status is 1.
+    /// Commands load on their own, and are only waited on when their status is needed,
+    /// e.g. by `&&` or `if`, so the line may end before the last one has loaded.
+    pub async fn run(&mut self, list: &List) -> Status {
+        let status = match self.list(list).await {
+            Ok(status) => status,
+            Err(abort) => {
+                self.runner.error(format!("wcli: {abort}"));
+                Status::Exited(1)
+            },
+        };
+        self.status = status;
+        if let Status::Exited(status) = status {
+            self.env.status = status;
+        }
+        status
+    }
+
+    /// Waits for the command run last to load if `status` is still loading
+    async fn resolve(&mut self, status: Status) -> u8 {
+        match status {
+            Status::Exited(status) => status,
+            Status::Loading => {
+                let status = self.runner.wait().await;
+                if self.status == Status::Loading {
+                    self.status = Status::Exited(status);
+                }
+                status
+            },
+        }
+    }
+
+    /// The status of the last command, once it has loaded
+    async fn last_status(&mut self) -> u8 {
+        self.resolve(self.status).await
+    }
+
+    fn list<'s>(&'s mut self, list: &'s List) -> BoxFuture<'s, Result<Status, Abort>> {
+        Box::pin(async move {
+            let mut status = Status::Exited(0);
+            for and_or in list {
+                status = self.and_or(and_or).await?;
+            }
+            Ok(status)
+        })
+    }
+
+    async fn and_or(&mut self, and_or: &AndOr) -> Result<Status, Abort> {
+        let mut status = self.command(&and_or.first).await?;
+        for (connector, command) in &and_or.rest {
+            let code = self.resolve(status).await;
+            status = Status::Exited(code);
+            let run = match connector {
+                Connector::And => code == 0,
+                Connector::Or => code != 0,
+            };
+            if run {
+                status = self.command(command).await?;
+            }
+        }
+        Ok(status)
+    }
+
+    async fn command(&mut self, command: &Command) -> Result<Status, Abort> {
+        let status = match command {
+            Command::Simple(simple) => self.simple(simple).await?,
+            Command::If {
+                branches,
+                otherwise,
+            } => {
+                let mut status = Status::Exited(0);
+                let mut taken = false;
+                for (cond, body) in branches {
+                    let cond = self.list(cond).await?;
+                    if self.resolve(cond).await == 0 {
+                        status = self.list(body).await?;
+                        taken = true;
+                        break;
+                    }
+                }
+                match otherwise {
+                    Some(otherwise) if !taken => self.list(otherwise).await?,
+                    _ => status,
+                }
+            },
+            Command::For { var, words, body } => {
+                let items = match words {
+                    Some(words) => self.expand_all(words).await?,
+                    None => self.args.clone(),
+                };
+                let mut status = Status::Exited(0);
+                for item in items {
+                    self.tick()?;
+                    self.env.vars.insert(var.clone(), item);
+                    status = self.list(body).await?;
+                }
+                status
+            },
+            Command::While { cond, body, until } => {
+                let mut status = Status::Exited(0);
+                loop {
+                    // the body has to load before the condition runs again, as in bash
+                    let last = self.resolve(status).await;
+                    status = Status::Exited(last);
+                    let cond = self.list(cond).await?;
+                    if (self.resolve(cond).await == 0) == *until {
+                        break;
+                    }
+                    self.tick()?;
+                    status = self.list(body).await?;
+                }
+                status
+            },
+            Command::Group(list) => self.list(list).await?,
+            Command::Not(command) => {
+                let status = self.boxed(command).await?;
+                match self.resolve(status).await {
+                    0 => Status::Exited(1),
+                    _ => Status::Exited(0),
+                }
+            },
+            Command::Pipeline(commands) => self.pipeline(commands).await?,
+            Command::Function { name, body } => {
+                self.env.functions.insert(name.clone(), (**body).clone());
+                Status::Exited(0)
+            },
+        };
+        self.status = status;
+        Ok(status)
+    }
+
+    /// Runs a command within another, like that of a `!`, boxed as it's recursive
+    fn boxed<'s>(&'s mut self, command: &'s Command) -> BoxFuture<'s, Result<Status, Abort>> {
+        Box::pin(self.command(command))
+    }
+
+    /// Runs each command but the last in a subshell, piping what it prints into the next.
+    /// The last one runs in the shell itself, so its output is shown,
+    /// and its status is that of the pipeline.
+    async fn pipeline(&mut self, commands: &[Command]) -> Result<Status, Abort> {
+        let Some((last, rest)) = commands.split_last() else {
+            return Ok(Status::Exited(0));
+        };
+
+        let mut stdin = self.stdin.clone();
+        for command in rest {
+            let list = vec![AndOr {
+                first: command.clone(),
+                rest: Vec::new(),
+            }];
+            stdin = Some(self.capture(list, stdin).await?.stdout);
+        }
+
+        let outer = std::mem::replace(&mut self.stdin, stdin);
+        let status = self.boxed(last).await;
+        self.stdin = outer;
+        status
+    }
+
+    /// Counts a loop iteration, stopping the line once there have been too many
+    fn tick(&mut self) -> Result<(), Abort> {
+        self.iterations.set(self.iterations.get() + 1);
+        match self.iterations.get() > MAX_ITERATIONS {
+            true => Err(Abort::TooManyIterations),
+            false => Ok(()),
+        }
+    }
+
+    async fn simple(&mut self, simple: &Simple) -> Result<Status, Abort> {
+        self.substituted = None;
+
+        // assignments outlive the command they come with, which keeps things simple
+        for (name, value) in &simple.assignments {
+            let value = self.expand(value).await?.join(" ");
+            self.env.vars.insert(name.clone(), value);
+        }
+
+        let mut words = self.expand_all(&simple.words).await?.into_iter();
+        let Some(name) = words.next() else {
+            return Ok(Status::Exited(self.substituted.unwrap_or(0)));
+        };
+        let args = words.collect::<Vec<_>>();
+
+        if let Some(body) = self.env.functions.get(&name).cloned() {
+            return self.call(body, args).await;
+        }
+
+        let status = match name.as_str() {
+            "true" => 0,
+            "false" => 1,
+            "test" => self.test(&name, &args),
+            "[" => match args.split_last() {
+                Some((last, args)) if last == "]" => self.test(&name, args),
+                _ => {
+                    self.runner.error("[: missing `]'".to_owned());
+                    2
+                },
+            },
+            _ => return Ok(self.runner.run(&name, args, self.stdin.clone())),
+        };
+        Ok(Status::Exited(status))
+    }
+
+    fn test(&mut self, name: &str, args: &[String]) -> u8 {
+        let args = args.iter().map(String::as_str).collect::<Vec<_>>();
+        match builtins::test(&args) {
+            Ok(true) => 0,
+            Ok(false) => 1,
+            Err(e) => {
+                self.runner.error(format!("{name}: {e}"));
+                2
+            },
+        }
+    }
+
+    fn call(&mut self, body: Command, args: Vec<String>) -> BoxFuture<'_, Result<Status, Abort>> {
+        Box::pin(async move {
+            if self.depth >= MAX_DEPTH {
+                return Err(Abort::TooDeep);
+            }
+
+            let caller = std::mem::replace(&mut self.args, args);
+            self.depth += 1;
+            let status = self.command(&body).await;
+            self.depth -= 1;
+            self.args = caller;
+            status
+        })
+    }
+
+    async fn expand_all(&mut self, words: &[Word]) -> Result<Vec<String>, Abort> {
+        let mut fields = Vec::new();
+        for word in words {
+            fields.extend(self.expand(word).await?);
+        }
+        Ok(fields)
+    }
+
+    /// Expands the variables and command substitutions in `word` into fields.
+    /// Expansions outside of double quotes are split on whitespace, which also ends the
+    /// field before or after them, so a word may expand to no field at all, or to several.
+    pub async fn expand(&mut self, word: &Word) -> Result<Vec<String>, Abort> {
+        let mut fields = Vec::new();
+        let mut field = String::new();
+        // whether the current field exists even if it's empty, e.g. after `""`
+        let mut started = false;
+
+        for part in &word.0 {
+            let (value, quoted) = match part {
+                Part::Literal(s) | Part::Quoted(s) => {
+                    field.push_str(s);
+                    started = true;
+                    continue;
+                },
+                Part::Var { name, .. } if name == "@" => {
+                    for (i, arg) in self.args.iter().enumerate() {
+                        if i > 0 {
+                            fields.push(std::mem::take(&mut field));
+                        }
+                        field.push_str(arg);
+                        started = true;
+                    }
+                    continue;
+                },
+                Part::Var { name, quoted } if name == "?" => {
+                    (self.last_status().await.to_string(), *quoted)
+                },
+                Part::Var { name, quoted } => (self.param(name), *quoted),
+                Part::Subst { list, quoted } => (self.substitute(list).await?, *quoted),
+            };
+
+            if quoted {
+                field.push_str(&value);
+                started = true;
+                continue;
+            }
+            if value.starts_with(IFS) && started {
+                fields.push(std::mem::take(&mut field));
+                started = false;
+            }
+            for (i, piece) in value.split(IFS).filter(|p| !p.is_empty()).enumerate() {
+                if i > 0 {
+                    fields.push(std::mem::take(&mut field));
+                }
+                field.push_str(piece);
+                started = true;
+            }
+            if value.ends_with(IFS) && started {
+                fields.push(std::mem::take(&mut field));
+                started = false;
+            }
+        }
+
+        if started {
+            fields.push(field);
+        }
+        Ok(fields)
+    }
+
+    /// Runs a command substitution and returns its output without trailing newlines
+    async fn substitute(&mut self, list: &List) -> Result<String, Abort> {
+        let captured = self.capture(list.clone(), self.stdin.clone()).await?;
+        self.substituted = Some(captured.status);
+        Ok(captured.stdout.trim_end_matches('\n').to_owned())
+    }
+
+    /// Runs `list` in a subshell and captures its output, showing its errors
+    async fn capture(&mut self, list: List, stdin: Option<String>) -> Result<Captured, Abort> {
+        if self.depth >= MAX_DEPTH {
+            return Err(Abort::TooDeep);
+        }
+
+        // the subshell starts with the status of the last command, so it has to have loaded
+        let status = self.last_status().await;
+        let mut env = self.env.clone();
+        env.status = status;
+        let subshell = Subshell {
+            list,
+            env,
+            args: self.args.clone(),
+            stdin,
+            iterations: Rc::clone(&self.iterations),
+            depth: self.depth + 1,
+        };
+        let mut captured = self.runner.capture(subshell).await;
+        for msg in std::mem::take(&mut captured.stderr) {
+            self.runner.error(msg);
+        }
+        Ok(captured)
+    }
+
+    /// The value of a variable or a parameter set by the shell other than `$?`,
+    /// empty if unset
+    fn param(&self, name: &str) -> String {
+        match name {
+            "#" => self.args.len().to_string(),
+            _ => match name.parse::<usize>() {
+                // `$0`, however many zeros it's written with
+                Ok(0) => "wcli".to_owned(),
+                Ok(n) => self.args.get(n - 1).cloned().unwrap_or_default(),
+                Err(_) => self.env.var(name).unwrap_or_default().to_owned(),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::executor::block_on;
+    use rstest::rstest;
+
+    use super::*;
+    use crate::shell::lang::parser::parse;
+
+    /// Records commands instead of running them, with what's piped into them after a `<`.
+    /// `fail` exits with 1, and `defer` loads before it does.
+    #[derive(Default)]
+    struct Recorder {
+        ran: Vec<String>,
+        errors: Vec<String>,
+        /// How many times the interpreter waited for `defer` to load
+        waits: usize,
+    }
+
+    impl Runner for Recorder {
+        fn run(&mut self, name: &str, args: Vec<String>, stdin: Option<String>) -> Status {
+            let mut line = vec![name.to_owned()];
+            line.extend(args);
+            if let Some(stdin) = stdin {
+                line.extend(["<".to_owned(), stdin.trim_end().replace('\n', ", ")]);
+            }
+            self.ran.push(line.join(" "));
+            match name {
+                "fail" => Status::Exited(1),
+                "defer" => Status::Loading,
+                _ => Status::Exited(0),
+            }
+        }
+
+        fn wait(&mut self) -> BoxFuture<'_, u8> {
+            self.waits += 1;
+            Box::pin(async { 1 })
+        }
+
+        fn error(&mut self, msg: String) {
+            self.errors.push(msg);
+        }
+
+        /// Outputs the commands run by the subshell, one per line
+        fn capture(&mut self, subshell: Subshell) -> BoxFuture<'static, Captured> {
+            Box::pin(async move {
+                let mut recorder = Recorder::default();
+                let status = subshell.run(&mut recorder).await;
+                Captured {
+                    stdout: recorder.ran.join("\n") + "\n",
+                    stderr: recorder.errors,
+                    status,
+                }
+            })
+        }
+    }
+
+    fn run_in(env: &mut Env, src: &str) -> (Status, Recorder) {
+        let list = parse(src).expect("should parse");
+        let mut recorder = Recorder::default();
+        let status = block_on(Interpreter::new(env, &mut recorder).run(&list));
+        (status, recorder)
+    }
+
+    fn run(src: &str) -> (Status, Recorder) {
+        run_in(&mut Env::default(), src)
+    }
+
+    #[rstest]
+    #[case::simple("echo a  b", &["echo a b"])]
+    #[case::sequence("echo a; echo b", &["echo a", "echo b"])]
+    #[case::and("fail && echo a; echo b && echo c", &["fail", "echo b", "echo c"])]
+    #[case::or("fail || echo a; echo b || echo c", &["fail", "echo a", "echo b"])]
+    #[case::if_then("if echo a; then echo b; else echo c; fi", &["echo a", "echo b"])]
+    #[case::if_else("if fail; then echo b; else echo c; fi", &["fail", "echo c"])]
+    #[case::elif("if fail; then echo b; elif true; then echo c; fi", &["fail", "echo c"])]
+    #[case::not("if ! fail; then echo a; fi", &["fail", "echo a"])]
+    #[case::for_loop("for x in a b c; do echo $x; done", &["echo a", "echo b", "echo c"])]
+    #[case::while_loop(
+        "i=; while [ \"$i\" != 000 ]; do i=0$i; echo $i; done",
+        &["echo 0", "echo 00", "echo 000"]
+    )]
+    #[case::until_loop("x=; until [ -n \"$x\" ]; do echo; x=1; done", &["echo"])]
+    #[case::function("greet() { echo hi $1 $#; }; greet you; greet", &["echo hi you 1", "echo hi 0"])]
+    #[case::function_args(
+        "each() { for x; do echo $x; done; }; each a 'b c'",
+        &["echo a", "echo b c"]
+    )]
+    fn test_run(#[case] src: &str, #[case] expected: &[&str]) {
+        let (_status, recorder) = run(src);
+        assert_eq!(recorder.ran, expected);
+        assert!(recorder.errors.is_empty());
+    }
+
+    #[rstest]
+    #[case::success("true", 0)]
+    #[case::failure("false", 1)]
+    #[case::last("true; false", 1)]
+    #[case::test_true("test a = a", 0)]
+    #[case::test_false("[ a = b ]", 1)]
+    #[case::test_error("[ a -lt b ]", 2)]
+    #[case::missing_bracket("[ a", 2)]
+    #[case::status("fail; [ $? -eq 1 ]", 0)]
+    fn test_status(#[case] src: &str, #[case] expected: u8) {
+        assert_eq!(run(src).0, Status::Exited(expected));
+    }
+
+    #[rstest]
+    #[case::or("defer || echo a", &["defer", "echo a"])]
+    #[case::and("defer && echo a", &["defer"])]
+    #[case::if_cond("if defer; then echo a; else echo b; fi", &["defer", "echo b"])]
+    #[case::while_cond("while defer; do echo a; done", &["defer"])]
+    #[case::not("! defer && echo a", &["defer", "echo a"])]
+    #[case::status("defer; echo $?", &["defer", "echo 1"])]
+    fn test_deferred_status(#[case] src: &str, #[case] expected: &[&str]) {
+        let (_status, recorder) = run(src);
+        assert_eq!(recorder.ran, expected);
+        assert_eq!(recorder.waits, 1);
+    }
+
+    #[test]
+    fn test_deferred_status_not_needed() {
+        let (status, recorder) = run("echo a; defer");
+        assert_eq!(status, Status::Loading);
+        assert_eq!(recorder.waits, 0);
+
+        let (status, recorder) = run("defer; echo a");
+        assert_eq!(status, Status::Exited(0));
+        assert_eq!(recorder.waits, 0);
+    }
+
+    #[test]
+    fn test_deferred_status_in_substitution() {
+        let (status, recorder) = run("x=$(defer)");
+        assert_eq!(status, Status::Exited(1));
+        // waited on by the subshell's own runner
+        assert_eq!(recorder.waits, 0);
+    }
+
+    #[test]
+    fn test_deferred_status_persists() {
+        let env = &mut Env::default();
+        run_in(env, "defer || true");
+        let (_status, recorder) = run_in(env, "echo $?");
+        assert_eq!(recorder.ran, ["echo 0"]);
+
+        run_in(env, "defer; false");
+        let (_status, recorder) = run_in(env, "echo $?");
+        assert_eq!(recorder.ran, ["echo 1"]);
+    }
+
+    #[rstest]
+    #[case::unquoted("x='a  b'; echo $x", &["echo a b"])]
+    #[case::quoted("x='a  b'; echo \"$x\"", &["echo a  b"])]
+    #[case::empty_unquoted("echo $unset", &["echo"])]
+    #[case::empty_quoted("echo \"$unset\" ''", &["echo  "])]
+    #[case::joined("x=b; echo a${x}c", &["echo abc"])]
+    #[case::leading_blank("x=' a'; echo b$x", &["echo b a"])]
+    #[case::trailing_blank("x='a '; echo ${x}b", &["echo a b"])]
+    #[case::only_blank("x=' '; echo b${x}c", &["echo b c"])]
+    #[case::blank_alone("x=' '; echo $x", &["echo"])]
+    #[case::zero("echo $0 ${00}", &["echo wcli wcli"])]
+    #[case::args("f() { echo \"$@\"; }; f 'a b' c", &["echo a b c"])]
+    fn test_expand(#[case] src: &str, #[case] expected: &[&str]) {
+        assert_eq!(run(src).1.ran, expected);
+    }
+
+    #[test]
+    fn test_expand_fields() {
+        let env = &mut Env::default();
+        run_in(env, "x='a  b'");
+        let mut recorder = Recorder::default();
+        let mut interpreter = Interpreter::new(env, &mut recorder);
+        let list = parse("echo $x\"$x\"").unwrap();
+        let Command::Simple(simple) = &list[0].first else {
+            panic!("should be a simple command");
+        };
+
+        assert_eq!(
+            block_on(interpreter.expand(&simple.words[1])),
+            Ok(vec!["a".to_owned(), "ba  b".to_owned()])
+        );
+    }
+
+    #[rstest]
+    #[case::unquoted("echo $(echo a; echo b)", &["echo echo a echo b"])]
+    #[case::quoted("echo \"$(echo a; echo b)\"", &["echo echo a\necho b"])]
+    #[case::nested("echo $(echo $(echo a))", &["echo echo echo a"])]
+    #[case::in_loop("for x in $(echo a); do echo $x; done", &["echo echo", "echo a"])]
+    #[case::args("f() { echo $(echo $1); }; f a", &["echo echo a"])]
+    #[case::empty("echo $()", &["echo"])]
+    fn test_substitute(#[case] src: &str, #[case] expected: &[&str]) {
+        assert_eq!(run(src).1.ran, expected);
+    }
+
+    #[test]
+    fn test_substitute_status() {
+        assert_eq!(run("x=$(fail)").0, Status::Exited(1));
+        assert_eq!(run("x=$(fail) && echo").1.ran, Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_substitute_runs_in_a_copy() {
+        let (_status, recorder) = run("x=1; y=$(x=2); echo $x");
+        assert_eq!(recorder.ran, ["echo 1"]);
+    }
+
+    #[rstest]
+    #[case::two("echo a | cat", &["cat < echo a"])]
+    #[case::three("echo a | cat | wc -l", &["wc -l < cat < echo a"])]
+    #[case::group("echo a | { cat; cat; }", &["cat < echo a", "cat < echo a"])]
+    #[case::function("f() { cat; }; echo a | f", &["cat < echo a"])]
+    #[case::and("echo a | cat && echo b", &["cat < echo a", "echo b"])]
+    #[case::only_piped("echo a | cat; cat", &["cat < echo a", "cat"])]
+    #[case::loop_body("for x in 1 2; do echo $x; done | wc", &["wc < echo 1, echo 2"])]
+    fn test_pipeline(#[case] src: &str, #[case] expected: &[&str]) {
+        assert_eq!(run(src).1.ran, expected);
+    }
+
+    #[test]
+    fn test_pipeline_status() {
+        assert_eq!(run("echo | fail").0, Status::Exited(1));
+        assert_eq!(run("fail | echo").0, Status::Exited(0));
+        assert_eq!(run("! echo | fail").0, Status::Exited(0));
+    }
+
+    #[test]
+    fn test_pipeline_runs_in_a_copy() {
+        let (_status, recorder) = run("x=1; x=2 | echo; echo $x");
+        assert_eq!(recorder.ran, ["echo < ", "echo 1"]);
+    }
+
+    #[test]
+    fn test_pipeline_shows_errors() {
+        let (_status, recorder) = run("[ a | cat");
+        assert_eq!(recorder.errors, ["[: missing `]'"]);
+        assert_eq!(recorder.ran, ["cat < "]);
+    }
+
+    #[test]
+    fn test_env_persists() {
+        let env = &mut Env::default();
+        run_in(env, "x=1; greet() { echo hi; }");
+        let (_status, recorder) = run_in(env, "greet; echo $x");
+
+        assert_eq!(recorder.ran, ["echo hi", "echo 1"]);
+        assert!(env.is_function("greet"));
+    }
+
+    #[test]
+    fn test_iteration_cap() {
+        let (status, recorder) = run("while true; do echo; done");
+
+        assert_eq!(status, Status::Exited(1));
+        assert_eq!(recorder.ran.len(), MAX_ITERATIONS);
+        assert_eq!(
+            recorder.errors,
+            [format!(
+                "wcli: loop stopped after {MAX_ITERATIONS} iterations"
+            )]
+        );
+    }
+
+    #[test]
+    fn test_iteration_cap_in_substitution() {
+        let (status, recorder) = run("while true; do x=$(while true; do echo; done); done");
+
+        assert_eq!(status, Status::Exited(1));
+        assert!(!recorder.errors.is_empty());
+        assert!(
+            recorder
+                .errors
+                .iter()
+                .all(|e| e == &format!("wcli: loop stopped after {MAX_ITERATIONS} iterations"))
+        );
+    }
+
+    #[test]
+    fn test_depth_cap() {
+        let (status, recorder) = run("f() { f; }; f");
+
+        assert_eq!(status, Status::Exited(1));
+        assert_eq!(
+            recorder.errors,
+            [format!(
+                "wcli: maximum function nesting level ({MAX_DEPTH}) exceeded"
+            )]
+        );
+    }
+}
@@ -0,0 +1,29 @@
+use leptos::prelude::*;
+
+pub use self::interpreter::{BoxFuture, Captured, Env, Interpreter, Runner, Status, Subshell};
+pub use self::parser::{parse, parse_line};
+
+pub mod ast;
+pub mod builtins;
+pub mod interpreter;
+pub mod parser;
+
+/// Whether `word` means something to the shell language itself,
+/// i.e. it's a reserved word or a builtin
+pub fn is_keyword(word: &str) -> bool {
+    parser::is_reserved(word) || builtins::BUILTINS.contains(&word)
+}
+
+/// Creates the shell environment and provides it as context to the component tree.
+/// It should be called only once in the root component.
+pub fn create_env() -> StoredValue<Env> {
+    let env = StoredValue::new(Env::default());
+    provide_context(env);
+    env
+}
+
+/// Retrieves the shell environment from the component tree context.
+/// Returns `None` if no environment has been created.
+pub fn use_env() -> Option<StoredValue<Env>> {
+    use_context::<StoredValue<Env>>()
+}
@@ -0,0 +1,1006 @@
+use std::fmt;
+
+use super::ast::{AndOr, Command, Connector, List, Part, Simple, Word};
+
+/// Words that start or end a compound command when they appear where a command is expected
+const RESERVED: [&str; 14] = [
+    "if", "then", "elif", "else", "fi", "for", "in", "do", "done", "while", "until", "function",
+    "{", "}",
+];
+
+/// Whether `word` is reserved by the shell language
+pub fn is_reserved(word: &str) -> bool {
+    RESERVED.contains(&word) || word == "!"
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Byte offset into the input where the error was found
+    pub pos: usize,
+    pub kind: ErrorKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// A quote is never closed; the position is that of the opening quote
+    UnterminatedQuote,
+    UnexpectedToken(String),
+    /// The input ended while a command or a compound command was still expected
+    UnexpectedEof {
+        expected: Option<&'static str>,
+    },
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnterminatedQuote => write!(f, "unterminated quote"),
+            Self::UnexpectedToken(t) => write!(f, "unexpected token `{t}'"),
+            Self::UnexpectedEof {
+                expected: Some(expected),
+            } => write!(f, "unexpected end of input, expected `{expected}'"),
+            Self::UnexpectedEof { expected: None } => write!(f, "unexpected end of input"),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "syntax error: {}", self.kind)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum TokenKind {
+    Word(Word),
+    /// `;`
+    Semi,
+    Newline,
+    /// `&&`
+    And,
+    /// `||`
+    Or,
+    /// `|`
+    Pipe,
+    /// `&`
+    Amp,
+    LParen,
+    RParen,
+    Eof,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Token {
+    kind: TokenKind,
+    start: usize,
+    end: usize,
+}
+
+/// Splits the input into tokens, expanding nothing but keeping track of quotes
+struct Lexer<'a> {
+    src: &'a str,
+    pos: usize,
+}
+
+impl<'a> Lexer<'a> {
+    fn new(src: &'a str) -> Self {
+        Self { src, pos: 0 }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.src[self.pos..].chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    fn tokens(mut self) -> Result<Vec<Token>, ParseError> {
+        let mut tokens = Vec::new();
+        loop {
+            let token = self.token()?;
+            let eof = token.kind == TokenKind::Eof;
+            tokens.push(token);
+            if eof {
+                return Ok(tokens);
+            }
+        }
+    }
+
+    fn token(&mut self) -> Result<Token, ParseError> {
+        // skip blanks and comments, but not newlines, which separate commands
+        loop {
+            match self.peek() {
+                Some(c) if c != '\n' && c.is_whitespace() => {
+                    self.bump();
+                },
+                Some('#') => {
+                    while self.peek().is_some_and(|c| c != '\n') {
+                        self.bump();
+                    }
+                },
+                _ => break,
+            }
+        }
+
+        let start = self.pos;
+        let kind = match self.peek() {
+            None => TokenKind::Eof,
+            Some('\n') => {
+                self.bump();
+                TokenKind::Newline
+            },
+            Some(';') => {
+                self.bump();
+                TokenKind::Semi
+            },
+            Some('(') => {
+                self.bump();
+                TokenKind::LParen
+            },
+            Some(')') => {
+                self.bump();
+                TokenKind::RParen
+            },
+            Some('&') => {
+                self.bump();
+                match self.peek() {
+                    Some('&') => {
+                        self.bump();
+                        TokenKind::And
+                    },
+                    _ => TokenKind::Amp,
+                }
+            },
+            Some('|') => {
+                self.bump();
+                match self.peek() {
+                    Some('|') => {
+                        self.bump();
+                        TokenKind::Or
+                    },
+                    _ => TokenKind::Pipe,
+                }
+            },
+            Some(_) => TokenKind::Word(self.word()?),
+        };
+
+        Ok(Token {
+            kind,
+            start,
+            end: self.pos,
+        })
+    }
+
+    fn word(&mut self) -> Result<Word, ParseError> {
+        let mut parts = Vec::new();
+        let mut literal = String::new();
+
+        while let Some(c) = self.peek() {
+            if c.is_whitespace() || matches!(c, ';' | '&' | '|' | '(' | ')') {
+                break;
+            }
+
+            let start = self.pos;
+            self.bump();
+            match c {
+                '\\' => literal.extend(self.bump()),
+                '\'' => {
+                    flush(&mut literal, &mut parts);
+                    let end = self.src[self.pos..].find('\'').ok_or(ParseError {
+                        pos: start,
+                        kind: ErrorKind::UnterminatedQuote,
+                    })?;
+                    parts.push(Part::Quoted(self.src[self.pos..self.pos + end].to_owned()));
+                    self.pos += end + 1;
+                },
+                '"' => {
+                    flush(&mut literal, &mut parts);
+                    self.double_quoted(start, &mut parts)?;
+                },
+                '$' => match self.dollar(false)? {
+                    Some(part) => {
+                        flush(&mut literal, &mut parts);
+                        parts.push(part);
+                    },
+                    None => literal.push('$'),
+                },
+                c => literal.push(c),
+            }
+        }
+
+        flush(&mut literal, &mut parts);
+        Ok(Word(parts))
+    }
+
+    /// Reads up to the closing `"`, where only variables and a few escapes are special
+    fn double_quoted(&mut self, start: usize, parts: &mut Vec<Part>) -> Result<(), ParseError> {
+        let before = parts.len();
+        let mut text = String::new();
+        loop {
+            match self.bump() {
+                None => {
+                    return Err(ParseError {
+                        pos: start,
+                        kind: ErrorKind::UnterminatedQuote,
+                    });
+                },
+                Some('"') => break,
+                Some('\\') => match self.peek() {
+                    Some(c @ ('"' | '\\' | '$' | '`')) => {
+                        self.bump();
+                        text.push(c);
+                    },
+                    _ => text.push('\\'),
+                },
+                Some('$') => match self.dollar(true)? {
+                    Some(part) => {
+                        if !text.is_empty() {
+                            parts.push(Part::Quoted(std::mem::take(&mut text)));
+                        }
+                        parts.push(part);
+                    },
+                    None => text.push('$'),
+                },
+                Some(c) => text.push(c),
+            }
+        }
+
+        // keep the quoted text even if it's empty, as `""` is still an argument
+        if !text.is_empty() || parts.len() == before {
+            parts.push(Part::Quoted(text));
+        }
+        Ok(())
+    }
+
+    /// Reads what follows a `$`, i.e. a variable or a command substitution.
+    /// Returns `None` if there's neither, in which case the `$` is taken literally.
+    fn dollar(&mut self, quoted: bool) -> Result<Option<Part>, ParseError> {
+        if self.peek() != Some('(') {
+            return Ok(self.var().map(|name| Part::Var { name, quoted }));
+        }
+
+        self.bump();
+        let inner = self.pos;
+        let end = self.closing_paren()?;
+        let list = parse(&self.src[inner..end]).map_err(|e| ParseError {
+            pos: inner + e.pos,
+            ..e
+        })?;
+        Ok(Some(Part::Subst { list, quoted }))
+    }
+
+    /// Skips to the `)` closing a command substitution and returns its position
+    fn closing_paren(&mut self) -> Result<usize, ParseError> {
+        let mut depth = 0;
+        while let Some(c) = self.bump() {
+            match c {
+                '\\' => {
+                    self.bump();
+                },
+                '\'' => while self.bump().is_some_and(|c| c != '\'') {},
+                '"' => {
+                    while let Some(c) = self.bump() {
+                        match c {
+                            '\\' => {
+                                self.bump();
+                            },
+                            '"' => break,
+                            _ => {},
+                        }
+                    }
+                },
+                '(' => depth += 1,
+                ')' if depth == 0 => return Ok(self.pos - 1),
+                ')' => depth -= 1,
+                _ => {},
+            }
+        }
+
+        // the substitution may go on over the next lines of a script
+        Err(ParseError {
+            pos: self.src.len(),
+            kind: ErrorKind::UnexpectedEof {
+                expected: Some(")"),
+            },
+        })
+    }
+
+    /// Reads the name of a variable right after a `$`.
+    /// Returns `None` if there's none, in which case the `$` is taken literally.
+    fn var(&mut self) -> Option<String> {
+        let rest = &self.src[self.pos..];
+        let name = match rest.chars().next()? {
+            '{' => {
+                let end = rest.find('}')?;
+                let name = &rest[1..end];
+                if !is_name(name) && !is_special(name) {
+                    return None;
+                }
+                self.pos += end + 1;
+                return Some(name.to_owned());
+            },
+            c @ ('0'..='9' | '@' | '#' | '?') => &rest[..c.len_utf8()],
+            c if c == '_' || c.is_ascii_alphabetic() => {
+                let end = rest
+                    .find(|c: char| c != '_' && !c.is_ascii_alphanumeric())
+                    .unwrap_or(rest.len());
+                &rest[..end]
+            },
+            _ => return None,
+        };
+        self.pos += name.len();
+        Some(name.to_owned())
+    }
+}
+
+fn flush(literal: &mut String, parts: &mut Vec<Part>) {
+    if !literal.is_empty() {
+        parts.push(Part::Literal(std::mem::take(literal)));
+    }
+}
+
+/// Whether `s` can name a variable or a function
+pub fn is_name(s: &str) -> bool {
+    let mut chars = s.chars();
+    chars
+        .next()
+        .is_some_and(|c| c == '_' || c.is_ascii_alphabetic())
+        && chars.all(|c| c == '_' || c.is_ascii_alphanumeric())
+}
+
+/// Whether `s` names a parameter set by the shell, like `$1` or `$?`
+fn is_special(s: &str) -> bool {
+    matches!(s, "@" | "#" | "?") || (!s.is_empty() && s.chars().all(|c| c.is_ascii_digit()))
+}
+
+/// Parses a line, or a whole script, into a list of commands
+pub fn parse(src: &str) -> Result<List, ParseError> {
+    let tokens = Lexer::new(src).tokens()?;
+    let mut parser = Parser {
+        src,
+        tokens,
+        idx: 0,
+    };
+    let list = parser.list(&[])?;
+    match parser.peek().kind {
+        TokenKind::Eof => Ok(list),
+        _ => Err(parser.unexpected()),
+    }
+}
+
+/// A line typed at the prompt
+#[derive(Debug, PartialEq)]
+pub struct Line<'a> {
+    pub list: List,
+    /// The line without the `&` it ends with, if it's to run in the background
+    pub background: Option<&'a str>,
+}
+
+/// Parses a line typed at the prompt, which may end with `&` to run it in the background
+pub fn parse_line(src: &str) -> Result<Line<'_>, ParseError> {
+    let tokens = Lexer::new(src).tokens()?;
+    let mut parser = Parser {
+        src,
+        tokens,
+        idx: 0,
+    };
+    let list = parser.list(&[])?;
+    let mut background = None;
+    // only an `&` that ends the line, anywhere else it's an error
+    if parser.peek().kind == TokenKind::Amp
+        && !list.is_empty()
+        && parser.tokens[parser.idx + 1..]
+            .iter()
+            .all(|t| matches!(t.kind, TokenKind::Newline | TokenKind::Eof))
+    {
+        let amp = parser.next();
+        parser.skip_newlines();
+        background = Some(src[..amp.start].trim_end());
+    }
+    match parser.peek().kind {
+        TokenKind::Eof => Ok(Line { list, background }),
+        _ => Err(parser.unexpected()),
+    }
+}
+
+struct Parser<'a> {
+    src: &'a str,
+    tokens: Vec<Token>,
+    idx: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> &Token {
+        &self.tokens[self.idx]
+    }
+
+    fn next(&mut self) -> Token {
+        let token = self.tokens[self.idx].clone();
+        // the last token is always `Eof`, which is never consumed
+        if self.idx + 1 < self.tokens.len() {
+            self.idx += 1;
+        }
+        token
+    }
+
+    /// The peeked token if it's a plain word
+    fn peek_plain(&self) -> Option<&str> {
+        match &self.peek().kind {
+            TokenKind::Word(word) => word.as_plain(),
+            _ => None,
+        }
+    }
+
+    fn unexpected(&self) -> ParseError {
+        let token = self.peek();
+        let kind = match token.kind {
+            TokenKind::Eof => ErrorKind::UnexpectedEof { expected: None },
+            TokenKind::Newline => ErrorKind::UnexpectedToken("newline".to_owned()),
+            _ => ErrorKind::UnexpectedToken(self.src[token.start..token.end].to_owned()),
+        };
+        ParseError {
+            pos: token.start,
+            kind,
+        }
+    }
+
+    /// Consumes the reserved word `word`, or fails with what was found instead
+    fn expect(&mut self, word: &'static str) -> Result<(), ParseError> {
+        if self.peek_plain() == Some(word) {
+            self.next();
+            return Ok(());
+        }
+
+        match self.peek().kind {
+            TokenKind::Eof => Err(ParseError {
+                pos: self.peek().start,
+                kind: ErrorKind::UnexpectedEof {
+                    expected: Some(word),
+                },
+            }),
+            _ => Err(self.unexpected()),
+        }
+    }
+
+    fn skip_newlines(&mut self) {
+        while self.peek().kind == TokenKind::Newline {
+            self.next();
+        }
+    }
+
+    /// Parses commands up to one of the reserved words in `terminators`,
+    /// which is left for the caller to consume
+    fn list(&mut self, terminators: &[&str]) -> Result<List, ParseError> {
+        let mut list = Vec::new();
+        loop {
+            self.skip_newlines();
+            if self.peek().kind == TokenKind::Eof
+                || self.peek_plain().is_some_and(|w| terminators.contains(&w))
+            {
+                return Ok(list);
+            }
+
+            list.push(self.and_or()?);
+            match self.peek().kind {
+                TokenKind::Semi | TokenKind::Newline => {
+                    self.next();
+                },
+                _ => return Ok(list),
+            }
+        }
+    }
+
+    /// Parses a list that has to hold at least one command, e.g. the body of a loop
+    fn body(&mut self, terminators: &[&'static str]) -> Result<List, ParseError> {
+        let list = self.list(terminators)?;
+        if list.is_empty() {
+            return Err(match self.peek().kind {
+                TokenKind::Eof => ParseError {
+                    pos: self.peek().start,
+                    kind: ErrorKind::UnexpectedEof {
+                        expected: Some(terminators[0]),
+                    },
+                },
+                _ => self.unexpected(),
+            });
+        }
+        Ok(list)
+    }
+
+    fn and_or(&mut self) -> Result<AndOr, ParseError> {
+        let first = self.pipeline()?;
+        let mut rest = Vec::new();
+        loop {
+            let connector = match self.peek().kind {
+                TokenKind::And => Connector::And,
+                TokenKind::Or => Connector::Or,
+                _ => return Ok(AndOr { first, rest }),
+            };
+            self.next();
+            self.skip_newlines();
+            rest.push((connector, self.pipeline()?));
+        }
+    }
+
+    /// Parses commands joined by `|`, or a single command if there's no `|`
+    fn pipeline(&mut self) -> Result<Command, ParseError> {
+        let first = self.command()?;
+        if self.peek().kind != TokenKind::Pipe {
+            return Ok(first);
+        }
+
+        let mut commands = vec![first];
+        while self.peek().kind == TokenKind::Pipe {
+            self.next();
+            self.skip_newlines();
+            commands.push(self.command()?);
+        }
+        Ok(Command::Pipeline(commands))
+    }
+
+    fn command(&mut self) -> Result<Command, ParseError> {
+        match self.peek_plain() {
+            Some("if") => self.if_clause(),
+            Some("for") => self.for_clause(),
+            Some(w @ ("while" | "until")) => {
+                let until = w == "until";
+                self.next();
+                let cond = self.body(&["do"])?;
+                self.expect("do")?;
+                let body = self.body(&["done"])?;
+                self.expect("done")?;
+                Ok(Command::While { cond, body, until })
+            },
+            Some("{") => {
+                self.next();
+                let list = self.body(&["}"])?;
+                self.expect("}")?;
+                Ok(Command::Group(list))
+            },
+            // `!` inverts the status of the whole pipeline after it
+            Some("!") => {
+                self.next();
+                Ok(Command::Not(Box::new(self.pipeline()?)))
+            },
+            Some("function") => {
+                self.next();
+                let name = self.function_name()?;
+                // the parentheses are optional after `function`
+                if self.peek().kind == TokenKind::LParen {
+                    self.parens()?;
+                }
+                self.function_body(name)
+            },
+            Some(w) if RESERVED.contains(&w) => Err(self.unexpected()),
+            Some(_)
+                if self.tokens.get(self.idx + 1).map(|t| &t.kind) == Some(&TokenKind::LParen) =>
+            {
+                let name = self.function_name()?;
+                self.parens()?;
+                self.function_body(name)
+            },
+            _ => self.simple(),
+        }
+    }
+
+    fn if_clause(&mut self) -> Result<Command, ParseError> {
+        let mut branches = Vec::new();
+        let mut otherwise = None;
+
+        self.expect("if")?;
+        loop {
+            let cond = self.body(&["then"])?;
+            self.expect("then")?;
+            let body = self.body(&["elif", "else", "fi"])?;
+            branches.push((cond, body));
+
+            match self.peek_plain() {
+                Some("elif") => {
+                    self.next();
+                },
+                Some("else") => {
+                    self.next();
+                    otherwise = Some(self.body(&["fi"])?);
+                    break;
+                },
+                _ => break,
+            }
+        }
+        self.expect("fi")?;
+
+        Ok(Command::If {
+            branches,
+            otherwise,
+        })
+    }
+
+    fn for_clause(&mut self) -> Result<Command, ParseError> {
+        self.expect("for")?;
+        let var = match self.peek_plain() {
+            Some(name) if is_name(name) => name.to_owned(),
+            _ => return Err(self.unexpected()),
+        };
+        self.next();
+
+        self.skip_newlines();
+        let words = match self.peek_plain() {
+            Some("in") => {
+                self.next();
+                let mut words = Vec::new();
+                while let TokenKind::Word(word) = &self.peek().kind {
+                    words.push(word.clone());
+                    self.next();
+                }
+                match self.peek().kind {
+                    TokenKind::Semi | TokenKind::Newline => {
+                        self.next();
+                    },
+                    _ => return Err(self.unexpected()),
+                }
+                Some(words)
+            },
+            _ => {
+                if self.peek().kind == TokenKind::Semi {
+                    self.next();
+                }
+                None
+            },
+        };
+
+        self.skip_newlines();
+        self.expect("do")?;
+        let body = self.body(&["done"])?;
+        self.expect("done")?;
+
+        Ok(Command::For { var, words, body })
+    }
+
+    fn function_name(&mut self) -> Result<String, ParseError> {
+        match self.peek_plain() {
+            Some(name) if is_name(name) && !is_reserved(name) => {
+                let name = name.to_owned();
+                self.next();
+                Ok(name)
+            },
+            _ => Err(self.unexpected()),
+        }
+    }
+
+    fn parens(&mut self) -> Result<(), ParseError> {
+        for paren in [TokenKind::LParen, TokenKind::RParen] {
+            if self.peek().kind != paren {
+                return Err(self.unexpected());
+            }
+            self.next();
+        }
+        Ok(())
+    }
+
+    fn function_body(&mut self, name: String) -> Result<Command, ParseError> {
+        self.skip_newlines();
+        // the body has to be a compound command, like `{ ...; }`
+        match self.peek_plain() {
+            Some("{" | "if" | "for" | "while" | "until") => Ok(Command::Function {
+                name,
+                body: Box::new(self.command()?),
+            }),
+            _ => Err(self.unexpected()),
+        }
+    }
+
+    fn simple(&mut self) -> Result<Command, ParseError> {
+        let mut simple = Simple::default();
+        while let TokenKind::Word(word) = &self.peek().kind {
+            match assignment(word).filter(|_| simple.words.is_empty()) {
+                Some(assignment) => simple.assignments.push(assignment),
+                None => simple.words.push(word.clone()),
+            }
+            self.next();
+        }
+
+        if simple.assignments.is_empty() && simple.words.is_empty() {
+            return Err(self.unexpected());
+        }
+        Ok(Command::Simple(simple))
+    }
+}
+
+/// Splits `name=value` into the name and the value
+fn assignment(word: &Word) -> Option<(String, Word)> {
+    let Some(Part::Literal(first)) = word.0.first() else {
+        return None;
+    };
+    let (name, value) = first.split_once('=')?;
+    if !is_name(name) {
+        return None;
+    }
+
+    let mut parts = Vec::with_capacity(word.0.len());
+    if !value.is_empty() {
+        parts.push(Part::Literal(value.to_owned()));
+    }
+    parts.extend(word.0[1..].iter().cloned());
+    Some((name.to_owned(), Word(parts)))
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    fn lit(s: &str) -> Word {
+        Word(vec![Part::Literal(s.to_owned())])
+    }
+
+    fn simple(words: &[&str]) -> AndOr {
+        AndOr {
+            first: Command::Simple(Simple {
+                assignments: vec![],
+                words: words.iter().map(|w| lit(w)).collect(),
+            }),
+            rest: vec![],
+        }
+    }
+
+    fn error(src: &str) -> (usize, String) {
+        let e = parse(src).expect_err("should fail to parse");
+        (e.pos, e.kind.to_string())
+    }
+
+    #[test]
+    fn test_parse_simple() {
+        assert_eq!(
+            parse("echo  hello world").unwrap(),
+            vec![simple(&["echo", "hello", "world"])]
+        );
+    }
+
+    #[test]
+    fn test_parse_list() {
+        assert_eq!(
+            parse("echo a; echo b\necho c;").unwrap(),
+            vec![
+                simple(&["echo", "a"]),
+                simple(&["echo", "b"]),
+                simple(&["echo", "c"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_quotes() {
+        let list = parse(r#"echo 'a $b' "c $d" e\ f"#).unwrap();
+        let Command::Simple(simple) = &list[0].first else {
+            panic!("should be a simple command");
+        };
+
+        assert_eq!(
+            simple.words[1..],
+            [
+                Word(vec![Part::Quoted("a $b".to_owned())]),
+                Word(vec![
+                    Part::Quoted("c ".to_owned()),
+                    Part::Var {
+                        name: "d".to_owned(),
+                        quoted: true
+                    },
+                ]),
+                lit("e f"),
+            ]
+        );
+    }
+
+    #[rstest]
+    #[case::plain("$x", "x")]
+    #[case::braced("${x}y", "x")]
+    #[case::positional("$1", "1")]
+    #[case::status("$?", "?")]
+    fn test_parse_var(#[case] src: &str, #[case] name: &str) {
+        let list = parse(&format!("echo {src}")).unwrap();
+        let Command::Simple(simple) = &list[0].first else {
+            panic!("should be a simple command");
+        };
+
+        assert_eq!(
+            simple.words[1].0[0],
+            Part::Var {
+                name: name.to_owned(),
+                quoted: false
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_subst() {
+        let list = parse(r#"echo "a $(echo "b)" c)" $(x $(y))"#).unwrap();
+        let Command::Simple(simple) = &list[0].first else {
+            panic!("should be a simple command");
+        };
+
+        assert_eq!(
+            simple.words[1],
+            Word(vec![
+                Part::Quoted("a ".to_owned()),
+                Part::Subst {
+                    list: parse(r#"echo "b)" c"#).unwrap(),
+                    quoted: true
+                },
+            ])
+        );
+        assert_eq!(
+            simple.words[2],
+            Word(vec![Part::Subst {
+                list: parse("x $(y)").unwrap(),
+                quoted: false
+            }])
+        );
+    }
+
+    #[test]
+    fn test_parse_lone_dollar() {
+        assert_eq!(
+            parse("echo $ 5$").unwrap(),
+            vec![simple(&["echo", "$", "5$"])]
+        );
+    }
+
+    #[test]
+    fn test_parse_comment() {
+        assert_eq!(
+            parse("# nothing\necho a # b").unwrap(),
+            vec![simple(&["echo", "a"])]
+        );
+    }
+
+    #[test]
+    fn test_parse_assignment() {
+        let list = parse("a=1 b= echo c=2").unwrap();
+        let Command::Simple(simple) = &list[0].first else {
+            panic!("should be a simple command");
+        };
+
+        assert_eq!(
+            simple.assignments,
+            [("a".to_owned(), lit("1")), ("b".to_owned(), Word(vec![]))]
+        );
+        assert_eq!(simple.words, [lit("echo"), lit("c=2")]);
+    }
+
+    #[test]
+    fn test_parse_and_or() {
+        let list = parse("true && echo a || echo b").unwrap();
+        assert_eq!(list[0].rest.len(), 2);
+        assert_eq!(list[0].rest[0].0, Connector::And);
+        assert_eq!(list[0].rest[1].0, Connector::Or);
+    }
+
+    #[test]
+    fn test_parse_pipeline() {
+        let list = parse("echo a | sort -r |\n  head && true").unwrap();
+        assert_eq!(
+            list[0].first,
+            Command::Pipeline(vec![
+                simple(&["echo", "a"]).first,
+                simple(&["sort", "-r"]).first,
+                simple(&["head"]).first,
+            ])
+        );
+        assert_eq!(list[0].rest.len(), 1);
+    }
+
+    #[test]
+    fn test_parse_not_pipeline() {
+        let list = parse("! a | b").unwrap();
+        assert_eq!(
+            list[0].first,
+            Command::Not(Box::new(Command::Pipeline(vec![
+                simple(&["a"]).first,
+                simple(&["b"]).first,
+            ])))
+        );
+    }
+
+    #[test]
+    fn test_parse_if() {
+        let list = parse("if a; then b; elif c; then d; else e; fi").unwrap();
+        assert_eq!(
+            list,
+            vec![AndOr {
+                first: Command::If {
+                    branches: vec![
+                        (vec![simple(&["a"])], vec![simple(&["b"])]),
+                        (vec![simple(&["c"])], vec![simple(&["d"])]),
+                    ],
+                    otherwise: Some(vec![simple(&["e"])]),
+                },
+                rest: vec![],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_for() {
+        let list = parse("for x in a b\ndo\n  echo $x\ndone").unwrap();
+        let Command::For { var, words, body } = &list[0].first else {
+            panic!("should be a for loop");
+        };
+
+        assert_eq!(var, "x");
+        assert_eq!(words.as_deref(), Some(&[lit("a"), lit("b")][..]));
+        assert_eq!(body.len(), 1);
+    }
+
+    #[test]
+    fn test_parse_for_without_in() {
+        let list = parse("for x; do echo $x; done").unwrap();
+        assert!(matches!(&list[0].first, Command::For { words: None, .. }));
+    }
+
+    #[test]
+    fn test_parse_while() {
+        let list = parse("until false; do echo; done").unwrap();
+        assert!(matches!(&list[0].first, Command::While { until: true, .. }));
+    }
+
+    #[rstest]
+    #[case::parens("greet() { echo hi; }")]
+    #[case::keyword("function greet { echo hi; }")]
+    #[case::both("function greet() {\n  echo hi\n}")]
+    fn test_parse_function(#[case] src: &str) {
+        let list = parse(src).unwrap();
+        let Command::Function { name, body } = &list[0].first else {
+            panic!("should be a function");
+        };
+
+        assert_eq!(name, "greet");
+        assert_eq!(**body, Command::Group(vec![simple(&["echo", "hi"])]));
+    }
+
+    #[test]
+    fn test_parse_reserved_words_as_arguments() {
+        assert_eq!(
+            parse("echo if then done").unwrap(),
+            vec![simple(&["echo", "if", "then", "done"])]
+        );
+    }
+
+    #[rstest]
+    #[case::missing_fi("if a; then b", (12, "unexpected end of input, expected `fi'".to_owned()))]
+    #[case::missing_do("for x in a; echo $x; done", (12, "unexpected token `echo'".to_owned()))]
+    #[case::dangling_and("true &&", (7, "unexpected end of input".to_owned()))]
+    #[case::stray_done("echo a; done", (8, "unexpected token `done'".to_owned()))]
+    #[case::empty_body("while a; do done", (12, "unexpected token `done'".to_owned()))]
+    #[case::unterminated("echo 'a", (5, "unterminated quote".to_owned()))]
+    #[case::dangling_pipe("echo a |", (8, "unexpected end of input".to_owned()))]
+    #[case::leading_pipe("| echo", (0, "unexpected token `|'".to_owned()))]
+    #[case::leading_semi("; echo", (0, "unexpected token `;'".to_owned()))]
+    #[case::subst_error("echo $(a; fi)", (10, "unexpected token `fi'".to_owned()))]
+    #[case::unterminated_subst("echo $(a", (8, "unexpected end of input, expected `)'".to_owned()))]
+    #[case::background("echo a & echo b", (7, "unexpected token `&'".to_owned()))]
+    fn test_parse_error(#[case] src: &str, #[case] expected: (usize, String)) {
+        assert_eq!(error(src), expected);
+    }
+
+    #[rstest]
+    #[case::trailing("echo a &", Some("echo a"))]
+    #[case::trailing_newline("echo a; echo b &\n", Some("echo a; echo b"))]
+    #[case::escaped("echo a\\&", None)]
+    #[case::single_quoted("echo 'a &'", None)]
+    #[case::double_quoted("echo \"&\"", None)]
+    #[case::and("true && echo a", None)]
+    fn test_parse_line(#[case] src: &str, #[case] expected: Option<&str>) {
+        assert_eq!(parse_line(src).unwrap().background, expected);
+    }
+
+    #[rstest]
+    #[case::alone("&", (0, "unexpected token `&'".to_owned()))]
+    #[case::mid_line("echo a & echo b", (7, "unexpected token `&'".to_owned()))]
+    fn test_parse_line_error(#[case] src: &str, #[case] expected: (usize, String)) {
+        let e = parse_line(src).expect_err("should fail to parse");
+        assert_eq!((e.pos, e.kind.to_string()), expected);
+    }
+}
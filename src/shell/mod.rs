@@ -1,37 +1,307 @@
 use std::str::FromStr;
+use std::task::{Context, Poll, Waker};
 
 pub use commands::{Command, Palette};
 use leptos::prelude::*;
-use leptos::reactive::wrappers::write::SignalSetter;
+use leptos::reactive::computed::ScopedFuture;
+use leptos::task::spawn_local_scoped_with_cancellation;
+
+use self::jobs::use_jobs;
+use self::lang::parser::ParseError;
+use self::lang::{BoxFuture, Captured, Interpreter, Runner, Status, Subshell, use_env};
+use self::output::{Block, CommandOutput, Line, Span, Style};
+use self::process::Process;
 
 pub mod commands;
+pub mod jobs;
+pub mod lang;
+pub mod net;
+pub mod output;
+pub mod process;
+pub mod raw;
+pub mod script;
+pub mod width;
+
+/// Makes a command with more than text to show print plain text, e.g. `projects --plain`
+const PLAIN_FLAG: &str = "--plain";
 
-pub fn dispatch(input: String, set_pending: SignalSetter<bool>) -> impl IntoView {
+pub fn dispatch(input: String, process: Process) -> impl IntoView {
     let input = input.trim();
 
     if input.is_empty() {
         return "".into_any();
     }
 
-    let (cmd, args) = match input.split_once(' ') {
-        Some((cmd, args)) => (
-            cmd.to_owned(),
-            args.split(' ').map(|s| s.to_owned()).collect::<Vec<_>>(),
-        ),
-        None => (input.to_owned(), vec![]),
+    let list = match lang::parse_line(input) {
+        Ok(line) => match line.background {
+            Some(input) => return background(input.to_owned()).into_any(),
+            None => line.list,
+        },
+        Err(e) => return syntax_error(input, e).into_any(),
     };
 
-    match Palette::from_str(&cmd) {
-        Ok(cmd) => cmd.run(args, set_pending).into_any(),
-        Err(_) => not_found(cmd).into_any(),
+    // run on a copy, as commands may dispatch themselves, e.g. to start a job
+    let env = use_env().expect("not yet created");
+    let mut run = Box::pin(async move {
+        let mut scratch = env.get_value();
+        let mut outputs = Outputs::new(process);
+        let status = Interpreter::new(&mut scratch, &mut outputs)
+            .run(&list)
+            .await;
+        env.set_value(scratch);
+        match status {
+            Status::Exited(status) => process.exit(status),
+            // the line is done once the command run last has loaded
+            Status::Loading => outputs.on_loaded(move |status| {
+                process.exit(status);
+                env.update_value(|env| env.set_status(status));
+            }),
+        }
+        outputs.render()
+    });
+
+    // unless it waits for substitutions or statuses, the line runs to completion right away
+    let mut cx = Context::from_waker(Waker::noop());
+    if let Poll::Ready(view) = run.as_mut().poll(&mut cx) {
+        return view;
+    }
+
+    // otherwise, show the output once they're in
+    let output = StoredValue::new_local(None);
+    let ready = RwSignal::new(false);
+    process.pending.set(true);
+    untrack(|| {
+        spawn_local_scoped_with_cancellation(async move {
+            let view = run.await;
+            output.set_value(Some(view));
+            process.pending.set(false);
+            ready.set(true);
+        })
+    });
+    (move || {
+        ready
+            .get()
+            .then(|| output.try_update_value(Option::take).flatten())
+    })
+    .into_any()
+}
+
+/// Width of the screen in columns, for output rendered as plain text
+fn columns() -> usize {
+    use_env()
+        .and_then(|env| env.with_value(|env| env.var("COLUMNS")?.parse().ok()))
+        .unwrap_or(output::COLUMNS)
+}
+
+/// Runs palette commands, collecting their output
+struct Outputs {
+    process: Process,
+    outputs: Vec<CommandOutput>,
+    /// Index of the output of the command run last, if it's still loading
+    loading: Option<usize>,
+}
+
+impl Outputs {
+    fn new(process: Process) -> Self {
+        Self {
+            process,
+            outputs: Vec::new(),
+            loading: None,
+        }
+    }
+
+    /// Calls `f` with the exit status of the command run last once it has loaded
+    fn on_loaded(&mut self, f: impl FnOnce(u8) + 'static) {
+        let Some(output) = self.loading.and_then(|i| self.outputs.get_mut(i)) else {
+            return;
+        };
+        // the status is that of the deferred block to load last
+        let Some(block) = output
+            .blocks
+            .iter_mut()
+            .rev()
+            .find(|b| matches!(b, Block::Deferred(_)))
+        else {
+            return;
+        };
+        if let Block::Deferred(deferred) = std::mem::replace(block, Block::Blank) {
+            *block = Block::Deferred(deferred.map(move |output| {
+                f(output.status);
+                output
+            }));
+        }
+    }
+
+    fn render(self) -> AnyView {
+        let process = self.process;
+        self.outputs
+            .into_iter()
+            .map(|o| output::render(o, process))
+            .collect_view()
+            .into_any()
+    }
+
+    /// The output as plain text once it has all loaded, with the error messages kept apart
+    async fn text(self) -> (String, Vec<String>) {
+        let columns = columns();
+        let mut text = String::new();
+        let mut errors = Vec::new();
+        for output in self.outputs {
+            let mut blocks = Vec::new();
+            for block in output.resolve().await.blocks {
+                match block {
+                    Block::Error(msg) => errors.push(msg),
+                    block => blocks.push(block),
+                }
+            }
+            text.push_str(&output::plain(&CommandOutput::new(blocks), columns));
+        }
+        (text, errors)
+    }
+}
+
+impl Runner for Outputs {
+    fn run(&mut self, name: &str, mut args: Vec<String>, stdin: Option<String>) -> Status {
+        self.loading = None;
+        let Ok(cmd) = Palette::from_str(name) else {
+            self.outputs.push(not_found(name));
+            return Status::Exited(127);
+        };
+
+        // those with more than text to show can print plain text instead, e.g. to copy it
+        let plain = cmd.has_plain_output() && take_plain_flag(&mut args);
+
+        self.process.pipe(stdin);
+        let mut output = cmd.run(args, self.process);
+        if plain {
+            output = output::into_plain(output, columns());
+        }
+        if output.is_loading() {
+            self.loading = Some(self.outputs.len());
+            self.outputs.push(output);
+            return Status::Loading;
+        }
+        let status = output.status;
+        self.process.exit(status);
+        self.outputs.push(output);
+        Status::Exited(status)
+    }
+
+    fn wait(&mut self) -> BoxFuture<'_, u8> {
+        Box::pin(async move {
+            let Some(i) = self.loading.take() else {
+                return self.process.status();
+            };
+            let output = std::mem::take(&mut self.outputs[i]).resolve().await;
+            self.process.exit(output.status);
+            self.outputs[i] = output;
+            self.process.status()
+        })
     }
+
+    fn error(&mut self, msg: String) {
+        self.outputs.push(CommandOutput::error(msg, 1));
+    }
+
+    fn capture(&mut self, subshell: Subshell) -> BoxFuture<'static, Captured> {
+        // the commands are disposed of once their output is captured
+        let owner = Owner::new();
+        let process = self.process.child();
+        let run = owner.with(|| {
+            ScopedFuture::new(async move {
+                let mut outputs = Outputs::new(process);
+                let status = subshell.run(&mut outputs).await;
+                let (stdout, stderr) = outputs.text().await;
+                Captured {
+                    stdout,
+                    stderr,
+                    status,
+                }
+            })
+        });
+        Box::pin(async move {
+            let output = run.await;
+            owner.cleanup();
+            output
+        })
+    }
+}
+
+/// Takes `--plain` out of `args`, wherever it is before a `--` that ends the flags.
+/// None of the commands it applies to takes it as a value or an operand.
+fn take_plain_flag(args: &mut Vec<String>) -> bool {
+    let end = args.iter().position(|a| a == "--").unwrap_or(args.len());
+    let mut rest = args.split_off(end);
+    let given = args.len();
+    args.retain(|a| a != PLAIN_FLAG);
+    let plain = args.len() < given;
+    args.append(&mut rest);
+    plain
 }
 
-fn not_found(cmd: String) -> impl IntoView {
+/// Shows where in the input parsing failed
+fn syntax_error(input: &str, e: ParseError) -> impl IntoView + use<> {
+    // point at the offending line, and the column within it
+    let start = input[..e.pos].rfind('\n').map_or(0, |i| i + 1);
+    let end = input[e.pos..].find('\n').map_or(input.len(), |i| e.pos + i);
+    let column = input[start..e.pos].chars().count();
+    let caret = format!("{}^", " ".repeat(column));
+
     view! {
-        <div class="text-foreground">
-            <p>"command not found: "<span class="text-fail">{cmd}</span></p>
-            <p>"try "<span class="text-pass">help</span> " to get a list of available commands"</p>
+        <div class="text-foreground" data-testid="syntax-error">
+            <p class="text-fail">{format!("wcli: {e}")}</p>
+            <pre>{input[start..end].to_owned()}"\n"{caret}</pre>
         </div>
     }
 }
+
+/// Starts `input` as a background job
+fn background(input: String) -> impl IntoView {
+    let cmd = input.split_whitespace().next().unwrap_or_default();
+    if Palette::from_str(cmd).is_ok_and(Palette::is_foreground_only) {
+        let msg = format!("{cmd}: cannot run in the background");
+        return view! { <p class="text-fail">{msg}</p> }.into_any();
+    }
+
+    let jobs = use_jobs().expect("not yet created");
+    let number = jobs.spawn(input.clone());
+    view! { <p class="text-foreground">{format!("[{number}] {input}")}</p> }.into_any()
+}
+
+fn not_found(cmd: &str) -> CommandOutput {
+    CommandOutput::new(vec![
+        Block::Line(Line(vec![
+            Span::new("command not found: "),
+            Span::styled(cmd, Style::Fail),
+        ])),
+        Block::Line(Line(vec![
+            Span::new("try "),
+            Span::styled("help", Style::Pass),
+            Span::new(" to get a list of available commands"),
+        ])),
+    ])
+    .with_status(127)
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case::alone(&["--plain"], true, &[])]
+    #[case::among_flags(&["-j", "--plain", "--refresh"], true, &["-j", "--refresh"])]
+    #[case::after_value(&["--sort", "stars", "--plain"], true, &["--sort", "stars"])]
+    #[case::after_filter(&["--lang", "rust", "--plain"], true, &["--lang", "rust"])]
+    #[case::after_operand(&["wcli", "--plain"], true, &["wcli"])]
+    #[case::twice(&["--plain", "wcli", "--plain"], true, &["wcli"])]
+    #[case::after_dashes(&["--", "--plain"], false, &["--", "--plain"])]
+    #[case::before_dashes(&["--plain", "--", "--plain"], true, &["--", "--plain"])]
+    #[case::none(&["-j"], false, &["-j"])]
+    fn test_take_plain_flag(#[case] args: &[&str], #[case] plain: bool, #[case] rest: &[&str]) {
+        let mut args = args.iter().map(ToString::to_string).collect::<Vec<_>>();
+        assert_eq!(take_plain_flag(&mut args), plain);
+        assert_eq!(args, rest);
+    }
+}
@@ -0,0 +1,422 @@
+use std::fmt;
+
+use gloo_net::http::{Request, Response};
+use web_sys::AbortSignal;
+use web_sys::js_sys::{Array, Date};
+
+use crate::shell::process::CancelToken;
+use crate::stores::cache::{self, Entry, Kind};
+
+/// Milliseconds a request is given before it times out
+const TIMEOUT: u32 = 5000;
+
+/// Why a request failed, told apart so each can be explained
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FetchError {
+    /// No response came within `TIMEOUT`
+    Timeout {
+        url: String,
+    },
+    /// The command was interrupted before a response came
+    Cancelled {
+        url: String,
+    },
+    /// The request couldn't be sent or answered, e.g. when offline
+    Network {
+        url: String,
+        reason: String,
+    },
+    /// Too many requests were made, like GitHub's 60 an hour without signing in.
+    /// `resets_in` is how many seconds until more are allowed, if the server says.
+    RateLimited {
+        url: String,
+        status: u16,
+        resets_in: Option<u64>,
+    },
+    NotFound {
+        url: String,
+    },
+    /// Any other status that isn't a success
+    Status {
+        url: String,
+        status: u16,
+    },
+    /// The response isn't what was expected
+    Parse {
+        url: String,
+        reason: String,
+    },
+}
+
+impl FetchError {
+    /// An error for a response that isn't a success
+    fn from_response(url: &str, resp: &Response, now: f64) -> Self {
+        let url = url.to_owned();
+        let headers = resp.headers();
+        let remaining = headers.get("x-ratelimit-remaining");
+        match resp.status() {
+            status @ (403 | 429) if status == 429 || remaining.as_deref() == Some("0") => {
+                Self::RateLimited {
+                    url,
+                    status,
+                    resets_in: headers
+                        .get("x-ratelimit-reset")
+                        .and_then(|reset| resets_in(&reset, now)),
+                }
+            },
+            404 => Self::NotFound { url },
+            status => Self::Status { url, status },
+        }
+    }
+
+    fn from_gloo(url: &str, e: gloo_net::Error) -> Self {
+        let url = url.to_owned();
+        match e {
+            gloo_net::Error::JsError(e) if e.name == "TimeoutError" => Self::Timeout { url },
+            gloo_net::Error::JsError(e) if e.name == "AbortError" => Self::Cancelled { url },
+            e => Self::Network {
+                url,
+                reason: e.to_string(),
+            },
+        }
+    }
+
+    /// Whether it may pass by itself, so a response cached before it is better than none.
+    /// A resource that's gone, or a request that was cancelled, won't come back.
+    fn is_transient(&self) -> bool {
+        match self {
+            Self::Timeout { .. } | Self::Network { .. } | Self::RateLimited { .. } => true,
+            Self::Status { status, .. } => *status >= 500,
+            Self::Cancelled { .. } | Self::NotFound { .. } | Self::Parse { .. } => false,
+        }
+    }
+
+    /// What to do about it
+    pub fn hint(&self) -> &'static str {
+        match self {
+            Self::Timeout { .. } => "the server may be busy, try again in a moment",
+            Self::Cancelled { .. } => "run the command again to load it",
+            Self::Network { .. } => "check your connection, then try again",
+            Self::RateLimited { .. } => "try again once it resets",
+            Self::NotFound { .. } => "check the username in the config",
+            Self::Status { status, .. } if *status >= 500 => {
+                "the server is having trouble, try again later"
+            },
+            Self::Status { .. } | Self::Parse { .. } => "try again later",
+        }
+    }
+
+    /// The request and how it ended, for `--verbose`
+    pub fn details(&self) -> String {
+        match self {
+            Self::Timeout { url } => format!("GET {url}: no response after {TIMEOUT}ms"),
+            Self::Cancelled { url } => format!("GET {url}: cancelled"),
+            Self::Network { url, reason } => format!("GET {url}: {reason}"),
+            Self::RateLimited { url, status, .. } | Self::Status { url, status } => {
+                format!("GET {url}: HTTP {status}")
+            },
+            Self::NotFound { url } => format!("GET {url}: HTTP 404"),
+            Self::Parse { url, .. } => format!("GET {url}: HTTP 200"),
+        }
+    }
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Timeout { .. } => write!(f, "the request timed out"),
+            Self::Cancelled { .. } => write!(f, "the request was cancelled"),
+            Self::Network { .. } => write!(f, "the server couldn't be reached"),
+            Self::RateLimited {
+                resets_in: Some(secs),
+                ..
+            } => write!(f, "the rate limit was hit, it resets {}", after(*secs)),
+            Self::RateLimited { .. } => write!(f, "the rate limit was hit"),
+            Self::NotFound { .. } => write!(f, "not found"),
+            Self::Status { status, .. } => write!(f, "the server answered with status {status}"),
+            Self::Parse { reason, .. } => write!(f, "the response couldn't be read: {reason}"),
+        }
+    }
+}
+
+/// Seconds from `now`, in milliseconds since the epoch, until `reset`,
+/// a time in seconds since the epoch as `X-RateLimit-Reset` gives it
+fn resets_in(reset: &str, now: f64) -> Option<u64> {
+    let reset = reset.trim().parse::<u64>().ok()?;
+    let now = (now / 1000.0) as u64;
+    Some(reset.saturating_sub(now))
+}
+
+/// How long `secs` is, rounded up to minutes, as in "in 3 minutes"
+fn after(secs: u64) -> String {
+    match secs.div_ceil(60) {
+        0 => "now".to_owned(),
+        1 => "in a minute".to_owned(),
+        n => format!("in {n} minutes"),
+    }
+}
+
+/// A cached response that's used after fetching it again failed
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stale {
+    /// When the response was fetched, in milliseconds since the epoch
+    pub fetched_at: f64,
+    pub error: FetchError,
+}
+
+impl Stale {
+    /// Whether `entry` is stale, and why
+    pub fn of(entry: &Entry) -> Option<Self> {
+        let error = entry.stale.clone()?;
+        Some(Self {
+            fetched_at: entry.fetched_at,
+            error,
+        })
+    }
+}
+
+/// GETs `url` through the cache. A response fetched less than `ttl` seconds ago is reused
+/// as is, and an older one is revalidated with its ETag. If that fails in a way that may
+/// pass, e.g. a timeout, the older one is reused with `stale` telling why.
+/// With `refresh`, the cache is skipped, though it's still filled. `kind` decides which
+/// cached responses it may evict.
+pub async fn fetch(
+    url: &str,
+    kind: Kind,
+    ttl: u64,
+    cancel: CancelToken,
+    refresh: bool,
+) -> Result<Entry, FetchError> {
+    let now = Date::now();
+    let cached = cache::load(url, kind).filter(|_| !refresh);
+    if let Some(entry) = &cached
+        && entry.is_fresh(now, ttl)
+    {
+        return Ok(entry.clone());
+    }
+
+    let etag = cached.as_ref().and_then(|e| e.etag.as_deref());
+    let resp = match get(url, etag, cancel).await {
+        Ok(resp) => resp,
+        Err(e) => return stale(cached, e),
+    };
+    if let Some(entry) = &cached
+        && resp.status() == 304
+    {
+        let entry = Entry {
+            fetched_at: now,
+            ..entry.clone()
+        };
+        cache::save(url, kind, &entry);
+        return Ok(entry);
+    }
+    if !resp.ok() {
+        return stale(cached, FetchError::from_response(url, &resp, now));
+    }
+
+    let headers = resp.headers();
+    let entry = Entry {
+        body: resp
+            .text()
+            .await
+            .map_err(|e| FetchError::from_gloo(url, e))?,
+        etag: headers.get("etag"),
+        link: headers.get("link"),
+        fetched_at: now,
+        stale: None,
+    };
+    cache::save(url, kind, &entry);
+    Ok(entry)
+}
+
+/// The cached entry, marked stale, if there is one and the failure `e` may pass,
+/// e.g. when rate limited, where stale data beats none
+fn stale(cached: Option<Entry>, e: FetchError) -> Result<Entry, FetchError> {
+    match cached {
+        Some(entry) if e.is_transient() => Ok(Entry {
+            stale: Some(e),
+            ..entry
+        }),
+        _ => Err(e),
+    }
+}
+
+/// Make a GET request with a timeout, aborted early if the command is cancelled.
+/// With an `etag`, the response is 304 Not Modified if it hasn't changed.
+async fn get(url: &str, etag: Option<&str>, cancel: CancelToken) -> Result<Response, FetchError> {
+    let signals = Array::of2(
+        &AbortSignal::timeout_with_u32(TIMEOUT),
+        &cancel.abort_signal(),
+    );
+    let signal = AbortSignal::any(&signals);
+    let mut request = Request::get(url).abort_signal(Some(&signal));
+    if let Some(etag) = etag {
+        request = request.header("If-None-Match", etag);
+    }
+
+    request
+        .send()
+        .await
+        .map_err(|e| FetchError::from_gloo(url, e))
+}
+
+/// The URL of the next page in a `Link` header, which GitHub paginates lists with, as in
+/// `<https://api.github.com/user/1/repos?page=2>; rel="next", <...?page=5>; rel="last"`
+pub fn next_page(link: &str) -> Option<&str> {
+    link.split(',').find_map(|part| {
+        let (url, params) = part.trim().strip_prefix('<')?.split_once('>')?;
+        let next = params.split(';').any(|param| {
+            param.trim().strip_prefix("rel=").is_some_and(|rel| {
+                rel.trim_matches('"')
+                    .split_whitespace()
+                    .any(|r| r == "next")
+            })
+        });
+        next.then_some(url)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    const URL: &str = "https://x.test";
+
+    #[rstest]
+    #[case::first(
+        r#"<https://api.github.com/user/1/repos?per_page=100&page=2>; rel="next", <https://api.github.com/user/1/repos?per_page=100&page=3>; rel="last""#,
+        Some("https://api.github.com/user/1/repos?per_page=100&page=2")
+    )]
+    #[case::middle(
+        r#"<https://x.test/?page=1>; rel="prev", <https://x.test/?page=3>; rel="next", <https://x.test/?page=1>; rel="first""#,
+        Some("https://x.test/?page=3")
+    )]
+    #[case::last(
+        r#"<https://x.test/?page=1>; rel="first", <https://x.test/?page=2>; rel="prev""#,
+        None
+    )]
+    #[case::unquoted("<https://x.test/?page=2>; rel=next", Some("https://x.test/?page=2"))]
+    #[case::several_rels(
+        r#"<https://x.test/?page=2>; title="more"; rel="next last""#,
+        Some("https://x.test/?page=2")
+    )]
+    #[case::empty("", None)]
+    #[case::malformed("https://x.test/?page=2; rel=\"next\"", None)]
+    fn test_next_page(#[case] link: &str, #[case] expected: Option<&str>) {
+        assert_eq!(next_page(link), expected);
+    }
+
+    #[rstest]
+    #[case::later("1700000600", 1_700_000_000_000.0, Some(600))]
+    #[case::past("1700000000", 1_700_000_100_000.0, Some(0))]
+    #[case::invalid("soon", 1_700_000_000_000.0, None)]
+    fn test_resets_in(#[case] reset: &str, #[case] now: f64, #[case] expected: Option<u64>) {
+        assert_eq!(resets_in(reset, now), expected);
+    }
+
+    #[rstest]
+    #[case::reset(Some(0), "the rate limit was hit, it resets now")]
+    #[case::seconds(Some(59), "the rate limit was hit, it resets in a minute")]
+    #[case::minutes(Some(601), "the rate limit was hit, it resets in 11 minutes")]
+    #[case::unknown(None, "the rate limit was hit")]
+    fn test_rate_limited(#[case] resets_in: Option<u64>, #[case] expected: &str) {
+        let error = FetchError::RateLimited {
+            url: "https://api.github.com/users/a/repos".to_owned(),
+            status: 403,
+            resets_in,
+        };
+        assert_eq!(error.to_string(), expected);
+        assert_eq!(
+            error.details(),
+            "GET https://api.github.com/users/a/repos: HTTP 403"
+        );
+    }
+
+    #[rstest]
+    #[case::timeout(FetchError::Timeout { url: URL.to_owned() }, true)]
+    #[case::rate_limited(
+        FetchError::RateLimited { url: URL.to_owned(), status: 429, resets_in: None },
+        true
+    )]
+    #[case::server_error(FetchError::Status { url: URL.to_owned(), status: 503 }, true)]
+    #[case::cancelled(FetchError::Cancelled { url: URL.to_owned() }, false)]
+    #[case::not_found(FetchError::NotFound { url: URL.to_owned() }, false)]
+    #[case::client_error(FetchError::Status { url: URL.to_owned(), status: 400 }, false)]
+    fn test_stale(#[case] error: FetchError, #[case] reused: bool) {
+        let entry = Entry {
+            body: "[]".to_owned(),
+            etag: None,
+            link: None,
+            fetched_at: 1_000.0,
+            stale: None,
+        };
+
+        let result = stale(Some(entry), error.clone());
+        if reused {
+            let entry = result.expect("should reuse the entry");
+            assert_eq!(
+                Stale::of(&entry),
+                Some(Stale {
+                    fetched_at: 1_000.0,
+                    error,
+                })
+            );
+        } else {
+            assert_eq!(result, Err(error));
+        }
+    }
+
+    #[test]
+    fn test_stale_without_cache() {
+        let error = FetchError::Timeout {
+            url: URL.to_owned(),
+        };
+        assert_eq!(stale(None, error.clone()), Err(error));
+    }
+
+    #[test]
+    fn test_errors_are_told_apart() {
+        let url = "https://x.test".to_owned();
+        let errors = [
+            FetchError::Timeout { url: url.clone() },
+            FetchError::Network {
+                url: url.clone(),
+                reason: "TypeError: Failed to fetch".to_owned(),
+            },
+            FetchError::NotFound { url: url.clone() },
+            FetchError::Status {
+                url: url.clone(),
+                status: 502,
+            },
+            FetchError::Parse {
+                url,
+                reason: "expected value at line 1 column 1".to_owned(),
+            },
+        ];
+        let messages = errors.iter().map(ToString::to_string).collect::<Vec<_>>();
+        let hints = errors.iter().map(FetchError::hint).collect::<Vec<_>>();
+
+        assert_eq!(
+            messages,
+            [
+                "the request timed out",
+                "the server couldn't be reached",
+                "not found",
+                "the server answered with status 502",
+                "the response couldn't be read: expected value at line 1 column 1",
+            ]
+        );
+        assert_eq!(
+            hints,
+            [
+                "the server may be busy, try again in a moment",
+                "check your connection, then try again",
+                "check the username in the config",
+                "the server is having trouble, try again later",
+                "try again later",
+            ]
+        );
+    }
+}
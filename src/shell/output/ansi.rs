@@ -0,0 +1,249 @@
+use std::iter::Peekable;
+use std::mem;
+use std::str::Chars;
+
+use super::{Line, Span, Style};
+
+/// The standard colors, in SGR order, as theme colors.
+/// Bright colors use the same ones, except bright black.
+const THEME: [&str; 8] = [
+    "var(--color-surface)",
+    "var(--color-red-theme)",
+    "var(--color-green-theme)",
+    "var(--color-yellow-theme)",
+    "var(--color-indigo-theme)",
+    "var(--color-violet-theme)",
+    "var(--color-blue-theme)",
+    "var(--color-foreground)",
+];
+
+/// Levels of each channel in the 6x6x6 color cube of the 256 colors
+const CUBE: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// Styles set by SGR escape sequences, like those printed by `cargo` or `ls --color`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Sgr {
+    pub fg: Option<Color>,
+    pub bg: Option<Color>,
+    pub bold: bool,
+    pub italic: bool,
+    pub underline: bool,
+    /// Swaps the foreground and background colors
+    pub inverse: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    /// One of the 8 standard colors, or 16 with the bright ones, mapped onto the theme
+    Standard(u8),
+    /// One of the other 240 of the 256 colors
+    Indexed(u8),
+    Rgb(u8, u8, u8),
+}
+
+impl Sgr {
+    /// Applies the parameters of an SGR sequence, e.g. `1;31` from `\e[1;31m`
+    fn apply(&mut self, params: &str) {
+        // an empty parameter means 0, so `\e[m` resets
+        let mut codes = params.split(';').map(|p| p.parse::<u16>().unwrap_or(0));
+        while let Some(code) = codes.next() {
+            match code {
+                0 => *self = Self::default(),
+                1 => self.bold = true,
+                3 => self.italic = true,
+                4 => self.underline = true,
+                7 => self.inverse = true,
+                22 => self.bold = false,
+                23 => self.italic = false,
+                24 => self.underline = false,
+                27 => self.inverse = false,
+                30..=37 => self.fg = Some(Color::Standard((code - 30) as u8)),
+                38 => self.fg = Color::extended(&mut codes),
+                39 => self.fg = None,
+                40..=47 => self.bg = Some(Color::Standard((code - 40) as u8)),
+                48 => self.bg = Color::extended(&mut codes),
+                49 => self.bg = None,
+                90..=97 => self.fg = Some(Color::Standard((code - 90 + 8) as u8)),
+                100..=107 => self.bg = Some(Color::Standard((code - 100 + 8) as u8)),
+                _ => {},
+            }
+        }
+    }
+}
+
+impl Color {
+    /// Reads the color after `38` or `48`, either `5;n` or `2;r;g;b`
+    fn extended(codes: &mut impl Iterator<Item = u16>) -> Option<Self> {
+        let mut next = || codes.next().and_then(|c| u8::try_from(c).ok());
+        match next()? {
+            5 => Some(Self::indexed(next()?)),
+            2 => Some(Self::Rgb(next()?, next()?, next()?)),
+            _ => None,
+        }
+    }
+
+    fn indexed(n: u8) -> Self {
+        if n < 16 { Self::Standard(n) } else { Self::Indexed(n) }
+    }
+
+    /// The color as a CSS value
+    pub fn css(self) -> String {
+        match self {
+            Self::Standard(8) => "var(--color-muted)".to_owned(),
+            Self::Standard(n) => THEME[usize::from(n % 8)].to_owned(),
+            Self::Indexed(n @ 16..=231) => {
+                let n = usize::from(n - 16);
+                let (r, g, b) = (CUBE[n / 36], CUBE[n / 6 % 6], CUBE[n % 6]);
+                format!("rgb({r} {g} {b})")
+            },
+            Self::Indexed(n) => {
+                let level = 8 + 10 * n.saturating_sub(232);
+                format!("rgb({level} {level} {level})")
+            },
+            Self::Rgb(r, g, b) => format!("rgb({r} {g} {b})"),
+        }
+    }
+}
+
+/// Splits `text` into lines of spans styled by its SGR sequences.
+/// Other escape sequences, like those moving the cursor, are dropped.
+pub fn parse(text: &str) -> Vec<Line> {
+    let mut lines = vec![Line::default()];
+    let mut sgr = Sgr::default();
+    let mut buf = String::new();
+    let mut chars = text.chars().peekable();
+
+    let flush = |lines: &mut Vec<Line>, buf: &mut String, sgr: Sgr| {
+        if !buf.is_empty() {
+            let style = if sgr == Sgr::default() { Style::Plain } else { Style::Ansi(sgr) };
+            let line = lines.last_mut().expect("has at least 1 line");
+            line.0.push(Span::styled(mem::take(buf), style));
+        }
+    };
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\n' => {
+                flush(&mut lines, &mut buf, sgr);
+                lines.push(Line::default());
+            },
+            '\x1b' => {
+                if let Some(params) = escape(&mut chars) {
+                    flush(&mut lines, &mut buf, sgr);
+                    sgr.apply(&params);
+                }
+            },
+            // progress bars redraw their line, which can't be done here
+            '\r' => {},
+            c => buf.push(c),
+        }
+    }
+    flush(&mut lines, &mut buf, sgr);
+
+    lines
+}
+
+/// Skips the escape sequence after `\e`, returning its parameters if it's an SGR sequence
+fn escape(chars: &mut Peekable<Chars>) -> Option<String> {
+    match chars.next()? {
+        // CSI, which ends with a byte in @..~, e.g. `m` for SGR or `K` to clear the line
+        '[' => {
+            let mut params = String::new();
+            for c in chars.by_ref() {
+                if ('@'..='~').contains(&c) {
+                    return (c == 'm').then_some(params);
+                }
+                params.push(c);
+            }
+            None
+        },
+        // OSC, like a hyperlink, which ends with BEL or `\e\`
+        ']' => {
+            while let Some(c) = chars.next() {
+                if c == '\x07' || (c == '\x1b' && chars.next_if_eq(&'\\').is_some()) {
+                    break;
+                }
+            }
+            None
+        },
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    fn sgr(params: &str) -> Sgr {
+        let mut sgr = Sgr::default();
+        sgr.apply(params);
+        sgr
+    }
+
+    #[test]
+    fn test_parse() {
+        let red = Style::Ansi(Sgr {
+            fg: Some(Color::Standard(1)),
+            ..Sgr::default()
+        });
+
+        assert_eq!(
+            parse("a \x1b[31mred\x1b[0m b"),
+            [Line(vec![
+                Span::new("a "),
+                Span::styled("red", red),
+                Span::new(" b"),
+            ])]
+        );
+    }
+
+    #[test]
+    fn test_parse_carries_over_lines() {
+        let lines = parse("\x1b[1mbold\nstill\x1b[m\nplain");
+        let styles = lines
+            .iter()
+            .map(|l| l.0[0].style.clone())
+            .collect::<Vec<_>>();
+
+        assert_eq!(styles[0], styles[1]);
+        assert_eq!(styles[2], Style::Plain);
+    }
+
+    #[rstest]
+    #[case::clear_line("done\x1b[K", "done")]
+    #[case::cursor("\x1b[2Aup", "up")]
+    #[case::hyperlink("\x1b]8;;https://example.com\x1b\\link\x1b]8;;\x07", "link")]
+    #[case::carriage_return("50%\r100%", "50%100%")]
+    #[case::unterminated("cut\x1b[3", "cut")]
+    fn test_parse_drops(#[case] text: &str, #[case] expected: &str) {
+        assert_eq!(parse(text)[0].text(), expected);
+    }
+
+    #[rstest]
+    #[case::reset("", Sgr::default())]
+    #[case::bold_red("1;31", Sgr { fg: Some(Color::Standard(1)), bold: true, ..Sgr::default() })]
+    #[case::bright("92", Sgr { fg: Some(Color::Standard(10)), ..Sgr::default() })]
+    #[case::background("44", Sgr { bg: Some(Color::Standard(4)), ..Sgr::default() })]
+    #[case::indexed("38;5;208", Sgr { fg: Some(Color::Indexed(208)), ..Sgr::default() })]
+    #[case::indexed_standard("38;5;3", Sgr { fg: Some(Color::Standard(3)), ..Sgr::default() })]
+    #[case::rgb("48;2;1;2;3", Sgr { bg: Some(Color::Rgb(1, 2, 3)), ..Sgr::default() })]
+    #[case::styles("3;4;7", Sgr { italic: true, underline: true, inverse: true, ..Sgr::default() })]
+    #[case::off("1;3;22;23", Sgr::default())]
+    #[case::default_fg("31;39", Sgr::default())]
+    fn test_apply(#[case] params: &str, #[case] expected: Sgr) {
+        assert_eq!(sgr(params), expected);
+    }
+
+    #[rstest]
+    #[case::red(Color::Standard(1), "var(--color-red-theme)")]
+    #[case::bright_red(Color::Standard(9), "var(--color-red-theme)")]
+    #[case::bright_black(Color::Standard(8), "var(--color-muted)")]
+    #[case::cube(Color::Indexed(208), "rgb(255 135 0)")]
+    #[case::gray(Color::Indexed(244), "rgb(128 128 128)")]
+    #[case::rgb(Color::Rgb(1, 2, 3), "rgb(1 2 3)")]
+    fn test_css(#[case] color: Color, #[case] expected: &str) {
+        assert_eq!(color.css(), expected);
+    }
+}
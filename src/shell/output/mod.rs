@@ -0,0 +1,365 @@
+use std::fmt;
+use std::pin::Pin;
+
+use leptos::prelude::*;
+
+pub use self::plain::{COLUMNS, into_plain, plain};
+pub use self::render::render;
+
+pub mod ansi;
+mod plain;
+mod render;
+
+/// What a command prints, kept apart from how it's displayed,
+/// so commands can be tested without a DOM
+#[derive(Debug, Default, PartialEq)]
+pub struct CommandOutput {
+    pub blocks: Vec<Block>,
+    /// Exit status of the command, where anything but 0 is a failure
+    pub status: u8,
+}
+
+impl CommandOutput {
+    pub fn new(blocks: Vec<Block>) -> Self {
+        Self { blocks, status: 0 }
+    }
+
+    /// A single line of text
+    pub fn line(line: impl Into<Line>) -> Self {
+        Self::new(vec![Block::Line(line.into())])
+    }
+
+    /// Text printed by a program, styled by the SGR sequences in it
+    pub fn text(text: &str) -> Self {
+        let lines = ansi::parse(text).into_iter().map(|l| {
+            if l.0.is_empty() { Block::Blank } else { Block::Line(l) }
+        });
+        Self::new(lines.collect())
+    }
+
+    /// Text printed by a program as is, where a final newline ends the last line
+    /// instead of starting another, and nothing prints nothing
+    pub fn printed(text: &str) -> Self {
+        if text.is_empty() {
+            return Self::default();
+        }
+        Self::text(text.strip_suffix('\n').unwrap_or(text))
+    }
+
+    /// A failure message with the given status
+    pub fn error(msg: impl Into<String>, status: u8) -> Self {
+        Self::new(vec![Block::Error(msg.into())]).with_status(status)
+    }
+
+    /// A live view, e.g. a game that takes over the screen
+    pub fn view<V: IntoView + 'static>(f: impl FnOnce() -> V + 'static) -> Self {
+        Self::new(vec![Block::View(Widget(Box::new(|| f().into_any())))])
+    }
+
+    /// Output that has to be loaded, showing `loading` until it is
+    pub fn deferred(
+        loading: impl Into<Line>,
+        output: impl Future<Output = CommandOutput> + 'static,
+    ) -> Self {
+        Self::new(vec![Block::Deferred(Deferred {
+            loading: loading.into(),
+            output: Box::pin(output),
+        })])
+    }
+
+    pub fn with_status(mut self, status: u8) -> Self {
+        self.status = status;
+        self
+    }
+
+    /// Whether some of the output has yet to load, so its status isn't known yet
+    pub fn is_loading(&self) -> bool {
+        self.blocks.iter().any(|b| matches!(b, Block::Deferred(_)))
+    }
+
+    /// Waits for the deferred blocks to load, putting their output in their place.
+    /// The status is that of the last one to load.
+    pub async fn resolve(self) -> Self {
+        let mut status = self.status;
+        let mut blocks = Vec::new();
+        for block in self.blocks {
+            match block {
+                Block::Deferred(d) => {
+                    let output = Box::pin(d.output.await.resolve()).await;
+                    status = output.status;
+                    blocks.extend(output.blocks);
+                },
+                block => blocks.push(block),
+            }
+        }
+        Self { blocks, status }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Block {
+    Line(Line),
+    /// An empty line
+    Blank,
+    /// Text shown as is, keeping whitespace and line breaks
+    Pre(String),
+    /// A failure message
+    Error(String),
+    /// A bulleted list
+    List(Vec<Line>),
+    /// Terms with their descriptions, aligned in two columns, like the commands in `help`
+    Terms {
+        /// Test id of each item
+        id: Option<&'static str>,
+        items: Vec<(Line, Line)>,
+    },
+    Table(Table),
+    /// Labels with a colored border, like the items in `stack`
+    Badges(Vec<Span>),
+    /// Blocks under an optional title
+    Group {
+        /// Test id of the group
+        id: Option<&'static str>,
+        title: Option<Line>,
+        blocks: Vec<Block>,
+    },
+    /// Ascii art shown beside the blocks on wide screens, and above them otherwise.
+    /// The art is decoration, so it's left out of copied text.
+    Figure {
+        art: String,
+        blocks: Vec<Block>,
+    },
+    /// A live view, which has no text of its own
+    View(Widget),
+    /// Output that isn't known until something has loaded
+    Deferred(Deferred),
+}
+
+impl Block {
+    /// A group without a title, identified by `id` in tests
+    pub fn group(id: &'static str, blocks: Vec<Block>) -> Self {
+        Self::Group {
+            id: Some(id),
+            title: None,
+            blocks,
+        }
+    }
+}
+
+/// A line of text, made of styled spans
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Line(pub Vec<Span>);
+
+impl Line {
+    /// The text of the line, without styles
+    pub fn text(&self) -> String {
+        self.0.iter().map(|s| s.text.as_str()).collect()
+    }
+}
+
+impl From<&str> for Line {
+    fn from(s: &str) -> Self {
+        Self(vec![Span::new(s)])
+    }
+}
+
+impl From<String> for Line {
+    fn from(s: String) -> Self {
+        Self(vec![Span::new(s)])
+    }
+}
+
+impl From<Span> for Line {
+    fn from(span: Span) -> Self {
+        Self(vec![span])
+    }
+}
+
+impl From<Vec<Span>> for Line {
+    fn from(spans: Vec<Span>) -> Self {
+        Self(spans)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Span {
+    pub text: String,
+    pub style: Style,
+    /// Where the span links to
+    pub link: Option<String>,
+    /// Shown before the text
+    pub glyph: Option<Glyph>,
+}
+
+impl Span {
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            ..Self::default()
+        }
+    }
+
+    /// A glyph on its own, without text
+    pub fn glyph(glyph: Glyph) -> Self {
+        Self::default().with_glyph(glyph)
+    }
+
+    pub fn styled(text: impl Into<String>, style: Style) -> Self {
+        Self::new(text).with_style(style)
+    }
+
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    pub fn with_link(mut self, link: impl Into<String>) -> Self {
+        self.link = Some(link.into());
+        self
+    }
+
+    pub fn with_glyph(mut self, glyph: Glyph) -> Self {
+        self.glyph = Some(glyph);
+        self
+    }
+}
+
+/// What a span means, which the renderer maps to a color of the theme
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum Style {
+    #[default]
+    Plain,
+    /// Stands out from the text around it, like a name
+    Strong,
+    /// Marks a heading
+    Heading,
+    /// The name of a command
+    Command,
+    /// A key, or a label of a table
+    Label,
+    /// A value worth drawing the eye to, like a contact
+    Accent,
+    Pass,
+    Fail,
+    /// Something that isn't there yet
+    Muted,
+    /// A CSS color, like that of a language
+    Color(String),
+    /// Set by SGR escape sequences in the text
+    Ansi(ansi::Sgr),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Glyph {
+    Icon(icondata::Icon),
+    /// A dot in a CSS color
+    Dot(String),
+}
+
+/// Rows of cells under a header, shown as a table on wide screens and as cards otherwise
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Table {
+    /// Test id of the table, suffixed with `-table` and `-list` for either layout
+    pub id: Option<&'static str>,
+    pub headers: Vec<String>,
+    pub rows: Vec<Row>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Row {
+    pub cells: Vec<Line>,
+    /// Where the whole row links to
+    pub link: Option<String>,
+}
+
+/// Builds a live view once the output is rendered
+pub struct Widget(Box<dyn FnOnce() -> AnyView>);
+
+impl fmt::Debug for Widget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Widget")
+    }
+}
+
+/// Live views can't be compared, so they never equal anything
+impl PartialEq for Widget {
+    fn eq(&self, _: &Self) -> bool {
+        false
+    }
+}
+
+pub struct Deferred {
+    /// Shown until the output has loaded
+    loading: Line,
+    output: Pin<Box<dyn Future<Output = CommandOutput>>>,
+}
+
+impl Deferred {
+    /// Changes the output once it has loaded
+    pub fn map(self, f: impl FnOnce(CommandOutput) -> CommandOutput + 'static) -> Self {
+        let Self { loading, output } = self;
+        Self {
+            loading,
+            output: Box::pin(async move { f(output.await) }),
+        }
+    }
+}
+
+impl fmt::Debug for Deferred {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Deferred")
+            .field("loading", &self.loading)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Pending output can't be compared, so it never equals anything
+impl PartialEq for Deferred {
+    fn eq(&self, _: &Self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::executor::block_on;
+
+    use super::*;
+
+    #[test]
+    fn test_line_text() {
+        let line = Line(vec![
+            Span::styled("name", Style::Command),
+            Span::new(" - "),
+            Span::new("a command").with_link("https://example.com"),
+        ]);
+        assert_eq!(line.text(), "name - a command");
+    }
+
+    #[test]
+    fn test_resolve() {
+        let output = CommandOutput::new(vec![
+            Block::Line("loading".into()),
+            CommandOutput::deferred("...", async { CommandOutput::error("failed", 1) })
+                .blocks
+                .remove(0),
+        ]);
+
+        let output = block_on(output.resolve());
+        assert_eq!(
+            output.blocks,
+            vec![
+                Block::Line("loading".into()),
+                Block::Error("failed".to_owned())
+            ]
+        );
+        assert_eq!(output.status, 1);
+    }
+
+    #[test]
+    fn test_error() {
+        let output = CommandOutput::error("oops", 2);
+        assert_eq!(output.blocks, vec![Block::Error("oops".to_owned())]);
+        assert_eq!(output.status, 2);
+    }
+}
@@ -0,0 +1,317 @@
+use std::mem;
+
+use super::{Block, CommandOutput, Line, Table};
+use crate::shell::raw::wrap;
+use crate::shell::width::{pad, width};
+
+/// Width of the screen when `$COLUMNS` isn't set
+pub const COLUMNS: usize = 80;
+
+/// Narrowest a table column is squeezed to, so it stays readable
+const MIN_COLUMN: usize = 4;
+
+/// Renders the output of a command as plain monospaced text, every line ending in `\n`.
+/// Tables are drawn with box characters and wrapped to `columns`.
+pub fn plain(output: &CommandOutput, columns: usize) -> String {
+    let mut lines = Vec::new();
+    blocks(&output.blocks, columns, &mut lines);
+    lines.into_iter().map(|l| l + "\n").collect()
+}
+
+/// Replaces the blocks of `output` with their plain text, as soon as they've loaded.
+/// Live views are kept as they are, as they have no text.
+pub fn into_plain(output: CommandOutput, columns: usize) -> CommandOutput {
+    let mut blocks = Vec::new();
+    let mut ready = Vec::new();
+    let flush = |ready: &mut Vec<Block>, blocks: &mut Vec<Block>| {
+        if !ready.is_empty() {
+            let text = plain(&CommandOutput::new(mem::take(ready)), columns);
+            let text = text.strip_suffix('\n').unwrap_or(&text);
+            blocks.push(Block::Pre(text.to_owned()));
+        }
+    };
+
+    for block in output.blocks {
+        match block {
+            Block::Deferred(d) => {
+                flush(&mut ready, &mut blocks);
+                blocks.push(Block::Deferred(d.map(move |o| into_plain(o, columns))));
+            },
+            Block::View(w) => {
+                flush(&mut ready, &mut blocks);
+                blocks.push(Block::View(w));
+            },
+            block => ready.push(block),
+        }
+    }
+    flush(&mut ready, &mut blocks);
+
+    CommandOutput {
+        blocks,
+        status: output.status,
+    }
+}
+
+fn blocks(blocks: &[Block], columns: usize, out: &mut Vec<String>) {
+    for (i, block) in blocks.iter().enumerate() {
+        match block {
+            Block::Line(l) => out.push(line(l)),
+            Block::Blank => out.push(String::new()),
+            Block::Pre(text) | Block::Error(text) => out.extend(text.lines().map(str::to_owned)),
+            Block::List(items) => out.extend(items.iter().map(|l| format!("• {}", line(l)))),
+            Block::Terms { items, .. } => {
+                let terms = items.iter().map(|(t, _)| line(t)).collect::<Vec<_>>();
+                let w = terms.iter().map(|t| width(t)).max().unwrap_or_default();
+                for (term, (_, desc)) in terms.iter().zip(items) {
+                    let item = format!("  {}  {}", pad(term, w), line(desc));
+                    out.push(item.trim_end().to_owned());
+                }
+            },
+            Block::Table(t) => out.extend(table(t, columns)),
+            Block::Badges(items) => out.extend(items.iter().map(|s| format!("• {}", s.text))),
+            Block::Group {
+                title, blocks: b, ..
+            } => {
+                if let Some(title) = title {
+                    if i > 0 {
+                        out.push(String::new());
+                    }
+                    out.push(line(title));
+                }
+                self::blocks(b, columns, out);
+            },
+            // the art is decoration, which gets in the way once copied
+            Block::Figure { blocks: b, .. } => self::blocks(b, columns, out),
+            Block::View(_) => {},
+            Block::Deferred(d) => out.push(line(&d.loading)),
+        }
+    }
+}
+
+/// The text of a line, with the target of each link that doesn't already show it
+fn line(line: &Line) -> String {
+    line.0
+        .iter()
+        .map(|span| match &span.link {
+            Some(link) if !link.contains(&span.text) => format!("{} <{link}>", span.text),
+            _ => span.text.clone(),
+        })
+        .collect()
+}
+
+/// Draws `table` with box characters, narrowing its widest columns until it fits in `columns`.
+/// Cells too wide for their column wrap onto more lines.
+fn table(table: &Table, columns: usize) -> Vec<String> {
+    let n = table
+        .rows
+        .iter()
+        .map(|r| r.cells.len())
+        .chain([table.headers.len()])
+        .max()
+        .unwrap_or_default();
+    if n == 0 {
+        return Vec::new();
+    }
+
+    let cells = |cells: Vec<String>| {
+        let mut cells = cells;
+        cells.resize(n, String::new());
+        cells
+    };
+    let headers = cells(table.headers.clone());
+    let rows = table
+        .rows
+        .iter()
+        .map(|r| cells(r.cells.iter().map(Line::text).collect()))
+        .collect::<Vec<_>>();
+
+    let mut widths = (0..n)
+        .map(|i| {
+            rows.iter()
+                .chain([&headers])
+                .map(|r| width(&r[i]))
+                .max()
+                .unwrap_or_default()
+        })
+        .collect::<Vec<_>>();
+    // each column is framed by a space on either side and a border
+    let frame = 3 * n + 1;
+    while widths.iter().sum::<usize>() + frame > columns {
+        let Some(widest) = widths.iter_mut().max() else {
+            break;
+        };
+        if *widest <= MIN_COLUMN {
+            break;
+        }
+        *widest -= 1;
+    }
+
+    let border = |left: &str, mid: &str, right: &str| {
+        let segments = widths
+            .iter()
+            .map(|&w| "─".repeat(w + 2))
+            .collect::<Vec<_>>();
+        format!("{left}{}{right}", segments.join(mid))
+    };
+
+    let mut out = vec![border("┌", "┬", "┐")];
+    out.extend(row(&headers, &widths));
+    out.push(border("├", "┼", "┤"));
+    for r in &rows {
+        out.extend(row(r, &widths));
+    }
+    out.push(border("└", "┴", "┘"));
+    out
+}
+
+fn row(cells: &[String], widths: &[usize]) -> Vec<String> {
+    let wrapped = cells
+        .iter()
+        .zip(widths)
+        .map(|(c, &w)| wrap(c, w))
+        .collect::<Vec<_>>();
+    let height = wrapped.iter().map(Vec::len).max().unwrap_or_default();
+
+    (0..height)
+        .map(|i| {
+            let mut line = "│".to_owned();
+            for (cell, &w) in wrapped.iter().zip(widths) {
+                let text = cell.get(i).map_or("", String::as_str);
+                line.push_str(&format!(" {} │", pad(text, w)));
+            }
+            line
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shell::output::{Row, Span, Style};
+
+    fn table(rows: &[[&str; 2]]) -> Block {
+        Block::Table(Table {
+            id: None,
+            headers: vec!["NAME".to_owned(), "DESCRIPTION".to_owned()],
+            rows: rows
+                .iter()
+                .map(|r| Row {
+                    cells: r.iter().map(|&c| c.into()).collect(),
+                    link: None,
+                })
+                .collect(),
+        })
+    }
+
+    #[test]
+    fn test_table() {
+        let output = CommandOutput::new(vec![table(&[["seaq", "About seaq"]])]);
+        let expected = "\
+┌──────┬─────────────┐
+│ NAME │ DESCRIPTION │
+├──────┼─────────────┤
+│ seaq │ About seaq  │
+└──────┴─────────────┘
+";
+        assert_eq!(plain(&output, COLUMNS), expected);
+    }
+
+    #[test]
+    fn test_table_wraps() {
+        let output = CommandOutput::new(vec![table(&[["seaq", "Search the web quickly"]])]);
+        let expected = "\
+┌──────┬────────────┐
+│ NAME │ DESCRIPTIO │
+│      │ N          │
+├──────┼────────────┤
+│ seaq │ Search the │
+│      │ web        │
+│      │ quickly    │
+└──────┴────────────┘
+";
+        assert_eq!(plain(&output, 21), expected);
+    }
+
+    #[test]
+    fn test_table_wide_characters() {
+        let output = CommandOutput::new(vec![table(&[["日本", "語"]])]);
+        let expected = "\
+┌──────┬─────────────┐
+│ NAME │ DESCRIPTION │
+├──────┼─────────────┤
+│ 日本 │ 語          │
+└──────┴─────────────┘
+";
+        assert_eq!(plain(&output, COLUMNS), expected);
+    }
+
+    #[test]
+    fn test_section() {
+        let section = |title: &str, items: &[&str]| Block::Group {
+            id: None,
+            title: Some(Line(vec![
+                Span::styled(">", Style::Heading),
+                Span::new(format!(" {title}")),
+            ])),
+            blocks: vec![Block::Badges(items.iter().map(|&i| Span::new(i)).collect())],
+        };
+        let output = CommandOutput::new(vec![
+            section("LANGUAGES", &["Rust", "Go"]),
+            section("TOOLS", &["Nix"]),
+        ]);
+
+        assert_eq!(
+            plain(&output, COLUMNS),
+            "> LANGUAGES\n• Rust\n• Go\n\n> TOOLS\n• Nix\n"
+        );
+    }
+
+    #[test]
+    fn test_terms() {
+        let output = CommandOutput::new(vec![Block::Terms {
+            id: None,
+            items: vec![
+                ("echo".into(), "display a line of text".into()),
+                ("projects".into(), "explore my projects".into()),
+            ],
+        }]);
+
+        assert_eq!(
+            plain(&output, COLUMNS),
+            "  echo      display a line of text\n  projects  explore my projects\n"
+        );
+    }
+
+    #[test]
+    fn test_links() {
+        let output = CommandOutput::line(Line(vec![
+            Span::new("this video").with_link("https://youtu.be/x"),
+            Span::new(" and "),
+            Span::new("github.com/me").with_link("https://github.com/me"),
+        ]));
+
+        assert_eq!(
+            plain(&output, COLUMNS),
+            "this video <https://youtu.be/x> and github.com/me\n"
+        );
+    }
+
+    #[test]
+    fn test_figure_leaves_out_art() {
+        let output = CommandOutput::new(vec![Block::Figure {
+            art: "(o_o)".to_owned(),
+            blocks: vec![Block::Line("name: me".into())],
+        }]);
+
+        assert_eq!(plain(&output, COLUMNS), "name: me\n");
+    }
+
+    #[test]
+    fn test_into_plain() {
+        let output = CommandOutput::new(vec![Block::Line("a".into()), Block::Blank]).with_status(3);
+        let output = into_plain(output, COLUMNS);
+
+        assert_eq!(output.blocks, vec![Block::Pre("a\n".to_owned())]);
+        assert_eq!(output.status, 3);
+    }
+}
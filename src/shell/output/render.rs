@@ -0,0 +1,347 @@
+use leptos::either::{Either, EitherOf3};
+use leptos::prelude::*;
+use leptos::task::spawn_local_scoped_with_cancellation;
+use leptos_icons::Icon;
+
+use super::ansi::{Color, Sgr};
+use super::{Block, CommandOutput, Deferred, Glyph, Line, Row, Span, Style, Table};
+use crate::shell::process::Process;
+
+/// Renders the output of a command as it's shown on screen
+pub fn render(output: CommandOutput, process: Process) -> AnyView {
+    blocks(output.blocks, process)
+}
+
+fn blocks(blocks: Vec<Block>, process: Process) -> AnyView {
+    blocks
+        .into_iter()
+        .map(|b| block(b, process))
+        .collect_view()
+        .into_any()
+}
+
+fn block(block: Block, process: Process) -> AnyView {
+    match block {
+        Block::Line(l) => view! { <p class="whitespace-pre-wrap">{line(l)}</p> }.into_any(),
+        Block::Blank => view! { <br /> }.into_any(),
+        Block::Pre(text) => view! { <pre>{text}</pre> }.into_any(),
+        Block::Error(msg) => view! { <p class="text-fail">{msg}</p> }.into_any(),
+        Block::List(items) => {
+            let items = items
+                .into_iter()
+                .map(|l| view! { <li>{line(l)}</li> })
+                .collect_view();
+            view! { <ul class="pl-4 list-disc">{items}</ul> }.into_any()
+        },
+        Block::Terms { id, items } => {
+            let items = items
+                .into_iter()
+                .map(|(term, desc)| {
+                    view! {
+                        <span class="contents" data-testid=id>
+                            <span class="pl-8">{line(term)}</span>
+                            <span>{line(desc)}</span>
+                        </span>
+                    }
+                })
+                .collect_view();
+            view! { <div class="grid gap-x-6 grid-cols-[max-content_auto]">{items}</div> }
+                .into_any()
+        },
+        Block::Table(t) => table(t).into_any(),
+        Block::Badges(items) => {
+            let items = items.into_iter().map(badge).collect_view();
+            view! { <div class="flex flex-wrap gap-4">{items}</div> }.into_any()
+        },
+        Block::Group {
+            id,
+            title,
+            blocks: b,
+        } => {
+            let title = title.map(|t| view! { <p>{line(t)}</p> });
+            view! {
+                <div class="not-first:mt-4" data-testid=id>
+                    {title}
+                    {blocks(b, process)}
+                </div>
+            }
+            .into_any()
+        },
+        Block::Figure { art, blocks: b } => view! {
+            <div class="flex flex-col gap-6 items-start lg:flex-row lg:gap-12 lg:items-center">
+                <p class="text-xs whitespace-pre text-orange-theme" aria-hidden="true">
+                    {art}
+                </p>
+                <div class="flex flex-col gap-0 py-4 border-y">{blocks(b, process)}</div>
+            </div>
+        }
+        .into_any(),
+        Block::View(widget) => (widget.0)(),
+        Block::Deferred(deferred) => {
+            // starts loading once built, not when the command runs,
+            // so the line it's on is done waiting on its own substitutions
+            let deferred = StoredValue::new_local(Some(deferred));
+            (move || {
+                let deferred = deferred.try_update_value(Option::take).flatten();
+                deferred.map(|d| self::deferred(d, process))
+            })
+            .into_any()
+        },
+    }
+}
+
+/// Shows the loading line until the output has loaded, holding up the prompt meanwhile
+fn deferred(deferred: Deferred, process: Process) -> AnyView {
+    let Deferred { loading, output } = deferred;
+    let loaded = StoredValue::new_local(None);
+    let ready = RwSignal::new(false);
+
+    process.pending.set(true);
+    untrack(|| {
+        spawn_local_scoped_with_cancellation(async move {
+            let output = output.await;
+            loaded.set_value(Some(output));
+            process.pending.set(false);
+            ready.set(true);
+        })
+    });
+
+    let loading = StoredValue::new_local(Some(loading));
+    (move || {
+        if ready.get() {
+            let output = loaded.try_update_value(Option::take).flatten();
+            Either::Left(output.map(|o| render(o, process)))
+        } else {
+            let loading = loading.try_update_value(Option::take).flatten();
+            Either::Right(loading.map(|l| view! { <p>{line(l)}</p> }))
+        }
+    })
+    .into_any()
+}
+
+fn line(line: Line) -> impl IntoView {
+    line.0.into_iter().map(span).collect_view()
+}
+
+fn span(span: Span) -> impl IntoView {
+    let Span {
+        text,
+        style,
+        link,
+        glyph,
+    } = span;
+
+    let class = class(&style);
+    let color = match style {
+        Style::Color(c) => Some(format!("color: {c}")),
+        Style::Ansi(sgr) => colors(sgr),
+        _ => None,
+    };
+    let inner = match glyph {
+        None => EitherOf3::A(text),
+        Some(Glyph::Icon(icon)) if text.is_empty() => EitherOf3::B(view! {
+            <span class="inline-flex relative items-center bottom-[1px]">
+                <Icon icon=icon height="1.125em" width="1.125em" />
+            </span>
+        }),
+        Some(glyph) => EitherOf3::C(view! {
+            <span class="inline-flex gap-1 items-center">
+                {self::glyph(glyph)}
+                <span>{text}</span>
+            </span>
+        }),
+    };
+
+    match link {
+        Some(href) => Either::Left(view! {
+            <a
+                class=format!("hover:underline {class}")
+                style=color
+                href=href
+                target="_blank"
+                rel="noopener noreferrer"
+            >
+                {inner}
+            </a>
+        }),
+        None => Either::Right(view! {
+            <span class=class style=color>
+                {inner}
+            </span>
+        }),
+    }
+}
+
+fn glyph(glyph: Glyph) -> impl IntoView {
+    match glyph {
+        Glyph::Icon(icon) => Either::Left(view! {
+            <span class="inline-block relative bottom-[2px]">
+                <Icon icon=icon height="1rem" width="1rem" />
+            </span>
+        }),
+        Glyph::Dot(color) => Either::Right(view! {
+            <span
+                class="inline-block relative mr-1 w-2 h-2 rounded-full bottom-[1px]"
+                aria-hidden="true"
+                style=format!("background-color: {color}")
+            ></span>
+        }),
+    }
+}
+
+/// Tailwind classes of a style, spelled out in full so Tailwind finds them
+fn class(style: &Style) -> String {
+    let class = match style {
+        Style::Ansi(sgr) => {
+            let classes = [
+                (sgr.bold, "font-bold"),
+                (sgr.italic, "italic"),
+                (sgr.underline, "underline"),
+            ];
+            let classes = classes.into_iter().filter_map(|(on, c)| on.then_some(c));
+            return classes.collect::<Vec<_>>().join(" ");
+        },
+        Style::Plain | Style::Color(_) => "",
+        Style::Strong => "font-semibold text-primary",
+        Style::Heading => "font-bold text-primary",
+        Style::Command => "text-green-theme",
+        Style::Label => "text-info",
+        Style::Accent => "text-orange-theme",
+        Style::Pass => "text-pass",
+        Style::Fail => "text-fail",
+        Style::Muted => "italic opacity-90",
+    };
+    class.to_owned()
+}
+
+/// Inline colors of an SGR style, with the theme's colors standing in for the
+/// default ones when they're swapped
+fn colors(sgr: Sgr) -> Option<String> {
+    let (mut fg, mut bg) = (sgr.fg.map(Color::css), sgr.bg.map(Color::css));
+    if sgr.inverse {
+        (fg, bg) = (
+            Some(bg.unwrap_or("var(--color-surface)".to_owned())),
+            Some(fg.unwrap_or("var(--color-foreground)".to_owned())),
+        );
+    }
+
+    let fg = fg.map(|c| format!("color: {c};"));
+    let bg = bg.map(|c| format!("background-color: {c};"));
+    let css = [fg, bg].into_iter().flatten().collect::<String>();
+    (!css.is_empty()).then_some(css)
+}
+
+fn badge(span: Span) -> impl IntoView {
+    let color = match &span.style {
+        Style::Color(c) => c.clone(),
+        _ => "var(--color-white)".to_owned(),
+    };
+    let icon = match span.glyph {
+        Some(Glyph::Icon(icon)) => Some(view! {
+            <Icon icon=icon height="1.5rem" width="1.5rem" {..} style=format!("color: {color}") />
+        }),
+        _ => None,
+    };
+
+    view! {
+        <div
+            class="flex gap-2 items-center py-2 px-3 rounded-md border text-foreground"
+            style=format!("border-color: {color}")
+        >
+            {icon}
+            <span class="flex-1">{span.text}</span>
+        </div>
+    }
+}
+
+/// A table on wide screens, and a list of cards on narrow ones
+fn table(table: Table) -> impl IntoView {
+    let Table { id, headers, rows } = table;
+    let headers = headers
+        .into_iter()
+        .map(|h| view! { <th class="font-normal" role="columnheader">{h}</th> })
+        .collect_view();
+    let cards = rows.clone().into_iter().map(card).collect_view();
+    let rows = rows.into_iter().map(row).collect_view();
+
+    view! {
+        <table
+            class="hidden relative right-8 whitespace-nowrap border-separate table-auto lg:table border-spacing-x-8"
+            data-testid=id.map(|id| format!("{id}-table"))
+        >
+            <thead>
+                <tr class="text-left text-info">{headers}</tr>
+            </thead>
+            <tbody>{rows}</tbody>
+        </table>
+
+        <div
+            class="flex flex-col gap-2 sm:gap-4 lg:hidden"
+            data-testid=id.map(|id| format!("{id}-list"))
+        >
+            {cards}
+        </div>
+    }
+}
+
+fn row(row: Row) -> impl IntoView {
+    let Row { cells, link } = row;
+    // the description, second, is the only cell that wraps
+    let cells = cells
+        .into_iter()
+        .enumerate()
+        .map(|(i, cell)| {
+            let class = match (i, link.is_some()) {
+                (1, true) => "whitespace-normal group-hover:underline max-w-[100ch]",
+                (1, false) => "whitespace-normal max-w-[100ch]",
+                (_, true) => "group-hover:underline",
+                _ => "",
+            };
+            view! { <td class=class>{line(cell)}</td> }
+        })
+        .collect_view();
+
+    match link {
+        Some(href) => Either::Left(view! {
+            <tr>
+                <a class="contents group" href=href target="_blank" rel="noopener noreferrer">
+                    {cells}
+                </a>
+            </tr>
+        }),
+        None => Either::Right(view! { <tr>{cells}</tr> }),
+    }
+}
+
+/// The first cell as a title, the second below it and the rest on one line
+fn card(row: Row) -> impl IntoView {
+    let Row { cells, link } = row;
+    let mut cells = cells.into_iter();
+    let title = cells.next().map(line);
+    let body = cells.next().map(line);
+    let meta = cells
+        .filter(|c| !c.text().is_empty())
+        .map(|c| view! { <span>{line(c)}</span> })
+        .collect_view();
+
+    let content = view! {
+        <span class="group-hover:underline text-info">{title}</span>
+        <span>{body}</span>
+        <span class="flex gap-2 items-center">{meta}</span>
+    };
+    match link {
+        Some(href) => Either::Left(view! {
+            <a
+                class="flex flex-col gap-1 sm:gap-0 group"
+                href=href
+                target="_blank"
+                rel="noopener noreferrer"
+            >
+                {content}
+            </a>
+        }),
+        None => {
+            Either::Right(view! { <span class="flex flex-col gap-1 sm:gap-0">{content}</span> })
+        },
+    }
+}
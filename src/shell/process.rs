@@ -0,0 +1,157 @@
+use leptos::prelude::*;
+use web_sys::{AbortController, AbortSignal};
+
+/// What the shell hands to each command it runs.
+/// Each command gets its own, so a command running in the background
+/// doesn't hold up the prompt.
+#[derive(Debug, Clone, Copy)]
+pub struct Process {
+    /// Whether the command is still loading.
+    /// The prompt is hidden while the command in the foreground is pending.
+    pub pending: RwSignal<bool>,
+    /// Cancelled when the user interrupts the command with Ctrl+C
+    pub cancel: CancelToken,
+    /// Exit status of the command, which scripts branch on
+    status: StoredValue<u8>,
+    /// Text piped into the command, e.g. the output of `echo` in `echo a | wc`
+    stdin: StoredValue<Option<String>>,
+}
+
+impl Process {
+    pub fn new() -> Self {
+        Self {
+            pending: RwSignal::new(false),
+            cancel: CancelToken::new(),
+            status: StoredValue::new(0),
+            stdin: StoredValue::new(None),
+        }
+    }
+
+    /// A process for a command run on behalf of this one, e.g. inside `$(...)`,
+    /// which is interrupted along with it
+    pub fn child(&self) -> Self {
+        Self {
+            cancel: self.cancel,
+            ..Self::new()
+        }
+    }
+
+    /// Sets the exit status of the command, where anything but 0 is a failure
+    pub fn exit(&self, status: u8) {
+        self.status.set_value(status);
+    }
+
+    pub fn status(&self) -> u8 {
+        self.status.get_value()
+    }
+
+    /// Pipes `stdin` into the command about to run, or nothing if it's `None`
+    pub fn pipe(&self, stdin: Option<String>) {
+        self.stdin.set_value(stdin);
+    }
+
+    /// Text piped into the command, if any
+    pub fn stdin(&self) -> Option<String> {
+        self.stdin.get_value()
+    }
+
+    /// Cancels the command and stops waiting for it
+    pub fn interrupt(&self) {
+        self.cancel.cancel();
+        self.pending.set(false);
+    }
+}
+
+impl Default for Process {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Tells a running command to stop.
+/// Commands doing async work should abort it once the token is cancelled,
+/// e.g. by passing `abort_signal` to their requests.
+#[derive(Debug, Clone, Copy)]
+pub struct CancelToken {
+    cancelled: RwSignal<bool>,
+    /// Created on first use, as most commands never make a request
+    controller: StoredValue<Option<AbortController>, LocalStorage>,
+}
+
+impl CancelToken {
+    pub fn new() -> Self {
+        Self {
+            cancelled: RwSignal::new(false),
+            controller: StoredValue::new_local(None),
+        }
+    }
+
+    /// Cancels the command, aborting any request made with its abort signal
+    pub fn cancel(&self) {
+        if self.cancelled.get_untracked() {
+            return;
+        }
+        self.cancelled.set(true);
+        self.controller.with_value(|c| {
+            if let Some(controller) = c {
+                controller.abort();
+            }
+        });
+    }
+
+    /// Whether the command has been cancelled.
+    /// This is reactive, so it can be used to take down the command's output.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.get()
+    }
+
+    /// A signal that aborts when the token is cancelled, to pass to `fetch`
+    pub fn abort_signal(&self) -> AbortSignal {
+        let controller = self
+            .controller
+            .try_update_value(|c| {
+                c.get_or_insert_with(|| AbortController::new().expect("should be supported"))
+                    .clone()
+            })
+            .unwrap_or_else(|| AbortController::new().expect("should be supported"));
+        // the token may have been cancelled before the controller existed
+        if self.cancelled.get_untracked() {
+            controller.abort();
+        }
+        controller.signal()
+    }
+}
+
+impl Default for CancelToken {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interrupt() {
+        let process = Process::new();
+        process.pending.set(true);
+        process.interrupt();
+
+        assert!(!process.pending.get_untracked());
+        assert!(process.cancel.is_cancelled());
+    }
+
+    #[test]
+    fn test_cancel() {
+        let token = CancelToken::new();
+        assert!(!token.is_cancelled());
+
+        token.cancel();
+        assert!(token.is_cancelled());
+
+        // cancelling twice is harmless
+        token.cancel();
+        assert!(token.is_cancelled());
+    }
+}
@@ -0,0 +1,478 @@
+use std::rc::Rc;
+use std::time::Duration;
+
+use leptos::prelude::*;
+use web_sys::KeyboardEvent;
+
+use super::width;
+
+/// Handler that receives every keystroke while a command is in raw mode
+type KeyHandler = Rc<dyn Fn(KeyboardEvent)>;
+
+/// Routes keystrokes to the command in raw mode, if any, instead of the prompt.
+/// A command claims the keyboard to receive every keystroke
+/// and releases it to hand control back to the prompt.
+#[derive(Clone, Copy)]
+pub struct Keyboard(RwSignal<Option<KeyHandler>, LocalStorage>);
+
+impl Keyboard {
+    /// Sends every subsequent keystroke to `handler`, replacing any previous claim
+    pub fn claim(&self, handler: impl Fn(KeyboardEvent) + 'static) {
+        self.0.set(Some(Rc::new(handler)));
+    }
+
+    /// Hands the keyboard back to the prompt
+    pub fn release(&self) {
+        self.0.set(None);
+    }
+
+    /// Whether a command currently owns the keyboard.
+    /// This is reactive, so it can be used to hide the prompt in raw mode.
+    pub fn is_claimed(&self) -> bool {
+        self.0.with(Option::is_some)
+    }
+
+    /// Forwards a keystroke to the claiming command.
+    /// Returns `false` if the keyboard is not claimed.
+    pub fn dispatch(&self, e: KeyboardEvent) -> bool {
+        // clone the handler out of the signal, as it may release the keyboard itself
+        match self.0.get_untracked() {
+            Some(handler) => {
+                handler(e);
+                true
+            },
+            None => false,
+        }
+    }
+}
+
+/// Creates the keyboard router and provides it as context to the component tree.
+/// It should be called only once in the root component.
+pub fn create_keyboard() -> Keyboard {
+    let keyboard = Keyboard(RwSignal::new_local(None));
+    provide_context(keyboard);
+    keyboard
+}
+
+/// Retrieves the keyboard router from the component tree context.
+/// Returns `None` if no router has been created.
+pub fn use_keyboard() -> Option<Keyboard> {
+    use_context::<Keyboard>()
+}
+
+/// A keystroke, decoupled from `KeyboardEvent` so programs can be tested on the host
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Key {
+    /// Value of `KeyboardEvent.key`, e.g. "a", "Enter" or "ArrowUp"
+    pub name: String,
+    /// Whether Ctrl (or Cmd on macOS) is held
+    pub ctrl: bool,
+}
+
+#[cfg(test)]
+impl Key {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ctrl: false,
+        }
+    }
+
+    pub fn ctrl(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ctrl: true,
+        }
+    }
+}
+
+impl Key {
+    /// Whether this is Ctrl+C, the conventional way to interrupt a program
+    pub fn is_interrupt(&self) -> bool {
+        self.ctrl && self.name == "c"
+    }
+}
+
+impl From<&KeyboardEvent> for Key {
+    fn from(e: &KeyboardEvent) -> Self {
+        Self {
+            name: e.key(),
+            ctrl: e.ctrl_key() || e.meta_key(),
+        }
+    }
+}
+
+/// Whether a program keeps running after handling an event
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Flow {
+    Continue,
+    Exit,
+}
+
+/// Colors of the current theme, so programs follow theme changes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Color {
+    #[default]
+    Foreground,
+    Primary,
+    Red,
+    Yellow,
+    Green,
+}
+
+impl Color {
+    // class names must be spelled out in full for Tailwind to pick them up
+    pub fn text_class(self) -> &'static str {
+        match self {
+            Self::Foreground => "text-foreground",
+            Self::Primary => "text-primary",
+            Self::Red => "text-red-theme",
+            Self::Yellow => "text-yellow-theme",
+            Self::Green => "text-green-theme",
+        }
+    }
+
+    pub fn bg_class(self) -> &'static str {
+        match self {
+            Self::Foreground => "bg-foreground",
+            Self::Primary => "bg-primary",
+            Self::Red => "bg-red-theme",
+            Self::Yellow => "bg-yellow-theme",
+            Self::Green => "bg-green-theme",
+        }
+    }
+}
+
+/// A single character cell of a screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cell {
+    pub ch: char,
+    pub fg: Color,
+    /// Background color, or `None` to show the surface behind the screen
+    pub bg: Option<Color>,
+    pub bold: bool,
+}
+
+impl Cell {
+    pub fn new(ch: char, fg: Color) -> Self {
+        Self {
+            ch,
+            fg,
+            ..Self::default()
+        }
+    }
+
+    /// Returns the same cell drawn with a background color
+    pub fn on(self, bg: Color) -> Self {
+        Self {
+            bg: Some(bg),
+            ..self
+        }
+    }
+
+    /// Whether `other` can be rendered in the same span as this cell
+    pub fn same_style(&self, other: &Cell) -> bool {
+        self.fg == other.fg && self.bg == other.bg && self.bold == other.bold
+    }
+}
+
+impl Default for Cell {
+    fn default() -> Self {
+        Self {
+            ch: ' ',
+            fg: Color::default(),
+            bg: None,
+            bold: false,
+        }
+    }
+}
+
+/// A fixed-size grid of cells that programs draw on each frame
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid {
+    rows: usize,
+    cols: usize,
+    cells: Vec<Cell>,
+}
+
+impl Grid {
+    pub fn new(rows: usize, cols: usize) -> Self {
+        Self {
+            rows,
+            cols,
+            cells: vec![Cell::default(); rows * cols],
+        }
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// Returns the cell at (row, col), or `None` if out of bounds
+    #[cfg(test)]
+    pub fn get(&self, row: usize, col: usize) -> Option<&Cell> {
+        (row < self.rows && col < self.cols).then(|| &self.cells[row * self.cols + col])
+    }
+
+    /// Sets the cell at (row, col). Out-of-bounds writes are ignored,
+    /// so programs can draw shapes partially off-screen.
+    pub fn set(&mut self, row: usize, col: usize, cell: Cell) {
+        if row < self.rows && col < self.cols {
+            self.cells[row * self.cols + col] = cell;
+        }
+    }
+
+    /// Writes `s` starting at (row, col), one character per cell, clipped at the right edge
+    pub fn print(&mut self, row: usize, col: usize, s: &str, fg: Color) {
+        for (i, ch) in s.chars().enumerate() {
+            self.set(row, col + i, Cell::new(ch, fg));
+        }
+    }
+
+    /// Resets every cell to a blank one
+    pub fn clear(&mut self) {
+        self.cells.fill(Cell::default());
+    }
+
+    /// Returns the cells of each row, top to bottom
+    pub fn lines(&self) -> impl Iterator<Item = &[Cell]> {
+        self.cells.chunks(self.cols.max(1))
+    }
+}
+
+/// Groups consecutive cells of the same style, so a line renders as a few spans
+/// instead of one per cell
+pub fn runs(line: &[Cell]) -> Vec<(Cell, String)> {
+    let mut runs: Vec<(Cell, String)> = Vec::new();
+    for cell in line {
+        match runs.last_mut() {
+            Some((style, text)) if style.same_style(cell) => text.push(cell.ch),
+            _ => runs.push((*cell, cell.ch.to_string())),
+        }
+    }
+    runs
+}
+
+/// Splits `s` into lines of at most `width` columns, breaking between words where possible.
+/// Newlines in `s` are kept, and words longer than a line are broken up.
+pub fn wrap(s: &str, width: usize) -> Vec<String> {
+    let width = width.max(1);
+    let mut lines = Vec::new();
+    for paragraph in s.split('\n') {
+        let mut line = String::new();
+        for mut word in paragraph.split_whitespace() {
+            let len = width::width(&line);
+            if len > 0 && len + 1 + width::width(word) > width {
+                lines.push(std::mem::take(&mut line));
+            }
+            while width::width(word) > width {
+                let (head, tail) = split_at_width(word, width);
+                lines.push(head.to_owned());
+                word = tail;
+            }
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(word);
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+/// Splits `s` after as many characters as fit in `width` columns, but at least one
+fn split_at_width(s: &str, width: usize) -> (&str, &str) {
+    let mut used = 0;
+    for (i, c) in s.char_indices() {
+        used += width::char_width(c);
+        if used > width && i > 0 {
+            return s.split_at(i);
+        }
+    }
+    (s, "")
+}
+
+/// A full-screen interactive program, run in raw mode by the `Screen` component.
+/// It receives every keystroke, optionally ticks on a timer,
+/// and draws itself on a grid of cells after each event.
+pub trait Program: 'static {
+    /// Size of the screen as (rows, cols)
+    fn size(&self) -> (usize, usize);
+
+    /// Interval between ticks, or `None` for programs driven only by keys.
+    /// It's checked again after every tick, so programs can speed up or slow down.
+    fn tick_rate(&self) -> Option<Duration> {
+        None
+    }
+
+    fn on_key(&mut self, key: &Key) -> Flow;
+
+    fn on_tick(&mut self) -> Flow {
+        Flow::Continue
+    }
+
+    fn draw(&self, grid: &mut Grid);
+
+    /// A line left in the history buffer after the program exits
+    fn summary(&self) -> Option<String> {
+        None
+    }
+}
+
+/// A sequence of frames played full-screen at a fixed rate, like `sl` or `matrix`.
+/// It can be stopped at any time with Ctrl+C, q or Escape.
+pub trait Animation: 'static {
+    /// Size of a frame as (rows, cols)
+    fn size(&self) -> (usize, usize);
+
+    /// Interval between frames
+    fn frame_rate(&self) -> Duration;
+
+    /// Moves on to the next frame. Returns `Flow::Exit` once the animation is over.
+    fn advance(&mut self) -> Flow;
+
+    fn draw(&self, grid: &mut Grid);
+
+    /// Moves to the frame shown on its own, instead of playing the animation,
+    /// to users who prefer reduced motion. Defaults to the first frame.
+    fn still(&mut self) {}
+}
+
+/// Plays an animation as a raw-mode program
+pub struct Frames<A>(pub A);
+
+impl<A: Animation> Program for Frames<A> {
+    fn size(&self) -> (usize, usize) {
+        self.0.size()
+    }
+
+    fn tick_rate(&self) -> Option<Duration> {
+        Some(self.0.frame_rate())
+    }
+
+    fn on_key(&mut self, key: &Key) -> Flow {
+        match key.name.as_str() {
+            _ if key.is_interrupt() => Flow::Exit,
+            "q" | "Q" | "Escape" => Flow::Exit,
+            _ => Flow::Continue,
+        }
+    }
+
+    fn on_tick(&mut self) -> Flow {
+        self.0.advance()
+    }
+
+    fn draw(&self, grid: &mut Grid) {
+        self.0.draw(grid);
+    }
+}
+
+/// Whether the user asked the system to minimize non-essential motion
+pub fn prefers_reduced_motion() -> bool {
+    window()
+        .match_media("(prefers-reduced-motion: reduce)")
+        .ok()
+        .flatten()
+        .is_some_and(|query| query.matches())
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[test]
+    fn test_grid_set_and_get() {
+        let mut grid = Grid::new(2, 3);
+        grid.set(1, 2, Cell::new('x', Color::Red));
+
+        assert_eq!(grid.get(1, 2), Some(&Cell::new('x', Color::Red)));
+        assert_eq!(grid.get(0, 0), Some(&Cell::default()));
+    }
+
+    #[test]
+    fn test_grid_out_of_bounds() {
+        let mut grid = Grid::new(2, 3);
+        grid.set(2, 0, Cell::new('x', Color::Red));
+        grid.set(0, 3, Cell::new('x', Color::Red));
+
+        assert_eq!(grid.get(2, 0), None);
+        assert_eq!(grid.get(0, 3), None);
+        assert!(grid.lines().flatten().all(|c| *c == Cell::default()));
+    }
+
+    #[test]
+    fn test_grid_print_clips() {
+        let mut grid = Grid::new(1, 4);
+        grid.print(0, 1, "hello", Color::Green);
+
+        let line = grid.lines().next().unwrap();
+        let text = line.iter().map(|c| c.ch).collect::<String>();
+        assert_eq!(text, " hel");
+        assert_eq!(line[1].fg, Color::Green);
+    }
+
+    #[test]
+    fn test_grid_clear() {
+        let mut grid = Grid::new(2, 2);
+        grid.print(0, 0, "ab", Color::Yellow);
+        grid.clear();
+
+        assert!(grid.lines().flatten().all(|c| *c == Cell::default()));
+    }
+
+    #[test]
+    fn test_cell_same_style() {
+        let a = Cell::new('a', Color::Red);
+        assert!(a.same_style(&Cell::new('b', Color::Red)));
+        assert!(!a.same_style(&Cell { bold: true, ..a }));
+        assert!(!a.same_style(&a.on(Color::Primary)));
+    }
+
+    #[test]
+    fn test_runs() {
+        let line = [
+            Cell::new('a', Color::Red),
+            Cell::new('b', Color::Red),
+            Cell::new('c', Color::Yellow),
+            Cell::new('d', Color::Red),
+        ];
+
+        let runs = runs(&line)
+            .into_iter()
+            .map(|(cell, text)| (cell.fg, text))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            runs,
+            [
+                (Color::Red, "ab".to_owned()),
+                (Color::Yellow, "c".to_owned()),
+                (Color::Red, "d".to_owned())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_runs_empty() {
+        assert!(runs(&[]).is_empty());
+    }
+
+    #[rstest]
+    #[case::fits("go north", 10, &["go north"])]
+    #[case::between_words("take the brass key", 10, &["take the", "brass key"])]
+    #[case::long_word("abcdefghij", 4, &["abcd", "efgh", "ij"])]
+    #[case::wide("日本語 です", 4, &["日本", "語", "です"])]
+    #[case::newlines("a\n\nb", 10, &["a", "", "b"])]
+    #[case::empty("", 10, &[""])]
+    fn test_wrap(#[case] s: &str, #[case] width: usize, #[case] expected: &[&str]) {
+        assert_eq!(wrap(s, width), expected);
+    }
+
+    #[test]
+    fn test_key_is_interrupt() {
+        assert!(Key::ctrl("c").is_interrupt());
+        assert!(!Key::new("c").is_interrupt());
+        assert!(!Key::ctrl("x").is_interrupt());
+    }
+}
@@ -0,0 +1,217 @@
+use std::collections::VecDeque;
+use std::fmt;
+
+use leptos::prelude::*;
+use web_sys::UrlSearchParams;
+
+use super::lang;
+use super::lang::parser::{ErrorKind, ParseError};
+use crate::config::CONFIG;
+use crate::stores::fs::use_fs;
+
+/// File in the home directory run on boot, after the startup commands in the config
+pub const RC_FILE: &str = ".wclirc";
+/// Query parameter that skips the startup commands, e.g. `/?nostartup`
+const SKIP_FLAG: &str = "nostartup";
+/// Most commands a single run of scripts may execute,
+/// so a script that sources itself doesn't run forever
+const MAX_COMMANDS: usize = 1000;
+
+/// Commands waiting to run, one at a time, as if they were typed at the prompt
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Queue {
+    lines: VecDeque<String>,
+    /// Commands run since the queue was last empty
+    ran: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TooManyCommands;
+
+impl fmt::Display for TooManyCommands {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "source: stopped after {MAX_COMMANDS} commands")
+    }
+}
+
+impl Queue {
+    /// Queues the commands of `script` ahead of those already waiting,
+    /// so a sourced script finishes before the one sourcing it carries on
+    pub fn source(&mut self, script: &str) {
+        for command in commands(script).into_iter().rev() {
+            self.lines.push_front(command);
+        }
+    }
+
+    /// Queues the commands of `script` after those already waiting
+    pub fn append(&mut self, script: &str) {
+        self.lines.extend(commands(script));
+    }
+
+    /// Takes the next command to run.
+    /// Gives up on every waiting command once too many have been run.
+    pub fn next(&mut self) -> Option<Result<String, TooManyCommands>> {
+        let Some(line) = self.lines.pop_front() else {
+            self.ran = 0;
+            return None;
+        };
+
+        if self.ran >= MAX_COMMANDS {
+            self.lines.clear();
+            self.ran = 0;
+            return Some(Err(TooManyCommands));
+        }
+
+        self.ran += 1;
+        Some(Ok(line))
+    }
+}
+
+/// Commands of a script, skipping blank lines and `#` comments
+pub fn lines(script: &str) -> impl DoubleEndedIterator<Item = &str> {
+    script
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+}
+
+/// Commands of a script, one per line,
+/// except for commands spanning several lines, like a function, which are kept whole
+pub fn commands(script: &str) -> Vec<String> {
+    let mut commands = Vec::new();
+    let mut command = String::new();
+    for line in lines(script) {
+        if !command.is_empty() {
+            command.push('\n');
+        }
+        command.push_str(line);
+
+        let incomplete = matches!(
+            lang::parse(&command),
+            Err(ParseError {
+                kind: ErrorKind::UnexpectedEof { .. },
+                ..
+            })
+        );
+        if !incomplete {
+            commands.push(std::mem::take(&mut command));
+        }
+    }
+
+    // what's left never parses, which is reported when it's run
+    if !command.is_empty() {
+        commands.push(command);
+    }
+    commands
+}
+
+/// Whether the page was opened with the flag to skip the startup commands
+fn skip_startup() -> bool {
+    window()
+        .location()
+        .search()
+        .ok()
+        .and_then(|search| UrlSearchParams::new_with_str(&search).ok())
+        .is_some_and(|params| params.has(SKIP_FLAG))
+}
+
+/// Creates the script queue and provides it as context to the component tree,
+/// with the startup commands from the config and the rc file queued up.
+/// It should be called only once in the root component, after the file layer.
+pub fn create_script() -> RwSignal<Queue> {
+    let mut queue = Queue::default();
+    if !skip_startup() {
+        queue.append(&CONFIG.startup.join("\n"));
+        let (fs, _set_fs) = use_fs().expect("not yet created");
+        fs.with_untracked(|fs| queue.append(fs.read(RC_FILE).unwrap_or_default()));
+    }
+
+    let queue = RwSignal::new(queue);
+    provide_context(queue);
+    queue
+}
+
+/// Retrieves the script queue from the component tree context.
+/// Returns `None` if no queue has been created.
+pub fn use_script() -> Option<RwSignal<Queue>> {
+    use_context::<RwSignal<Queue>>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lines() {
+        let script = "# greet\necho hello\n\n  theme dracula  \n";
+        assert_eq!(
+            lines(script).collect::<Vec<_>>(),
+            &["echo hello", "theme dracula"]
+        );
+    }
+
+    #[test]
+    fn test_commands_keep_compound_commands_whole() {
+        let script = "greet() {\n  echo hi\n}\ngreet\nfor x in a b; do\n  echo $x\ndone";
+        assert_eq!(
+            commands(script),
+            &[
+                "greet() {\necho hi\n}",
+                "greet",
+                "for x in a b; do\necho $x\ndone"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_commands_keep_unfinished_command() {
+        assert_eq!(
+            commands("echo a\nif true; then"),
+            &["echo a", "if true; then"]
+        );
+    }
+
+    #[test]
+    fn test_queue_runs_in_order() {
+        let mut queue = Queue::default();
+        queue.source("echo a\necho b");
+
+        assert_eq!(queue.next(), Some(Ok("echo a".to_owned())));
+        assert_eq!(queue.next(), Some(Ok("echo b".to_owned())));
+        assert_eq!(queue.next(), None);
+    }
+
+    #[test]
+    fn test_queue_append() {
+        let mut queue = Queue::default();
+        queue.append("echo a");
+        queue.append("echo b");
+
+        assert_eq!(queue.next(), Some(Ok("echo a".to_owned())));
+        assert_eq!(queue.next(), Some(Ok("echo b".to_owned())));
+    }
+
+    #[test]
+    fn test_queue_sourced_script_runs_first() {
+        let mut queue = Queue::default();
+        queue.source("source inner\necho outer");
+        queue.next();
+        queue.source("echo inner");
+
+        assert_eq!(queue.next(), Some(Ok("echo inner".to_owned())));
+        assert_eq!(queue.next(), Some(Ok("echo outer".to_owned())));
+    }
+
+    #[test]
+    fn test_queue_stops_runaway_scripts() {
+        let mut queue = Queue::default();
+        queue.source("source loop");
+        for _ in 0..MAX_COMMANDS {
+            assert!(matches!(queue.next(), Some(Ok(_))));
+            queue.source("source loop");
+        }
+
+        assert_eq!(queue.next(), Some(Err(TooManyCommands)));
+        assert_eq!(queue.next(), None);
+    }
+}
@@ -0,0 +1,62 @@
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// Number of columns `c` takes up in a monospaced font,
+/// where East Asian wide characters and emoji take up two, and combining marks
+/// and control characters none
+pub fn char_width(c: char) -> usize {
+    c.width().unwrap_or(0)
+}
+
+/// Number of columns `s` takes up, where a sequence like a flag counts as one character
+pub fn width(s: &str) -> usize {
+    // control characters take up a column within a string, but none on their own
+    s.split(char::is_control).map(UnicodeWidthStr::width).sum()
+}
+
+/// Pads `s` with spaces on the right to `width` columns
+pub fn pad(s: &str, width: usize) -> String {
+    let fill = width.saturating_sub(self::width(s));
+    format!("{s}{}", " ".repeat(fill))
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case::ascii("seaq", 4)]
+    #[case::cjk("日本語", 6)]
+    #[case::hangul("한국어", 6)]
+    #[case::fullwidth("ＡＢ", 4)]
+    #[case::half_width_katakana("ｱｲｳ", 3)]
+    #[case::combining("e\u{301}", 1)]
+    #[case::emoji("🦀", 2)]
+    #[case::transport("🚀", 2)]
+    #[case::extended_pictograph("🪐", 2)]
+    #[case::flag("🇫🇷", 2)]
+    #[case::emoji_symbol("⚡", 2)]
+    #[case::star("⭐", 2)]
+    #[case::text_symbol("☺", 1)]
+    #[case::mixed("a日b", 4)]
+    #[case::control("a\tb\n", 2)]
+    fn test_width(#[case] s: &str, #[case] expected: usize) {
+        assert_eq!(width(s), expected);
+    }
+
+    #[rstest]
+    #[case::ascii('a', 1)]
+    #[case::wide('日', 2)]
+    #[case::combining('\u{301}', 0)]
+    #[case::control('\u{7}', 0)]
+    fn test_char_width(#[case] c: char, #[case] expected: usize) {
+        assert_eq!(char_width(c), expected);
+    }
+
+    #[test]
+    fn test_pad() {
+        assert_eq!(pad("日本", 6), "日本  ");
+        assert_eq!(pad("toolong", 3), "toolong");
+    }
+}
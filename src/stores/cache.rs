@@ -0,0 +1,149 @@
+use serde::{Deserialize, Serialize};
+
+use super::storage;
+use crate::shell::net::FetchError;
+
+/// What a response is, which decides the ones it may evict to be kept
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    /// A page of a list, like the repositories of a user, which is shown when
+    /// fetching it again fails
+    List,
+    /// A detail of a single item, like the languages of a GitLab project.
+    /// There can be one for each item, so they're kept apart not to evict the lists.
+    Detail,
+}
+
+impl Kind {
+    fn prefix(self) -> &'static str {
+        match self {
+            Self::List => "wcli:cache:",
+            Self::Detail => "wcli:cache-detail:",
+        }
+    }
+
+    /// Most responses of the kind kept, beyond which the least recently fetched are evicted
+    fn max_entries(self) -> usize {
+        match self {
+            Self::List => 50,
+            Self::Detail => 200,
+        }
+    }
+}
+
+/// A response kept in localStorage under its URL,
+/// reused while it's fresh and revalidated with its ETag once it isn't
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Entry {
+    pub body: String,
+    pub etag: Option<String>,
+    /// The `Link` header, which paginated responses have
+    pub link: Option<String>,
+    /// When the response was fetched, or last found unchanged, in milliseconds since the epoch
+    pub fetched_at: f64,
+    /// Why the entry is reused though it's stale, when fetching it again failed.
+    /// It's only known to the fetch that failed, so it isn't stored.
+    #[serde(skip)]
+    pub stale: Option<FetchError>,
+}
+
+/// When an entry was fetched, read without its body
+#[derive(Deserialize)]
+struct Age {
+    fetched_at: f64,
+}
+
+impl Entry {
+    /// Whether the entry was fetched less than `ttl` seconds before `now`.
+    /// One from the future, after the clock was turned back, isn't.
+    pub fn is_fresh(&self, now: f64, ttl: u64) -> bool {
+        let age = now - self.fetched_at;
+        (0.0..ttl as f64 * 1000.0).contains(&age)
+    }
+}
+
+/// Returns the entry of `url`, fresh or not
+pub fn load(url: &str, kind: Kind) -> Option<Entry> {
+    storage::load(&format!("{}{url}", kind.prefix()))
+}
+
+/// Stores the entry of `url`, evicting the least recently fetched ones of its kind to keep
+/// at most as many as it may have. If it still doesn't fit, the details are evicted,
+/// then all the others of its kind.
+pub fn save(url: &str, kind: Kind, entry: &Entry) {
+    let key = format!("{}{url}", kind.prefix());
+    evict(kind, &key, kind.max_entries() - 1);
+    for kind in [Kind::Detail, kind] {
+        if storage::save(&key, entry) {
+            return;
+        }
+        evict(kind, &key, 0);
+    }
+    storage::save(&key, entry);
+}
+
+/// Removes entries of `kind` other than the one under `key`, the least recently fetched
+/// first, until `keep` are left
+fn evict(kind: Kind, key: &str, keep: usize) {
+    let entries = storage::keys(kind.prefix())
+        .into_iter()
+        .filter(|k| k != key)
+        .map(|k| {
+            // one that can't be read is of no use, so it goes first
+            let fetched_at = storage::load::<Age>(&k).map_or(f64::MIN, |age| age.fetched_at);
+            (k, fetched_at)
+        })
+        .collect();
+    for key in to_evict(entries, keep) {
+        storage::remove(&key);
+    }
+}
+
+/// Keys of the entries, along with when they were fetched, to remove so that `keep` are left
+fn to_evict(mut entries: Vec<(String, f64)>, keep: usize) -> Vec<String> {
+    entries.sort_by(|a, b| b.1.total_cmp(&a.1));
+    entries.into_iter().skip(keep).map(|(key, _)| key).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case::new(1_000.0, true)]
+    #[case::almost_stale(60_999.0, true)]
+    #[case::stale(61_000.0, false)]
+    #[case::from_the_future(999.0, false)]
+    fn test_is_fresh(#[case] now: f64, #[case] expected: bool) {
+        let entry = Entry {
+            body: String::new(),
+            etag: None,
+            link: None,
+            fetched_at: 1_000.0,
+            stale: None,
+        };
+        assert_eq!(entry.is_fresh(now, 60), expected);
+    }
+
+    #[rstest]
+    #[case::within(3, &[])]
+    #[case::one_over(2, &["a"])]
+    #[case::over(1, &["b", "a"])]
+    #[case::none_kept(0, &["c", "b", "a"])]
+    fn test_to_evict(#[case] keep: usize, #[case] expected: &[&str]) {
+        let entries = vec![
+            ("a".to_owned(), f64::MIN),
+            ("c".to_owned(), 3_000.0),
+            ("b".to_owned(), 2_000.0),
+        ];
+        assert_eq!(to_evict(entries, keep), expected);
+    }
+
+    #[test]
+    fn test_kinds_apart() {
+        let (list, detail) = (Kind::List.prefix(), Kind::Detail.prefix());
+        assert!(!list.starts_with(detail) && !detail.starts_with(list));
+    }
+}
@@ -0,0 +1,137 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::storage;
+
+const STORAGE_KEY: &str = "wcli:fs";
+
+/// The user-writable file layer.
+/// Files live in a flat namespace keyed by their normalized path
+/// and are persisted to localStorage on every change.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Fs {
+    files: BTreeMap<String, String>,
+}
+
+impl Fs {
+    /// Returns the content of the file at `path`, if it exists
+    pub fn read(&self, path: &str) -> Option<&str> {
+        self.files.get(path).map(String::as_str)
+    }
+
+    /// Creates or overwrites the file at `path`
+    pub fn write(&mut self, path: impl Into<String>, content: impl Into<String>) {
+        self.files.insert(path.into(), content.into());
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathError {
+    /// The path is empty after normalization
+    Empty,
+    /// The path points into a directory, which the flat layer doesn't support
+    Directory(String),
+    /// The path contains whitespace or a control character
+    InvalidChar(char),
+}
+
+impl fmt::Display for PathError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "missing file name"),
+            Self::Directory(path) => write!(f, "{path}: directories are not supported"),
+            Self::InvalidChar(c) => write!(f, "invalid character in file name: {c:?}"),
+        }
+    }
+}
+
+/// Normalizes a user-supplied path into a key of the writable layer.
+/// A leading `~/` or `./` is accepted and stripped, as every file lives in the home directory.
+pub fn normalize(path: &str) -> Result<String, PathError> {
+    let name = path
+        .strip_prefix("~/")
+        .or_else(|| path.strip_prefix("./"))
+        .unwrap_or(path);
+
+    if name.is_empty() {
+        return Err(PathError::Empty);
+    }
+
+    if name.contains('/') {
+        return Err(PathError::Directory(path.to_owned()));
+    }
+
+    if let Some(c) = name.chars().find(|c| c.is_whitespace() || c.is_control()) {
+        return Err(PathError::InvalidChar(c));
+    }
+
+    Ok(name.to_owned())
+}
+
+/// Creates a signal of the writable file layer, restored from localStorage,
+/// and provides it as context to the component tree.
+/// It should be called only once in the root component.
+pub fn create_fs() -> (ReadSignal<Fs>, WriteSignal<Fs>) {
+    let fs = RwSignal::new(storage::load::<Fs>(STORAGE_KEY).unwrap_or_default());
+    provide_context(fs);
+
+    // write through to localStorage whenever the layer changes
+    Effect::new(move || {
+        fs.with(|fs| storage::save(STORAGE_KEY, fs));
+    });
+
+    fs.split()
+}
+
+/// Retrieves the writable file layer from the component tree context.
+/// Returns `None` if no file layer has been created.
+pub fn use_fs() -> Option<(ReadSignal<Fs>, WriteSignal<Fs>)> {
+    use_context::<RwSignal<Fs>>().map(|v| v.split())
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[test]
+    fn test_fs_write_and_read() {
+        let mut fs = Fs::default();
+        fs.write("notes.txt", "hello");
+
+        assert_eq!(fs.read("notes.txt"), Some("hello"));
+        assert_eq!(fs.read("missing.txt"), None);
+    }
+
+    #[test]
+    fn test_fs_overwrite() {
+        let mut fs = Fs::default();
+        fs.write("notes.txt", "hello");
+        fs.write("notes.txt", "world");
+
+        assert_eq!(fs.read("notes.txt"), Some("world"));
+    }
+
+    #[rstest]
+    #[case::plain("notes.txt", "notes.txt")]
+    #[case::home("~/notes.txt", "notes.txt")]
+    #[case::relative("./notes.txt", "notes.txt")]
+    #[case::dotfile(".wclirc", ".wclirc")]
+    fn test_normalize(#[case] path: &str, #[case] expected: &str) {
+        assert_eq!(normalize(path), Ok(expected.to_owned()));
+    }
+
+    #[rstest]
+    #[case::empty("", PathError::Empty)]
+    #[case::home_only("~/", PathError::Empty)]
+    #[case::directory("docs/notes.txt", PathError::Directory("docs/notes.txt".to_owned()))]
+    #[case::absolute("/etc/passwd", PathError::Directory("/etc/passwd".to_owned()))]
+    #[case::tab("a\tb", PathError::InvalidChar('\t'))]
+    fn test_normalize_invalid(#[case] path: &str, #[case] expected: PathError) {
+        assert_eq!(normalize(path), Err(expected));
+    }
+}
@@ -2,36 +2,34 @@ use leptos::prelude::{ReadSignal, RwSignal, WriteSignal, provide_context, use_co
 
 #[derive(Debug, Clone)]
 pub struct Entry {
-    timestamp: u64,
+    id: usize,
     pub input: String,
+    pub kind: Kind,
 }
 
 impl Entry {
-    pub fn new(input: String) -> Self {
-        // use SystemTime for testing as js_sys::Date is only available in wasm target
-        #[cfg(test)]
-        let timestamp = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_secs();
-
-        #[cfg(not(test))]
-        let timestamp = web_sys::js_sys::Date::now() as u64;
-
-        Self { timestamp, input }
+    pub fn id(&self) -> usize {
+        self.id
     }
+}
 
-    pub fn id(&self) -> u64 {
-        self.timestamp
-    }
+/// What an entry in the buffer holds
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    /// A command typed at the prompt, which is run when rendered
+    Command,
+    /// A message from the shell itself, e.g. that a background job is done
+    Notice,
 }
 
 #[derive(Debug, Clone)]
 pub struct History {
     /// A persistent, dedup-on-push command history (no consecutive duplicates)
     commands: Vec<String>,
-    /// A temporary, clearable store for timestamped history entries.
+    /// A temporary, clearable store for history entries.
     buffer: Vec<Entry>,
+    /// Id of the next entry, unique even across clears
+    next_id: usize,
 }
 
 impl History {
@@ -39,13 +37,14 @@ impl History {
         Self {
             commands: Vec::new(),
             buffer: Vec::new(),
+            next_id: 0,
         }
     }
 
     pub fn push(&mut self, input: impl Into<String>) {
         let input = input.into();
 
-        self.buffer.push(Entry::new(input.clone()));
+        self.append(input.clone(), Kind::Command);
 
         // only push if commands is empty (i.e., .last() returns None)
         // or the last command is not the same as the new command
@@ -54,6 +53,26 @@ impl History {
         }
     }
 
+    /// Adds a command run by a script to the buffer.
+    /// It's rendered as if typed, but isn't recalled with the arrow keys.
+    pub fn replay(&mut self, input: impl Into<String>) {
+        self.append(input.into(), Kind::Command);
+    }
+
+    /// Adds a notice to the buffer, leaving the command history untouched
+    pub fn notify(&mut self, text: impl Into<String>) {
+        self.append(text.into(), Kind::Notice);
+    }
+
+    fn append(&mut self, input: String, kind: Kind) {
+        self.buffer.push(Entry {
+            id: self.next_id,
+            input,
+            kind,
+        });
+        self.next_id += 1;
+    }
+
     pub fn clear(&mut self) {
         self.buffer.clear();
     }
@@ -152,4 +171,39 @@ mod tests {
         assert!(history.buffer().is_empty());
         assert_eq!(history.commands(), &["echo", "clear"]); // commands should remain
     }
+
+    #[test]
+    fn test_history_notify() {
+        let mut history = History::new();
+        history.push("projects &");
+        history.notify("[1]+ Done projects");
+
+        assert_eq!(history.commands(), &["projects &"]);
+        assert_eq!(
+            history.buffer().iter().map(|e| e.kind).collect::<Vec<_>>(),
+            &[Kind::Command, Kind::Notice]
+        );
+    }
+
+    #[test]
+    fn test_history_replay() {
+        let mut history = History::new();
+        history.replay("fetch");
+
+        assert!(history.commands().is_empty());
+        assert_eq!(history.buffer()[0].input, "fetch");
+        assert_eq!(history.buffer()[0].kind, Kind::Command);
+    }
+
+    #[test]
+    fn test_history_ids_are_unique() {
+        let mut history = History::new();
+        history.push("echo");
+        history.clear();
+        history.push("echo");
+        history.notify("done");
+
+        let ids = history.buffer().iter().map(|e| e.id()).collect::<Vec<_>>();
+        assert_eq!(ids, &[1, 2]);
+    }
 }
@@ -1,2 +1,5 @@
+pub mod cache;
+pub mod fs;
 pub mod history;
+pub mod storage;
 pub mod theme;
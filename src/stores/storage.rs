@@ -0,0 +1,58 @@
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use web_sys::Storage;
+
+/// Returns the browser's localStorage, if it is available.
+/// It can be missing, for example, when the user disables site data.
+fn local_storage() -> Option<Storage> {
+    leptos::prelude::window().local_storage().ok().flatten()
+}
+
+/// Reads a JSON value stored under `key` in localStorage.
+/// Returns `None` if the key is missing, storage is unavailable, or the value is malformed.
+pub fn load<T: DeserializeOwned>(key: &str) -> Option<T> {
+    let raw = local_storage()?.get_item(key).ok()??;
+    serde_json::from_str(&raw).ok()
+}
+
+/// Writes `value` as JSON under `key` in localStorage, and returns whether it was written.
+/// Failures (e.g. quota exceeded) are logged rather than surfaced,
+/// as persistence is a best-effort convenience.
+pub fn save<T: Serialize>(key: &str, value: &T) -> bool {
+    let Some(storage) = local_storage() else {
+        return false;
+    };
+
+    match serde_json::to_string(value) {
+        Ok(raw) => {
+            let saved = storage.set_item(key, &raw).is_ok();
+            if !saved {
+                log::warn!("failed to persist '{key}' to localStorage");
+            }
+            saved
+        },
+        Err(e) => {
+            log::warn!("failed to serialize '{key}': {e}");
+            false
+        },
+    }
+}
+
+/// Removes the value under `key` from localStorage, if there is one
+pub fn remove(key: &str) {
+    if let Some(storage) = local_storage() {
+        _ = storage.remove_item(key);
+    }
+}
+
+/// Keys in localStorage that start with `prefix`
+pub fn keys(prefix: &str) -> Vec<String> {
+    let Some(storage) = local_storage() else {
+        return Vec::new();
+    };
+    let len = storage.length().unwrap_or_default();
+    (0..len)
+        .filter_map(|i| storage.key(i).ok().flatten())
+        .filter(|key| key.starts_with(prefix))
+        .collect()
+}